@@ -0,0 +1,665 @@
+//! Support for age (<https://age-encryption.org/v1>) `X25519` and
+//! `ssh-ed25519` recipient stanzas.
+//!
+//! This module implements the key agreement layer of age: wrapping and
+//! unwrapping file keys, as well as the encoding of recipients and
+//! identities. The header MAC and the payload encryption are left to the
+//! application. Support for `ssh-ed25519` recipients is only available if
+//! signatures are not disabled.
+
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+
+use ct_codecs::{Base64NoPadding, Decoder, Encoder};
+
+use super::bech32;
+use super::chacha20poly1305;
+use super::common::*;
+use super::error::Error;
+use super::sha256::Hkdf;
+use super::x25519;
+
+const X25519_TAG: &str = "X25519";
+const X25519_LABEL: &[u8] = b"age-encryption.org/v1/X25519";
+
+const RECIPIENT_HRP: &str = "age";
+const IDENTITY_HRP: &str = "AGE-SECRET-KEY-";
+
+const STANZA_PREFIX: &str = "-> ";
+const COLUMNS: usize = 64;
+
+/// A file key, the symmetric key that recipient stanzas wrap.
+#[derive(Clone, Eq)]
+pub struct FileKey([u8; FileKey::BYTES]);
+
+impl fmt::Debug for FileKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("FileKey(..)")
+    }
+}
+
+impl PartialEq for FileKey {
+    /// Compares two file keys in constant time.
+    fn eq(&self, other: &Self) -> bool {
+        Mem::ct_eq(&self.0, &other.0)
+    }
+}
+
+impl Hash for FileKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+impl FileKey {
+    /// Number of raw bytes in a file key.
+    pub const BYTES: usize = 16;
+
+    /// Creates a file key from raw bytes.
+    pub fn new(key: [u8; FileKey::BYTES]) -> Self {
+        FileKey(key)
+    }
+
+    /// Generates a new random file key.
+    #[cfg(feature = "random")]
+    pub fn generate() -> Self {
+        let mut key = [0u8; FileKey::BYTES];
+        getrandom::fill(&mut key).expect("RNG failure");
+        FileKey(key)
+    }
+}
+
+impl Drop for FileKey {
+    fn drop(&mut self) {
+        Mem::wipe(&mut self.0)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for FileKey {
+    fn zeroize(&mut self) {
+        Mem::wipe(&mut self.0)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for FileKey {}
+
+impl Deref for FileKey {
+    type Target = [u8; FileKey::BYTES];
+
+    /// Returns the file key as bytes.
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+const BODY_BYTES: usize = FileKey::BYTES + chacha20poly1305::TAG_BYTES;
+
+/// Derives a wrapping key from a shared secret.
+fn wrap_key(
+    shared_secret: &x25519::DHOutput,
+    share: &x25519::PublicKey,
+    pk: &x25519::PublicKey,
+    label: &[u8],
+) -> [u8; 32] {
+    let mut salt = [0u8; 64];
+    salt[..32].copy_from_slice(&share[..]);
+    salt[32..].copy_from_slice(&pk[..]);
+    let mut prk = Hkdf::extract(salt, &shared_secret[..]);
+    let mut key = [0u8; 32];
+    Hkdf::expand(&mut key, &prk, label);
+    Mem::wipe(&mut prk);
+    key
+}
+
+fn wrap_file_key(key: &[u8; 32], file_key: &FileKey) -> [u8; BODY_BYTES] {
+    let mut body = [0u8; BODY_BYTES];
+    body[..FileKey::BYTES].copy_from_slice(&file_key[..]);
+    let tag = chacha20poly1305::seal(key, &[0u8; 12], &[], &mut body[..FileKey::BYTES]);
+    body[FileKey::BYTES..].copy_from_slice(&tag);
+    body
+}
+
+fn unwrap_file_key(key: &[u8; 32], body: &[u8; BODY_BYTES]) -> Result<FileKey, Error> {
+    let mut file_key = FileKey([0u8; FileKey::BYTES]);
+    file_key.0.copy_from_slice(&body[..FileKey::BYTES]);
+    let mut tag = [0u8; chacha20poly1305::TAG_BYTES];
+    tag.copy_from_slice(&body[FileKey::BYTES..]);
+    chacha20poly1305::open(key, &[0u8; 12], &[], &mut file_key.0, &tag)?;
+    Ok(file_key)
+}
+
+fn decode_base64<const N: usize>(b64: &str) -> Result<[u8; N], Error> {
+    let mut bin = [0u8; N];
+    let len = Base64NoPadding::decode(&mut bin, b64, None)
+        .map_err(|_| Error::ParseError)?
+        .len();
+    if len != N {
+        return Err(Error::ParseError);
+    }
+    Ok(bin)
+}
+
+/// Writes `bin` as unpadded base64 to a formatter.
+fn write_base64(f: &mut fmt::Formatter<'_>, bin: &[u8]) -> fmt::Result {
+    let mut b64 = [0u8; 128];
+    let b64 = Base64NoPadding::encode(&mut b64, bin).map_err(|_| fmt::Error)?;
+    f.write_str(core::str::from_utf8(b64).map_err(|_| fmt::Error)?)
+}
+
+/// A recipient stanza, as found in the header of an age file.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Stanza<'a> {
+    tag: &'a str,
+    args: &'a str,
+    body: &'a str,
+}
+
+impl<'a> Stanza<'a> {
+    /// Parses a stanza, and returns it along with the text that follows it.
+    pub fn parse(text: &'a str) -> Result<(Self, &'a str), Error> {
+        let text = text.strip_prefix(STANZA_PREFIX).ok_or(Error::ParseError)?;
+        let eol = text.find('\n').ok_or(Error::ParseError)?;
+        let (line, mut rest) = (&text[..eol], &text[eol + 1..]);
+        let (tag, args) = match line.find(' ') {
+            Some(sep) => (&line[..sep], &line[sep + 1..]),
+            None => (line, ""),
+        };
+        if tag.is_empty() || line.split(' ').any(|arg| arg.is_empty()) {
+            return Err(Error::ParseError);
+        }
+        let body_start = rest;
+        // The body ends with the first line shorter than 64 characters.
+        loop {
+            let eol = rest.find('\n').ok_or(Error::ParseError)?;
+            rest = &rest[eol + 1..];
+            if eol > COLUMNS {
+                return Err(Error::ParseError);
+            }
+            if eol < COLUMNS {
+                break;
+            }
+        }
+        let body = &body_start[..body_start.len() - rest.len()];
+        Ok((Stanza { tag, args, body }, rest))
+    }
+
+    /// Returns the stanza type.
+    pub fn tag(&self) -> &'a str {
+        self.tag
+    }
+
+    /// Returns the arguments of the stanza.
+    pub fn args(&self) -> impl Iterator<Item = &'a str> {
+        self.args.split(' ').filter(|arg| !arg.is_empty())
+    }
+
+    /// Decodes the body of the stanza into `out`.
+    pub fn body<'t>(&self, out: &'t mut [u8]) -> Result<&'t [u8], Error> {
+        Base64NoPadding::decode(out, self.body, Some(b"\n")).map_err(|_| Error::ParseError)
+    }
+
+    fn x25519_body(&self) -> Result<[u8; BODY_BYTES], Error> {
+        let mut body = [0u8; BODY_BYTES + 1];
+        if self.body(&mut body)?.len() != BODY_BYTES {
+            return Err(Error::ParseError);
+        }
+        let mut res = [0u8; BODY_BYTES];
+        res.copy_from_slice(&body[..BODY_BYTES]);
+        Ok(res)
+    }
+}
+
+/// A wrapped file key for a `X25519` recipient.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct X25519Stanza {
+    ephemeral_share: x25519::PublicKey,
+    body: [u8; BODY_BYTES],
+}
+
+impl fmt::Display for X25519Stanza {
+    /// Formats the stanza as it appears in an age header.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{} ", STANZA_PREFIX, X25519_TAG)?;
+        write_base64(f, &self.ephemeral_share[..])?;
+        writeln!(f)?;
+        write_base64(f, &self.body)?;
+        writeln!(f)
+    }
+}
+
+/// An age `X25519` recipient, encoded as `age1...`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Recipient(x25519::PublicKey);
+
+impl Recipient {
+    /// Creates a recipient from a X25519 public key.
+    pub fn new(pk: x25519::PublicKey) -> Self {
+        Recipient(pk)
+    }
+
+    /// Returns the X25519 public key.
+    pub fn key(&self) -> &x25519::PublicKey {
+        &self.0
+    }
+
+    /// Decodes a `age1...` string.
+    pub fn decode(s: &str) -> Result<Self, Error> {
+        let mut pk = [0u8; x25519::PublicKey::BYTES];
+        let (hrp, data) = bech32::decode(s, &mut pk)?;
+        if !hrp.eq_ignore_ascii_case(RECIPIENT_HRP) || data.len() != x25519::PublicKey::BYTES {
+            return Err(Error::ParseError);
+        }
+        Ok(Recipient(x25519::PublicKey::new(pk)))
+    }
+
+    /// Wraps a file key using a random ephemeral key pair.
+    #[cfg(feature = "random")]
+    pub fn wrap(&self, file_key: &FileKey) -> Result<X25519Stanza, Error> {
+        self.wrap_with_ephemeral(file_key, &x25519::KeyPair::generate())
+    }
+
+    /// Wraps a file key using the given ephemeral key pair, which must not be
+    /// reused.
+    pub fn wrap_with_ephemeral(
+        &self,
+        file_key: &FileKey,
+        ephemeral: &x25519::KeyPair,
+    ) -> Result<X25519Stanza, Error> {
+        let shared_secret = self.0.dh(&ephemeral.sk)?;
+        let mut key = wrap_key(&shared_secret, &ephemeral.pk, &self.0, X25519_LABEL);
+        let body = wrap_file_key(&key, file_key);
+        Mem::wipe(&mut key);
+        Ok(X25519Stanza {
+            ephemeral_share: ephemeral.pk,
+            body,
+        })
+    }
+}
+
+impl fmt::Display for Recipient {
+    /// Formats the recipient as a `age1...` string.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        bech32::encode(f, RECIPIENT_HRP, &self.0[..], false)
+    }
+}
+
+/// An age `X25519` identity, encoded as `AGE-SECRET-KEY-1...`.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Identity {
+    sk: x25519::SecretKey,
+    pk: x25519::PublicKey,
+}
+
+impl Identity {
+    /// Creates an identity from a X25519 secret key.
+    pub fn new(sk: x25519::SecretKey) -> Result<Self, Error> {
+        let pk = sk.recover_public_key()?;
+        Ok(Identity { sk, pk })
+    }
+
+    /// Generates a new random identity.
+    #[cfg(feature = "random")]
+    pub fn generate() -> Self {
+        let kp = x25519::KeyPair::generate();
+        Identity {
+            sk: kp.sk.clone(),
+            pk: kp.pk,
+        }
+    }
+
+    /// Returns the X25519 secret key.
+    pub fn key(&self) -> &x25519::SecretKey {
+        &self.sk
+    }
+
+    /// Returns the recipient for this identity.
+    pub fn recipient(&self) -> Recipient {
+        Recipient(self.pk)
+    }
+
+    /// Decodes a `AGE-SECRET-KEY-1...` string.
+    pub fn decode(s: &str) -> Result<Self, Error> {
+        let mut sk = [0u8; x25519::SecretKey::BYTES];
+        let res = match bech32::decode(s, &mut sk) {
+            Ok((hrp, data))
+                if hrp.eq_ignore_ascii_case(IDENTITY_HRP)
+                    && data.len() == x25519::SecretKey::BYTES =>
+            {
+                Ok(())
+            }
+            _ => Err(Error::ParseError),
+        }
+        .and_then(|_| Identity::new(x25519::SecretKey::new(sk)));
+        Mem::wipe(&mut sk);
+        res
+    }
+
+    /// Unwraps the file key from a stanza.
+    ///
+    /// Returns `Ok(None)` if the stanza is not a `X25519` stanza, or was not
+    /// created for this identity.
+    pub fn unwrap(&self, stanza: &Stanza<'_>) -> Result<Option<FileKey>, Error> {
+        if stanza.tag != X25519_TAG {
+            return Ok(None);
+        }
+        let mut args = stanza.args();
+        let share = match (args.next(), args.next()) {
+            (Some(share), None) => x25519::PublicKey::new(decode_base64(share)?),
+            _ => return Err(Error::ParseError),
+        };
+        let body = stanza.x25519_body()?;
+        let shared_secret = share.dh(&self.sk)?;
+        let mut key = wrap_key(&shared_secret, &share, &self.pk, X25519_LABEL);
+        let file_key = unwrap_file_key(&key, &body).ok();
+        Mem::wipe(&mut key);
+        Ok(file_key)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for Identity {
+    fn zeroize(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.sk)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for Identity {}
+
+impl fmt::Display for Identity {
+    /// Formats the identity as a `AGE-SECRET-KEY-1...` string.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        bech32::encode(f, IDENTITY_HRP, &self.sk[..], true)
+    }
+}
+
+#[cfg(not(feature = "disable-signatures"))]
+mod ssh {
+    use super::super::{sha256, KeyPair as EdKeyPair, PublicKey as EdPublicKey};
+    use super::*;
+    use ct_codecs::Base64;
+
+    const SSH_ED25519_TAG: &str = "ssh-ed25519";
+    const SSH_ED25519_LABEL: &[u8] = b"age-encryption.org/v1/ssh-ed25519";
+    const SSH_KEY_BYTES: usize = 4 + 11 + 4 + EdPublicKey::BYTES;
+    const KEY_TAG_BYTES: usize = 4;
+
+    /// Returns the SSH wire encoding of an Ed25519 public key.
+    fn ssh_key(edpk: &EdPublicKey) -> [u8; SSH_KEY_BYTES] {
+        let mut ssh_key = [0u8; SSH_KEY_BYTES];
+        ssh_key[0..4].copy_from_slice(&11u32.to_be_bytes());
+        ssh_key[4..15].copy_from_slice(SSH_ED25519_TAG.as_bytes());
+        ssh_key[15..19].copy_from_slice(&32u32.to_be_bytes());
+        ssh_key[19..].copy_from_slice(&edpk[..]);
+        ssh_key
+    }
+
+    fn key_tag(ssh_key: &[u8; SSH_KEY_BYTES]) -> [u8; KEY_TAG_BYTES] {
+        let mut tag = [0u8; KEY_TAG_BYTES];
+        tag.copy_from_slice(&sha256::Hash::hash(&ssh_key[..])[..KEY_TAG_BYTES]);
+        tag
+    }
+
+    fn tweak(ssh_key: &[u8; SSH_KEY_BYTES]) -> x25519::SecretKey {
+        let prk = Hkdf::extract(&ssh_key[..], []);
+        let mut tweak = [0u8; x25519::SecretKey::BYTES];
+        Hkdf::expand(&mut tweak, &prk, SSH_ED25519_LABEL);
+        x25519::SecretKey::new(tweak)
+    }
+
+    /// A wrapped file key for a `ssh-ed25519` recipient.
+    #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+    pub struct SshEd25519Stanza {
+        key_tag: [u8; KEY_TAG_BYTES],
+        ephemeral_share: x25519::PublicKey,
+        body: [u8; BODY_BYTES],
+    }
+
+    impl fmt::Display for SshEd25519Stanza {
+        /// Formats the stanza as it appears in an age header.
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{}{} ", STANZA_PREFIX, SSH_ED25519_TAG)?;
+            write_base64(f, &self.key_tag)?;
+            f.write_str(" ")?;
+            write_base64(f, &self.ephemeral_share[..])?;
+            writeln!(f)?;
+            write_base64(f, &self.body)?;
+            writeln!(f)
+        }
+    }
+
+    /// An age `ssh-ed25519` recipient.
+    #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+    pub struct SshEd25519Recipient {
+        ssh_key: [u8; SSH_KEY_BYTES],
+        pk: x25519::PublicKey,
+    }
+
+    impl SshEd25519Recipient {
+        /// Creates a recipient from an Ed25519 public key.
+        pub fn from_ed25519(edpk: &EdPublicKey) -> Result<Self, Error> {
+            Ok(SshEd25519Recipient {
+                ssh_key: ssh_key(edpk),
+                pk: x25519::PublicKey::from_ed25519(edpk)?,
+            })
+        }
+
+        /// Decodes an OpenSSH public key line (`ssh-ed25519 AAAA... [comment]`).
+        pub fn decode(s: &str) -> Result<Self, Error> {
+            let mut parts = s.trim().split(' ').filter(|part| !part.is_empty());
+            if parts.next() != Some(SSH_ED25519_TAG) {
+                return Err(Error::ParseError);
+            }
+            let b64 = parts.next().ok_or(Error::ParseError)?;
+            let mut bin = [0u8; SSH_KEY_BYTES];
+            let len = Base64::decode(&mut bin, b64, None)
+                .map_err(|_| Error::ParseError)?
+                .len();
+            if len != SSH_KEY_BYTES {
+                return Err(Error::ParseError);
+            }
+            let edpk = EdPublicKey::from_slice(&bin[19..])?;
+            if ssh_key(&edpk) != bin {
+                return Err(Error::ParseError);
+            }
+            Self::from_ed25519(&edpk)
+        }
+
+        /// Wraps a file key using a random ephemeral key pair.
+        #[cfg(feature = "random")]
+        pub fn wrap(&self, file_key: &FileKey) -> Result<SshEd25519Stanza, Error> {
+            self.wrap_with_ephemeral(file_key, &x25519::KeyPair::generate())
+        }
+
+        /// Wraps a file key using the given ephemeral key pair, which must not
+        /// be reused.
+        pub fn wrap_with_ephemeral(
+            &self,
+            file_key: &FileKey,
+            ephemeral: &x25519::KeyPair,
+        ) -> Result<SshEd25519Stanza, Error> {
+            let shared_secret =
+                x25519::PublicKey::from(self.pk.dh(&ephemeral.sk)?).dh(&tweak(&self.ssh_key))?;
+            let mut key = wrap_key(&shared_secret, &ephemeral.pk, &self.pk, SSH_ED25519_LABEL);
+            let body = wrap_file_key(&key, file_key);
+            Mem::wipe(&mut key);
+            Ok(SshEd25519Stanza {
+                key_tag: key_tag(&self.ssh_key),
+                ephemeral_share: ephemeral.pk,
+                body,
+            })
+        }
+    }
+
+    impl fmt::Display for SshEd25519Recipient {
+        /// Formats the recipient as an OpenSSH public key line.
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let mut b64 = [0u8; 72];
+            let b64 = Base64::encode(&mut b64, self.ssh_key).map_err(|_| fmt::Error)?;
+            write!(f, "{} ", SSH_ED25519_TAG)?;
+            f.write_str(core::str::from_utf8(b64).map_err(|_| fmt::Error)?)
+        }
+    }
+
+    /// An age `ssh-ed25519` identity.
+    #[derive(Clone, Debug, Eq, PartialEq, Hash)]
+    pub struct SshEd25519Identity {
+        ssh_key: [u8; SSH_KEY_BYTES],
+        pk: x25519::PublicKey,
+        sk: x25519::SecretKey,
+    }
+
+    impl SshEd25519Identity {
+        /// Creates an identity from an Ed25519 key pair.
+        pub fn from_ed25519(edkp: &EdKeyPair) -> Result<Self, Error> {
+            let kp = x25519::KeyPair::from_ed25519(edkp)?;
+            Ok(SshEd25519Identity {
+                ssh_key: ssh_key(&edkp.pk),
+                pk: kp.pk,
+                sk: kp.sk.clone(),
+            })
+        }
+
+        /// Returns the recipient for this identity.
+        pub fn recipient(&self) -> SshEd25519Recipient {
+            SshEd25519Recipient {
+                ssh_key: self.ssh_key,
+                pk: self.pk,
+            }
+        }
+
+        /// Unwraps the file key from a stanza.
+        ///
+        /// Returns `Ok(None)` if the stanza is not a `ssh-ed25519` stanza, or
+        /// was created for a different key.
+        pub fn unwrap(&self, stanza: &Stanza<'_>) -> Result<Option<FileKey>, Error> {
+            if stanza.tag != SSH_ED25519_TAG {
+                return Ok(None);
+            }
+            let mut args = stanza.args();
+            let (key_tag_b64, share) = match (args.next(), args.next(), args.next()) {
+                (Some(key_tag), Some(share), None) => (key_tag, share),
+                _ => return Err(Error::ParseError),
+            };
+            let share = x25519::PublicKey::new(decode_base64(share)?);
+            let body = stanza.x25519_body()?;
+            if decode_base64::<KEY_TAG_BYTES>(key_tag_b64)? != key_tag(&self.ssh_key) {
+                return Ok(None);
+            }
+            let shared_secret =
+                x25519::PublicKey::from(share.dh(&self.sk)?).dh(&tweak(&self.ssh_key))?;
+            let mut key = wrap_key(&shared_secret, &share, &self.pk, SSH_ED25519_LABEL);
+            let file_key = unwrap_file_key(&key, &body);
+            Mem::wipe(&mut key);
+            file_key.map(Some)
+        }
+    }
+
+    #[cfg(feature = "zeroize")]
+    impl zeroize::Zeroize for SshEd25519Identity {
+        fn zeroize(&mut self) {
+            zeroize::Zeroize::zeroize(&mut self.sk)
+        }
+    }
+
+    #[cfg(feature = "zeroize")]
+    impl zeroize::ZeroizeOnDrop for SshEd25519Identity {}
+}
+
+#[cfg(not(feature = "disable-signatures"))]
+pub use ssh::*;
+
+#[cfg(feature = "std")]
+#[test]
+fn test_age_x25519() {
+    let identity = Identity::decode(
+        "AGE-SECRET-KEY-1GFPYYSJZGFPYYSJZGFPYYSJZGFPYYSJZGFPYYSJZGFPYYSJZGFPQ4EGAEX",
+    )
+    .unwrap();
+    let recipient = identity.recipient();
+    assert_eq!(
+        recipient.to_string(),
+        "age1zvkyg2lqzraa2lnjvqej32nkuu0ues2s82hzrye869xeexvn73equnujwj"
+    );
+    assert_eq!(
+        Recipient::decode(&recipient.to_string()).unwrap(),
+        recipient
+    );
+    assert_eq!(Identity::decode(&identity.to_string()).unwrap(), identity);
+    assert!(
+        Recipient::decode("age1zvkyg2lqzraa2lnjvqej32nkuu0ues2s82hzrye869xeexvn73equnujwk")
+            .is_err()
+    );
+
+    let (stanza, rest) = Stanza::parse(
+        "-> X25519 f2HViXLPT9zZcQWDgl3dNYb6ltgi0iO3ORBI4TpSciA
+y+cKGFkCW3f4o2W4focJs/A3xty5j0Fk4g03l2dRVsg
+--- ",
+    )
+    .unwrap();
+    assert_eq!(rest, "--- ");
+    let file_key = identity.unwrap(&stanza).unwrap().unwrap();
+    assert_eq!(*file_key, [0x42u8; 16]);
+
+    let other = Identity::new(x25519::SecretKey::new([1u8; 32])).unwrap();
+    assert!(other.unwrap(&stanza).unwrap().is_none());
+
+    let ephemeral = x25519::KeyPair {
+        pk: x25519::SecretKey::new([2u8; 32])
+            .recover_public_key()
+            .unwrap(),
+        sk: x25519::SecretKey::new([2u8; 32]),
+    };
+    let file_key = FileKey::new([0x17u8; 16]);
+    let text = recipient
+        .wrap_with_ephemeral(&file_key, &ephemeral)
+        .unwrap()
+        .to_string();
+    let (stanza, _) = Stanza::parse(&text).unwrap();
+    assert_eq!(identity.unwrap(&stanza).unwrap(), Some(file_key));
+}
+
+#[cfg(all(feature = "std", not(feature = "disable-signatures")))]
+#[test]
+fn test_age_ssh_ed25519() {
+    use super::{KeyPair as EdKeyPair, Seed};
+
+    let edkp = EdKeyPair::from_seed(Seed::new([0x33u8; 32]));
+    let identity = SshEd25519Identity::from_ed25519(&edkp).unwrap();
+    let line = "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIBfLefsrQSDysexl5BmNbgiyjoE/6wHkpACDm4XhgIDO";
+    let recipient = SshEd25519Recipient::decode(&format!("{} user@host", line)).unwrap();
+    assert_eq!(recipient, identity.recipient());
+    assert_eq!(recipient.to_string(), line);
+
+    let (stanza, _) = Stanza::parse(
+        "-> ssh-ed25519 58W2hQ QrRb7KAIQrU6m5caSb3X9LwoLbkJAlmyLqNUD1NvWzU
+OmGn8lC/SCP+kPj8poQVdgn9G3nYFOa9rilJHfW8+tc
+",
+    )
+    .unwrap();
+    let file_key = identity.unwrap(&stanza).unwrap().unwrap();
+    assert_eq!(*file_key, [0x42u8; 16]);
+
+    let x25519_identity = Identity::new(x25519::SecretKey::new([1u8; 32])).unwrap();
+    assert!(x25519_identity.unwrap(&stanza).unwrap().is_none());
+    let other =
+        SshEd25519Identity::from_ed25519(&EdKeyPair::from_seed(Seed::new([0x34u8; 32]))).unwrap();
+    assert!(other.unwrap(&stanza).unwrap().is_none());
+
+    let ephemeral = x25519::KeyPair {
+        pk: x25519::SecretKey::new([2u8; 32])
+            .recover_public_key()
+            .unwrap(),
+        sk: x25519::SecretKey::new([2u8; 32]),
+    };
+    let file_key = FileKey::new([0x17u8; 16]);
+    let text = recipient
+        .wrap_with_ephemeral(&file_key, &ephemeral)
+        .unwrap()
+        .to_string();
+    let (stanza, _) = Stanza::parse(&text).unwrap();
+    assert_eq!(identity.unwrap(&stanza).unwrap(), Some(file_key));
+}
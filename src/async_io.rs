@@ -0,0 +1,153 @@
+//! `futures::io` adapters for streaming hashing, signing and verification.
+//!
+//! The hash function and the streaming states implement `AsyncWrite`, and
+//! `SecretKey::sign_async_reader()`/`PublicKey::verify_async_reader()` consume
+//! an `AsyncRead` stream. Tokio streams can be used through the
+//! `tokio_util::compat` adapters.
+
+#[cfg(not(feature = "disable-signatures"))]
+use core::future::poll_fn;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use std::io;
+
+#[cfg(not(feature = "disable-signatures"))]
+use futures_io::AsyncRead;
+use futures_io::AsyncWrite;
+
+use super::sha512;
+#[cfg(not(feature = "disable-signatures"))]
+use super::{Noise, PublicKey, SecretKey, Signature, SigningState, VerifyingState};
+
+#[cfg(not(feature = "disable-signatures"))]
+const BUFFER_BYTES: usize = 8192;
+
+macro_rules! impl_async_write {
+    ($t:ty, $absorb:ident) => {
+        impl AsyncWrite for $t {
+            fn poll_write(
+                self: Pin<&mut Self>,
+                _cx: &mut Context<'_>,
+                buf: &[u8],
+            ) -> Poll<io::Result<usize>> {
+                self.get_mut().$absorb(buf);
+                Poll::Ready(Ok(buf.len()))
+            }
+
+            fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+                Poll::Ready(Ok(()))
+            }
+
+            fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+                Poll::Ready(Ok(()))
+            }
+        }
+    };
+}
+
+impl_async_write!(sha512::Hash, update);
+#[cfg(not(feature = "disable-signatures"))]
+impl_async_write!(SigningState, absorb);
+#[cfg(not(feature = "disable-signatures"))]
+impl_async_write!(VerifyingState, absorb);
+
+/// Reads `reader` until the end of the stream, calling `f` with every chunk.
+#[cfg(not(feature = "disable-signatures"))]
+async fn for_each_chunk(
+    mut reader: impl AsyncRead + Unpin,
+    mut f: impl FnMut(&[u8]),
+) -> io::Result<()> {
+    let mut buf = [0u8; BUFFER_BYTES];
+    loop {
+        let n = poll_fn(|cx| Pin::new(&mut reader).poll_read(cx, &mut buf)).await;
+        match n {
+            Ok(0) => return Ok(()),
+            Ok(n) => f(&buf[..n]),
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+#[cfg(not(feature = "disable-signatures"))]
+impl SecretKey {
+    /// Computes a signature for everything that can be read from `reader`.
+    /// It is critical for `noise` to never repeat.
+    ///
+    /// The message is read only once, so the signature is computed using the
+    /// streaming API, and differs from the one `sign()` would return.
+    pub async fn sign_async_reader(
+        &self,
+        reader: impl AsyncRead + Unpin,
+        noise: Noise,
+    ) -> io::Result<Signature> {
+        let mut st = self.sign_incremental(noise);
+        for_each_chunk(reader, |chunk| st.absorb(chunk)).await?;
+        Ok(st.sign())
+    }
+}
+
+#[cfg(not(feature = "disable-signatures"))]
+impl PublicKey {
+    /// Verifies that the signature `signature` is valid for everything that
+    /// can be read from `reader`.
+    ///
+    /// An invalid signature is reported as an `InvalidData` error, wrapping
+    /// the underlying `Error`.
+    pub async fn verify_async_reader(
+        &self,
+        reader: impl AsyncRead + Unpin,
+        signature: &Signature,
+    ) -> io::Result<()> {
+        let invalid = |e| io::Error::new(io::ErrorKind::InvalidData, e);
+        let mut st = self.verify_incremental(signature).map_err(invalid)?;
+        for_each_chunk(reader, |chunk| st.absorb(chunk)).await?;
+        st.verify().map_err(invalid)
+    }
+}
+
+#[cfg(all(test, not(feature = "disable-signatures")))]
+fn block_on<F: core::future::Future>(f: F) -> F::Output {
+    let mut f = core::pin::pin!(f);
+    let mut cx = Context::from_waker(core::task::Waker::noop());
+    loop {
+        if let Poll::Ready(output) = f.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
+
+#[cfg(not(feature = "disable-signatures"))]
+#[test]
+fn test_async_sign_reader() {
+    use super::{Error, KeyPair};
+
+    let kp = KeyPair::from_seed([42u8; 32].into());
+    let msg = [0x42u8; 100_000];
+    let signature = block_on(
+        kp.sk
+            .sign_async_reader(&msg[..], Noise::from_slice(&[1u8; 16]).unwrap()),
+    )
+    .unwrap();
+    kp.pk.verify(&msg[..], &signature).unwrap();
+    block_on(kp.pk.verify_async_reader(&msg[..], &signature)).unwrap();
+    let err = block_on(kp.pk.verify_async_reader(&msg[1..], &signature)).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert_eq!(
+        err.into_inner().unwrap().downcast::<Error>().unwrap(),
+        Box::new(Error::SignatureMismatch)
+    );
+
+    let mut st = kp.pk.verify_incremental(&signature).unwrap();
+    let mut written = 0;
+    while written < msg.len() {
+        match Pin::new(&mut st).poll_write(
+            &mut Context::from_waker(core::task::Waker::noop()),
+            &msg[written..],
+        ) {
+            Poll::Ready(Ok(n)) => written += n,
+            _ => unreachable!(),
+        }
+    }
+    st.verify().unwrap();
+}
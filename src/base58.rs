@@ -0,0 +1,113 @@
+//! Base58 encoding, with the Bitcoin alphabet.
+//!
+//! This is not constant-time, and must only be used for public data.
+
+use super::error::Error;
+
+const ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Moves the `len` little-endian digits at the beginning of `buf` after
+/// `zeros` zero digits, and reverses them. Returns the total length.
+fn finalize(buf: &mut [u8], zeros: usize, len: usize) -> Result<usize, Error> {
+    let total = zeros + len;
+    if total > buf.len() {
+        return Err(Error::BufferTooSmall);
+    }
+    buf.copy_within(0..len, zeros);
+    buf[..zeros].iter_mut().for_each(|x| *x = 0);
+    buf[zeros..total].reverse();
+    Ok(total)
+}
+
+/// Encodes `bin` into `b58`.
+#[cfg(feature = "std")]
+pub fn encode<'t>(b58: &'t mut [u8], bin: &[u8]) -> Result<&'t str, Error> {
+    let zeros = bin.iter().take_while(|&&x| x == 0).count();
+    let mut len = 0;
+    for &x in &bin[zeros..] {
+        let mut carry = x as u32;
+        for digit in b58[..len].iter_mut() {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            if len == b58.len() {
+                return Err(Error::BufferTooSmall);
+            }
+            b58[len] = (carry % 58) as u8;
+            carry /= 58;
+            len += 1;
+        }
+    }
+    let total = finalize(b58, zeros, len)?;
+    for c in b58[..total].iter_mut() {
+        *c = ALPHABET[*c as usize];
+    }
+    Ok(core::str::from_utf8(&b58[..total]).unwrap())
+}
+
+/// Decodes `b58` into `bin`.
+pub fn decode<'t>(bin: &'t mut [u8], b58: &str) -> Result<&'t [u8], Error> {
+    let b58 = b58.as_bytes();
+    let zeros = b58.iter().take_while(|&&c| c == ALPHABET[0]).count();
+    let mut len = 0;
+    for &c in &b58[zeros..] {
+        let mut carry = ALPHABET
+            .iter()
+            .position(|&x| x == c)
+            .ok_or(Error::ParseError)? as u32;
+        for x in bin[..len].iter_mut() {
+            carry += (*x as u32) * 58;
+            *x = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            if len == bin.len() {
+                return Err(Error::BufferTooSmall);
+            }
+            bin[len] = carry as u8;
+            carry >>= 8;
+            len += 1;
+        }
+    }
+    let total = finalize(bin, zeros, len)?;
+    Ok(&bin[..total])
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_base58() {
+    let mut b58 = [0u8; 32];
+    assert_eq!(
+        encode(&mut b58, b"Hello World!").unwrap(),
+        "2NEpo7TZRRrLZSi2U"
+    );
+    assert_eq!(
+        encode(&mut b58, &[0x00, 0x00, 0x28, 0x7f, 0xb4, 0xcd]).unwrap(),
+        "11233QC4"
+    );
+    assert_eq!(encode(&mut b58, &[]).unwrap(), "");
+
+    let mut bin = [0u8; 32];
+    assert_eq!(
+        decode(&mut bin, "2NEpo7TZRRrLZSi2U").unwrap(),
+        b"Hello World!"
+    );
+    assert_eq!(
+        decode(&mut bin, "11233QC4").unwrap(),
+        [0x00, 0x00, 0x28, 0x7f, 0xb4, 0xcd]
+    );
+    assert_eq!(
+        decode(&mut bin, "2NEpo7TZRRrLZSi2O"),
+        Err(Error::ParseError)
+    );
+    assert_eq!(
+        decode(&mut bin[..4], "2NEpo7TZRRrLZSi2U"),
+        Err(Error::BufferTooSmall)
+    );
+    assert_eq!(
+        encode(&mut b58[..4], b"Hello World!"),
+        Err(Error::BufferTooSmall)
+    );
+}
@@ -0,0 +1,186 @@
+//! Bech32 (BIP 173) encoding, without the 90 character length limit.
+//!
+//! Data can be secret, such as age identities, so the encoded characters
+//! are mapped to values, and the checksum is computed, without branches or
+//! table lookups depending on them.
+
+use core::fmt;
+
+use super::error::Error;
+
+const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const CHECKSUM_LEN: usize = 6;
+
+fn polymod_step(chk: u32, v: u8) -> u32 {
+    const GEN: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+    let b = chk >> 25;
+    let mut chk = ((chk & 0x1ffffff) << 5) ^ v as u32;
+    for (i, g) in GEN.iter().enumerate() {
+        chk ^= g & 0u32.wrapping_sub((b >> i) & 1);
+    }
+    chk
+}
+
+/// Returns `0xff` if `a == b`, `0` otherwise, in constant time.
+fn ct_eq_mask(a: u8, b: u8) -> u8 {
+    (((a ^ b) as u32).wrapping_sub(1) >> 8) as u8
+}
+
+/// Returns the character encoding the 5-bit value `v`, in constant time.
+fn value_to_char(v: u8) -> u8 {
+    let mut c = 0;
+    for (i, &x) in CHARSET.iter().enumerate() {
+        c |= ct_eq_mask(v, i as u8) & x;
+    }
+    c
+}
+
+/// Returns the 5-bit value encoded by the character `c`, in either case, and
+/// `0xff` if it is valid or `0` if it isn't, in constant time.
+fn char_to_value(c: u8) -> (u8, u8) {
+    // Lowercases letters, and doesn't map any other printable character to
+    // a character of the set.
+    let c = c | 0x20;
+    let (mut v, mut found) = (0, 0);
+    for (i, &x) in CHARSET.iter().enumerate() {
+        let m = ct_eq_mask(c, x);
+        v |= m & i as u8;
+        found |= m;
+    }
+    (v, found)
+}
+
+fn hrp_polymod(hrp: &[u8]) -> u32 {
+    let mut chk = 1;
+    for c in hrp {
+        chk = polymod_step(chk, c.to_ascii_lowercase() >> 5);
+    }
+    chk = polymod_step(chk, 0);
+    for c in hrp {
+        chk = polymod_step(chk, c.to_ascii_lowercase() & 31);
+    }
+    chk
+}
+
+/// Calls `f` with the 5-bit groups of `data`, the last one being zero-padded.
+fn for_each_group(data: &[u8], mut f: impl FnMut(u8)) {
+    let (mut acc, mut bits) = (0u32, 0u32);
+    for &x in data {
+        acc = ((acc << 8) | x as u32) & 0xfff;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            f(((acc >> bits) & 31) as u8);
+        }
+    }
+    if bits > 0 {
+        f(((acc << (5 - bits)) & 31) as u8);
+    }
+}
+
+/// Writes the Bech32 encoding of `data` with the human-readable part `hrp`.
+pub fn encode(f: &mut impl fmt::Write, hrp: &str, data: &[u8], upper: bool) -> fmt::Result {
+    // Characters of the set are either digits or lowercase letters, which
+    // are the only ones with the 0x40 bit set.
+    let case = |c: u8| {
+        if upper {
+            (c ^ ((c & 0x40) >> 1)) as char
+        } else {
+            c as char
+        }
+    };
+    for &c in hrp.as_bytes() {
+        if upper {
+            f.write_char(c.to_ascii_uppercase() as char)?;
+        } else {
+            f.write_char(c.to_ascii_lowercase() as char)?;
+        }
+    }
+    f.write_char('1')?;
+    let mut chk = hrp_polymod(hrp.as_bytes());
+    let mut res = Ok(());
+    for_each_group(data, |v| {
+        chk = polymod_step(chk, v);
+        res = res.and(f.write_char(case(value_to_char(v))));
+    });
+    res?;
+    for _ in 0..CHECKSUM_LEN {
+        chk = polymod_step(chk, 0);
+    }
+    chk ^= 1;
+    for i in 0..CHECKSUM_LEN {
+        let v = (chk >> (5 * (5 - i))) & 31;
+        f.write_char(case(value_to_char(v as u8)))?;
+    }
+    Ok(())
+}
+
+/// Decodes a Bech32 string into `out`, and returns the human-readable part
+/// along with the data.
+pub fn decode<'s, 't>(s: &'s str, out: &'t mut [u8]) -> Result<(&'s str, &'t [u8]), Error> {
+    let bytes = s.as_bytes();
+    let (mut has_lower, mut has_upper, mut invalid) = (false, false, false);
+    for c in bytes {
+        has_lower |= c.is_ascii_lowercase();
+        has_upper |= c.is_ascii_uppercase();
+        invalid |= !(33..=126).contains(c);
+    }
+    if has_lower && has_upper || invalid {
+        return Err(Error::ParseError);
+    }
+    let sep = s.rfind('1').ok_or(Error::ParseError)?;
+    let (hrp, data) = (&s[..sep], &bytes[sep + 1..]);
+    if hrp.is_empty() || data.len() < CHECKSUM_LEN {
+        return Err(Error::ParseError);
+    }
+    let mut chk = hrp_polymod(hrp.as_bytes());
+    let (mut acc, mut bits, mut len) = (0u32, 0u32, 0usize);
+    let mut valid = 0xff;
+    for (i, &c) in data.iter().enumerate() {
+        let (v, found) = char_to_value(c);
+        valid &= found;
+        chk = polymod_step(chk, v);
+        if i >= data.len() - CHECKSUM_LEN {
+            continue;
+        }
+        acc = ((acc << 5) | v as u32) & 0xfff;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            *out.get_mut(len).ok_or(Error::BufferTooSmall)? = (acc >> bits) as u8;
+            len += 1;
+        }
+    }
+    if valid != 0xff || chk != 1 || bits >= 5 || (acc & ((1 << bits) - 1)) != 0 {
+        return Err(Error::ParseError);
+    }
+    Ok((hrp, &out[..len]))
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_bech32() {
+    use std::string::String;
+
+    let s = "abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxw";
+    let mut out = [0u8; 20];
+    let (hrp, data) = decode(s, &mut out).unwrap();
+    assert_eq!(hrp, "abcdef");
+    let data = data.to_vec();
+    let mut encoded = String::new();
+    encode(&mut encoded, hrp, &data, false).unwrap();
+    assert_eq!(encoded, s);
+    let mut encoded = String::new();
+    encode(&mut encoded, hrp, &data, true).unwrap();
+    assert_eq!(encoded, s.to_ascii_uppercase());
+    assert_eq!(decode(&encoded, &mut out).unwrap().1, &data[..]);
+
+    assert!(decode("abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxW", &mut out).is_err());
+    assert!(decode("abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxx", &mut out).is_err());
+    assert!(decode("abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqbw", &mut out).is_err());
+    assert!(decode(
+        "abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxw",
+        &mut out[..19]
+    )
+    .is_err());
+}
@@ -0,0 +1,180 @@
+//! A small, self-contained BLAKE2b implementation, without keying support.
+
+#![allow(clippy::unreadable_literal)]
+
+const IV: [u64; 8] = [
+    0x6a09e667f3bcc908,
+    0xbb67ae8584caa73b,
+    0x3c6ef372fe94f82b,
+    0xa54ff53a5f1d36f1,
+    0x510e527fade682d1,
+    0x9b05688c2b3e6c1f,
+    0x1f83d9abfb41bd6b,
+    0x5be0cd19137e2179,
+];
+
+const SIGMA: [[usize; 16]; 12] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+    [11, 8, 12, 0, 5, 2, 15, 13, 10, 14, 3, 6, 7, 1, 9, 4],
+    [7, 9, 3, 1, 13, 12, 11, 14, 2, 6, 5, 10, 4, 0, 15, 8],
+    [9, 0, 5, 7, 2, 4, 10, 15, 14, 1, 11, 12, 6, 8, 3, 13],
+    [2, 12, 6, 10, 0, 11, 8, 3, 4, 13, 7, 5, 15, 14, 1, 9],
+    [12, 5, 1, 15, 14, 13, 4, 10, 0, 7, 6, 3, 9, 2, 8, 11],
+    [13, 11, 7, 14, 12, 1, 3, 9, 5, 0, 15, 4, 8, 6, 2, 10],
+    [6, 15, 14, 9, 11, 3, 0, 8, 12, 2, 13, 7, 1, 4, 10, 5],
+    [10, 2, 8, 4, 7, 6, 1, 5, 15, 11, 9, 14, 3, 12, 13, 0],
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [14, 10, 4, 8, 9, 15, 13, 6, 1, 12, 0, 2, 11, 7, 5, 3],
+];
+
+#[derive(Copy, Clone)]
+pub struct Hash {
+    h: [u64; 8],
+    t: u128,
+    w: [u8; 128],
+    r: usize,
+    out_len: usize,
+}
+
+impl Hash {
+    /// Maximum output length, in bytes.
+    pub const MAX_BYTES: usize = 64;
+
+    /// Creates a new hash function with an `out_len`-byte output.
+    pub fn new(out_len: usize) -> Hash {
+        assert!(out_len > 0 && out_len <= Self::MAX_BYTES);
+        let mut h = IV;
+        h[0] ^= 0x01010000 ^ out_len as u64;
+        Hash {
+            h,
+            t: 0,
+            w: [0u8; 128],
+            r: 0,
+            out_len,
+        }
+    }
+
+    #[cfg_attr(feature = "opt_size", inline(never))]
+    #[cfg_attr(not(feature = "opt_size"), inline(always))]
+    fn g(v: &mut [u64; 16], a: usize, b: usize, c: usize, d: usize, x: u64, y: u64) {
+        v[a] = v[a].wrapping_add(v[b]).wrapping_add(x);
+        v[d] = (v[d] ^ v[a]).rotate_right(32);
+        v[c] = v[c].wrapping_add(v[d]);
+        v[b] = (v[b] ^ v[c]).rotate_right(24);
+        v[a] = v[a].wrapping_add(v[b]).wrapping_add(y);
+        v[d] = (v[d] ^ v[a]).rotate_right(16);
+        v[c] = v[c].wrapping_add(v[d]);
+        v[b] = (v[b] ^ v[c]).rotate_right(63);
+    }
+
+    fn compress(&mut self, block: &[u8], last: bool) {
+        let mut m = [0u64; 16];
+        for (i, m) in m.iter_mut().enumerate() {
+            let mut x = [0u8; 8];
+            x.copy_from_slice(&block[i * 8..][..8]);
+            *m = u64::from_le_bytes(x);
+        }
+        let mut v = [0u64; 16];
+        v[..8].copy_from_slice(&self.h);
+        v[8..].copy_from_slice(&IV);
+        v[12] ^= self.t as u64;
+        v[13] ^= (self.t >> 64) as u64;
+        if last {
+            v[14] = !v[14];
+        }
+        for s in SIGMA.iter() {
+            Self::g(&mut v, 0, 4, 8, 12, m[s[0]], m[s[1]]);
+            Self::g(&mut v, 1, 5, 9, 13, m[s[2]], m[s[3]]);
+            Self::g(&mut v, 2, 6, 10, 14, m[s[4]], m[s[5]]);
+            Self::g(&mut v, 3, 7, 11, 15, m[s[6]], m[s[7]]);
+            Self::g(&mut v, 0, 5, 10, 15, m[s[8]], m[s[9]]);
+            Self::g(&mut v, 1, 6, 11, 12, m[s[10]], m[s[11]]);
+            Self::g(&mut v, 2, 7, 8, 13, m[s[12]], m[s[13]]);
+            Self::g(&mut v, 3, 4, 9, 14, m[s[14]], m[s[15]]);
+        }
+        for i in 0..8 {
+            self.h[i] ^= v[i] ^ v[i + 8];
+        }
+    }
+
+    /// Absorb content
+    pub fn update<T: AsRef<[u8]>>(&mut self, input: T) {
+        let mut input = input.as_ref();
+        while !input.is_empty() {
+            // The last block must be kept for finalization.
+            if self.r == 128 {
+                self.t += 128;
+                let w = self.w;
+                self.compress(&w, false);
+                self.r = 0;
+            }
+            let tc = ::core::cmp::min(input.len(), 128 - self.r);
+            self.w[self.r..self.r + tc].copy_from_slice(&input[..tc]);
+            self.r += tc;
+            input = &input[tc..];
+        }
+    }
+
+    /// Compute BLAKE2b(absorbed content), and store it into `out`.
+    /// The length of `out` must match the output length.
+    pub fn finalize_into(mut self, out: &mut [u8]) {
+        assert_eq!(out.len(), self.out_len);
+        self.t += self.r as u128;
+        for x in self.w[self.r..].iter_mut() {
+            *x = 0;
+        }
+        let w = self.w;
+        self.compress(&w, true);
+        let mut h = [0u8; 64];
+        for (i, &x) in self.h.iter().enumerate() {
+            h[i * 8..][..8].copy_from_slice(&x.to_le_bytes());
+        }
+        out.copy_from_slice(&h[..self.out_len]);
+    }
+
+    /// Compute BLAKE2b(absorbed content), with a compile-time output length.
+    pub fn finalize<const N: usize>(self) -> [u8; N] {
+        let mut out = [0u8; N];
+        self.finalize_into(&mut out);
+        out
+    }
+
+    /// Compute BLAKE2b(`input`), with a compile-time output length.
+    #[allow(dead_code)]
+    pub fn hash<const N: usize, T: AsRef<[u8]>>(input: T) -> [u8; N] {
+        let mut h = Hash::new(N);
+        h.update(input);
+        h.finalize()
+    }
+}
+
+#[test]
+fn test_blake2b() {
+    use ct_codecs::{Decoder, Hex};
+
+    let h: [u8; 64] = Hash::hash(b"abc");
+    assert_eq!(
+        &h[..],
+        &Hex::decode_to_vec(
+            "ba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d17d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923",
+            None
+        )
+        .unwrap()[..]
+    );
+    let h: [u8; 32] = Hash::hash(b"");
+    assert_eq!(
+        &h[..],
+        &Hex::decode_to_vec(
+            "0e5751c026e543b2e8ab2eb06099daa1d1e5df47778f7787faab45cdf12fe3a8",
+            None
+        )
+        .unwrap()[..]
+    );
+    let msg = [0x42u8; 300];
+    let mut st = Hash::new(24);
+    for chunk in msg.chunks(64) {
+        st.update(chunk);
+    }
+    assert_eq!(st.finalize::<24>(), Hash::hash::<24, _>(&msg[..]));
+}
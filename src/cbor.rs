@@ -0,0 +1,284 @@
+//! A minimal CBOR (RFC 8949) encoder and decoder, with just enough
+//! functionality for COSE structures. No allocations are required.
+
+use super::error::Error;
+
+pub const MAJOR_UINT: u8 = 0;
+pub const MAJOR_NINT: u8 = 1;
+pub const MAJOR_BYTES: u8 = 2;
+pub const MAJOR_TEXT: u8 = 3;
+pub const MAJOR_ARRAY: u8 = 4;
+pub const MAJOR_MAP: u8 = 5;
+pub const MAJOR_TAG: u8 = 6;
+#[cfg(not(feature = "disable-signatures"))]
+pub const MAJOR_SIMPLE: u8 = 7;
+
+#[cfg(not(feature = "disable-signatures"))]
+pub const SIMPLE_NULL: u8 = 22;
+
+/// Encodes the head of a data item, and returns it along with its length.
+pub fn head(major: u8, value: u64) -> ([u8; 9], usize) {
+    let mut h = [0u8; 9];
+    let major = major << 5;
+    let len = if value < 24 {
+        h[0] = major | value as u8;
+        1
+    } else if value <= 0xff {
+        h[0] = major | 24;
+        h[1] = value as u8;
+        2
+    } else if value <= 0xffff {
+        h[0] = major | 25;
+        h[1..3].copy_from_slice(&(value as u16).to_be_bytes());
+        3
+    } else if value <= 0xffff_ffff {
+        h[0] = major | 26;
+        h[1..5].copy_from_slice(&(value as u32).to_be_bytes());
+        5
+    } else {
+        h[0] = major | 27;
+        h[1..9].copy_from_slice(&value.to_be_bytes());
+        9
+    };
+    (h, len)
+}
+
+/// Serializes CBOR data items into a caller-supplied buffer.
+pub struct Writer<'a> {
+    out: &'a mut [u8],
+    pos: usize,
+}
+
+impl<'a> Writer<'a> {
+    pub fn new(out: &'a mut [u8]) -> Self {
+        Writer { out, pos: 0 }
+    }
+
+    /// Returns the number of bytes written so far.
+    #[cfg(not(feature = "disable-signatures"))]
+    pub fn len(&self) -> usize {
+        self.pos
+    }
+
+    pub fn raw(&mut self, bytes: &[u8]) -> Result<(), Error> {
+        let end = self
+            .pos
+            .checked_add(bytes.len())
+            .ok_or(Error::BufferTooSmall)?;
+        if end > self.out.len() {
+            return Err(Error::BufferTooSmall);
+        }
+        self.out[self.pos..end].copy_from_slice(bytes);
+        self.pos = end;
+        Ok(())
+    }
+
+    pub fn head(&mut self, major: u8, value: u64) -> Result<(), Error> {
+        let (h, len) = head(major, value);
+        self.raw(&h[..len])
+    }
+
+    pub fn int(&mut self, value: i64) -> Result<(), Error> {
+        if value < 0 {
+            self.head(MAJOR_NINT, !value as u64)
+        } else {
+            self.head(MAJOR_UINT, value as u64)
+        }
+    }
+
+    pub fn bytes(&mut self, bytes: &[u8]) -> Result<(), Error> {
+        self.head(MAJOR_BYTES, bytes.len() as u64)?;
+        self.raw(bytes)
+    }
+
+    #[cfg(not(feature = "disable-signatures"))]
+    pub fn array(&mut self, len: usize) -> Result<(), Error> {
+        self.head(MAJOR_ARRAY, len as u64)
+    }
+
+    pub fn map(&mut self, len: usize) -> Result<(), Error> {
+        self.head(MAJOR_MAP, len as u64)
+    }
+
+    #[cfg(not(feature = "disable-signatures"))]
+    pub fn tag(&mut self, tag: u64) -> Result<(), Error> {
+        self.head(MAJOR_TAG, tag)
+    }
+}
+
+/// Parses CBOR data items from a slice, without copying.
+/// Indefinite-length items are not supported.
+pub struct Reader<'a> {
+    input: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(input: &'a [u8]) -> Self {
+        Reader { input, pos: 0 }
+    }
+
+    /// Returns `true` if the whole input has been consumed.
+    pub fn is_empty(&self) -> bool {
+        self.pos == self.input.len()
+    }
+
+    fn take(&mut self, len: u64) -> Result<&'a [u8], Error> {
+        let remaining = self.input.len() - self.pos;
+        if len > remaining as u64 {
+            return Err(Error::ParseError);
+        }
+        let bytes = &self.input[self.pos..self.pos + len as usize];
+        self.pos += len as usize;
+        Ok(bytes)
+    }
+
+    /// Returns the major type of the next data item, without consuming it.
+    pub fn peek_major(&self) -> Result<u8, Error> {
+        self.input
+            .get(self.pos)
+            .map(|ib| ib >> 5)
+            .ok_or(Error::ParseError)
+    }
+
+    /// Reads the head of a data item, returning its major type and argument.
+    pub fn head(&mut self) -> Result<(u8, u64), Error> {
+        let ib = self.take(1)?[0];
+        let major = ib >> 5;
+        let ai = ib & 0x1f;
+        let value = match ai {
+            0..=23 => ai as u64,
+            24 => self.take(1)?[0] as u64,
+            25 => {
+                let b = self.take(2)?;
+                u16::from_be_bytes([b[0], b[1]]) as u64
+            }
+            26 => {
+                let b = self.take(4)?;
+                u32::from_be_bytes([b[0], b[1], b[2], b[3]]) as u64
+            }
+            27 => {
+                let b = self.take(8)?;
+                let mut v = [0u8; 8];
+                v.copy_from_slice(b);
+                u64::from_be_bytes(v)
+            }
+            _ => return Err(Error::ParseError),
+        };
+        Ok((major, value))
+    }
+
+    fn expect(&mut self, major: u8) -> Result<u64, Error> {
+        match self.head()? {
+            (m, value) if m == major => Ok(value),
+            _ => Err(Error::ParseError),
+        }
+    }
+
+    pub fn int(&mut self) -> Result<i64, Error> {
+        match self.head()? {
+            (MAJOR_UINT, v) if v <= i64::MAX as u64 => Ok(v as i64),
+            (MAJOR_NINT, v) if v <= i64::MAX as u64 => Ok(!(v as i64)),
+            _ => Err(Error::ParseError),
+        }
+    }
+
+    pub fn bytes(&mut self) -> Result<&'a [u8], Error> {
+        let len = self.expect(MAJOR_BYTES)?;
+        self.take(len)
+    }
+
+    #[cfg(not(feature = "disable-signatures"))]
+    pub fn array(&mut self) -> Result<u64, Error> {
+        self.expect(MAJOR_ARRAY)
+    }
+
+    pub fn map(&mut self) -> Result<u64, Error> {
+        self.expect(MAJOR_MAP)
+    }
+
+    /// Consumes a tag if the next data item is tagged with `tag`.
+    #[cfg(not(feature = "disable-signatures"))]
+    pub fn optional_tag(&mut self, tag: u64) -> Result<(), Error> {
+        if self.peek_major()? != MAJOR_TAG {
+            return Ok(());
+        }
+        if self.expect(MAJOR_TAG)? != tag {
+            return Err(Error::ParseError);
+        }
+        Ok(())
+    }
+
+    /// Consumes a `null` simple value if it is the next data item, and
+    /// returns `true` if that was the case.
+    #[cfg(not(feature = "disable-signatures"))]
+    pub fn null(&mut self) -> Result<bool, Error> {
+        if self.input.get(self.pos) != Some(&(MAJOR_SIMPLE << 5 | SIMPLE_NULL)) {
+            return Ok(false);
+        }
+        self.pos += 1;
+        Ok(true)
+    }
+
+    /// Skips a complete data item, including nested items.
+    pub fn skip(&mut self) -> Result<(), Error> {
+        let mut pending: u64 = 1;
+        while pending > 0 {
+            pending -= 1;
+            let (major, value) = self.head()?;
+            let nested = match major {
+                MAJOR_BYTES | MAJOR_TEXT => {
+                    self.take(value)?;
+                    0
+                }
+                MAJOR_ARRAY => value,
+                MAJOR_MAP => value.checked_mul(2).ok_or(Error::ParseError)?,
+                MAJOR_TAG => 1,
+                _ => 0,
+            };
+            // Every nested item takes at least one byte.
+            if nested > (self.input.len() - self.pos) as u64 {
+                return Err(Error::ParseError);
+            }
+            pending += nested;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(not(feature = "disable-signatures"))]
+#[test]
+fn test_cbor() {
+    let mut buf = [0u8; 64];
+    let mut w = Writer::new(&mut buf);
+    w.array(4).unwrap();
+    w.int(-8).unwrap();
+    w.int(500).unwrap();
+    w.bytes(b"abc").unwrap();
+    w.map(1).unwrap();
+    w.int(1).unwrap();
+    w.raw(&[0xf6]).unwrap();
+    let len = w.len();
+    assert_eq!(
+        &buf[..len],
+        [0x84, 0x27, 0x19, 0x01, 0xf4, 0x43, 0x61, 0x62, 0x63, 0xa1, 0x01, 0xf6]
+    );
+
+    let mut r = Reader::new(&buf[..len]);
+    assert_eq!(r.array().unwrap(), 4);
+    assert_eq!(r.int().unwrap(), -8);
+    assert_eq!(r.int().unwrap(), 500);
+    assert_eq!(r.bytes().unwrap(), b"abc");
+    r.skip().unwrap();
+    assert!(r.is_empty());
+
+    let mut small = [0u8; 2];
+    let mut w = Writer::new(&mut small);
+    assert_eq!(w.bytes(b"abc"), Err(Error::BufferTooSmall));
+
+    assert!(
+        Reader::new(&[0x9b, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff])
+            .skip()
+            .is_err()
+    );
+}
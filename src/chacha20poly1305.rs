@@ -0,0 +1,161 @@
+//! A small ChaCha20-Poly1305 (RFC 8439) implementation.
+
+use super::common::*;
+use super::error::Error;
+use super::poly1305::Poly1305;
+
+pub const KEY_BYTES: usize = 32;
+pub const NONCE_BYTES: usize = 12;
+pub const TAG_BYTES: usize = super::poly1305::TAG_BYTES;
+
+#[inline(always)]
+fn quarter_round(x: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize) {
+    x[a] = x[a].wrapping_add(x[b]);
+    x[d] = (x[d] ^ x[a]).rotate_left(16);
+    x[c] = x[c].wrapping_add(x[d]);
+    x[b] = (x[b] ^ x[c]).rotate_left(12);
+    x[a] = x[a].wrapping_add(x[b]);
+    x[d] = (x[d] ^ x[a]).rotate_left(8);
+    x[c] = x[c].wrapping_add(x[d]);
+    x[b] = (x[b] ^ x[c]).rotate_left(7);
+}
+
+fn load32_le(s: &[u8]) -> u32 {
+    u32::from_le_bytes([s[0], s[1], s[2], s[3]])
+}
+
+struct ChaCha20 {
+    state: [u32; 16],
+}
+
+impl ChaCha20 {
+    fn new(key: &[u8; KEY_BYTES], nonce: &[u8; NONCE_BYTES]) -> Self {
+        let mut state = [0u32; 16];
+        state[0..4].copy_from_slice(&[0x61707865, 0x3320646e, 0x79622d32, 0x6b206574]);
+        for (i, k) in key.chunks(4).enumerate() {
+            state[4 + i] = load32_le(k);
+        }
+        for (i, n) in nonce.chunks(4).enumerate() {
+            state[13 + i] = load32_le(n);
+        }
+        ChaCha20 { state }
+    }
+
+    fn block(&mut self) -> [u8; 64] {
+        let mut x = self.state;
+        for _ in 0..10 {
+            quarter_round(&mut x, 0, 4, 8, 12);
+            quarter_round(&mut x, 1, 5, 9, 13);
+            quarter_round(&mut x, 2, 6, 10, 14);
+            quarter_round(&mut x, 3, 7, 11, 15);
+            quarter_round(&mut x, 0, 5, 10, 15);
+            quarter_round(&mut x, 1, 6, 11, 12);
+            quarter_round(&mut x, 2, 7, 8, 13);
+            quarter_round(&mut x, 3, 4, 9, 14);
+        }
+        let mut out = [0u8; 64];
+        for (i, (x, s)) in x.iter().zip(self.state.iter()).enumerate() {
+            out[i * 4..][..4].copy_from_slice(&x.wrapping_add(*s).to_le_bytes());
+        }
+        self.state[12] = self.state[12].wrapping_add(1);
+        out
+    }
+
+    fn xor(&mut self, data: &mut [u8]) {
+        for chunk in data.chunks_mut(64) {
+            let mut ks = self.block();
+            for (d, k) in chunk.iter_mut().zip(ks.iter()) {
+                *d ^= k;
+            }
+            Mem::wipe(&mut ks);
+        }
+    }
+}
+
+impl Drop for ChaCha20 {
+    fn drop(&mut self) {
+        Mem::wipe(&mut self.state)
+    }
+}
+
+/// Derives the Poly1305 key from the first ChaCha20 block.
+fn poly1305(chacha: &mut ChaCha20) -> Poly1305 {
+    let mut block = chacha.block();
+    let mut poly_key = [0u8; 32];
+    poly_key.copy_from_slice(&block[..32]);
+    let poly = Poly1305::new(&poly_key);
+    Mem::wipe(&mut block);
+    Mem::wipe(&mut poly_key);
+    poly
+}
+
+fn compute_tag(mut poly: Poly1305, ad: &[u8], ciphertext: &[u8]) -> [u8; TAG_BYTES] {
+    let padding = [0u8; 16];
+    poly.update(ad);
+    poly.update(&padding[..(16 - ad.len() % 16) % 16]);
+    poly.update(ciphertext);
+    poly.update(&padding[..(16 - ciphertext.len() % 16) % 16]);
+    poly.update(&(ad.len() as u64).to_le_bytes());
+    poly.update(&(ciphertext.len() as u64).to_le_bytes());
+    poly.finalize()
+}
+
+/// Encrypts `data` in place, and returns the authentication tag.
+pub fn seal(
+    key: &[u8; KEY_BYTES],
+    nonce: &[u8; NONCE_BYTES],
+    ad: &[u8],
+    data: &mut [u8],
+) -> [u8; TAG_BYTES] {
+    let mut chacha = ChaCha20::new(key, nonce);
+    let poly = poly1305(&mut chacha);
+    chacha.xor(data);
+    compute_tag(poly, ad, data)
+}
+
+/// Verifies the tag and decrypts `data` in place.
+/// `data` is left untouched if verification fails.
+pub fn open(
+    key: &[u8; KEY_BYTES],
+    nonce: &[u8; NONCE_BYTES],
+    ad: &[u8],
+    data: &mut [u8],
+    tag: &[u8; TAG_BYTES],
+) -> Result<(), Error> {
+    let mut chacha = ChaCha20::new(key, nonce);
+    let poly = poly1305(&mut chacha);
+    if !Mem::ct_eq(&compute_tag(poly, ad, data), tag) {
+        return Err(Error::DecryptionFailed);
+    }
+    chacha.xor(data);
+    Ok(())
+}
+
+#[test]
+fn test_chacha20poly1305() {
+    use ct_codecs::{Decoder, Hex};
+
+    let key = [7u8; KEY_BYTES];
+    let nonce = [1u8; NONCE_BYTES];
+    let mut data = [0u8; 100];
+    for (i, x) in data.iter_mut().enumerate() {
+        *x = i as u8;
+    }
+    let tag = seal(&key, &nonce, b"additional data", &mut data);
+    let expected = Hex::decode_to_vec(
+        "7c4b831494e027ca2b80b6e67e442fea5e56f74cd6ee6cf63dbdd059e1258de963b35945e7f57f9a01db0bd6b48901cc1082d455edd82029e39705b5d6fe078e1c93f2f85ad8566a416947e7998182b756fb3d6be810e1262d478057c024fbbf24718ed0c3055c8eb628435b83db2fa99818f1c0",
+        None,
+    )
+    .unwrap();
+    assert_eq!(&data[..], &expected[..100]);
+    assert_eq!(&tag[..], &expected[100..]);
+    assert_eq!(
+        open(&key, &nonce, b"additional data!", &mut data, &tag),
+        Err(Error::DecryptionFailed)
+    );
+    assert_eq!(data[..], expected[..100]);
+    open(&key, &nonce, b"additional data", &mut data, &tag).unwrap();
+    for (i, x) in data.iter().enumerate() {
+        assert_eq!(*x, i as u8);
+    }
+}
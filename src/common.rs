@@ -1,3 +1,5 @@
+use core::fmt;
+use core::hash::{Hash, Hasher};
 use core::ops::{Deref, DerefMut};
 use core::ptr;
 use core::sync::atomic;
@@ -5,9 +7,28 @@ use core::sync::atomic;
 use super::error::Error;
 
 /// A seed, which a key pair can be derived from.
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Copy, Clone, Eq)]
 pub struct Seed([u8; Seed::BYTES]);
 
+impl fmt::Debug for Seed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Seed(..)")
+    }
+}
+
+impl PartialEq for Seed {
+    /// Compares two seeds in constant time.
+    fn eq(&self, other: &Self) -> bool {
+        Mem::ct_eq(&self.0, &other.0)
+    }
+}
+
+impl Hash for Seed {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
 impl From<[u8; 32]> for Seed {
     fn from(seed: [u8; 32]) -> Self {
         Seed(seed)
@@ -63,6 +84,16 @@ impl Seed {
     }
 }
 
+#[cfg(feature = "rand_core")]
+impl Seed {
+    /// Generates a random seed using `rng`.
+    pub fn generate_with_rng<R: rand_core::CryptoRng + ?Sized>(rng: &mut R) -> Self {
+        let mut seed = [0u8; Seed::BYTES];
+        rng.fill_bytes(&mut seed);
+        Seed(seed)
+    }
+}
+
 impl Deref for Seed {
     type Target = [u8; Seed::BYTES];
 
@@ -79,6 +110,29 @@ impl DerefMut for Seed {
     }
 }
 
+#[cfg(feature = "subtle")]
+impl subtle::ConstantTimeEq for Seed {
+    fn ct_eq(&self, other: &Self) -> subtle::Choice {
+        subtle::ConstantTimeEq::ct_eq(&self.0[..], &other.0[..])
+    }
+}
+
+#[cfg(feature = "subtle")]
+impl subtle::ConditionallySelectable for Seed {
+    fn conditional_select(a: &Self, b: &Self, choice: subtle::Choice) -> Self {
+        Seed(subtle::ConditionallySelectable::conditional_select(
+            &a.0, &b.0, choice,
+        ))
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for Seed {
+    fn zeroize(&mut self) {
+        Mem::wipe(&mut self.0)
+    }
+}
+
 pub(crate) struct Mem;
 
 impl Mem {
@@ -92,4 +146,18 @@ impl Mem {
         atomic::compiler_fence(atomic::Ordering::SeqCst);
         atomic::fence(atomic::Ordering::SeqCst);
     }
+
+    /// Constant-time comparison of two slices of the same length.
+    #[allow(dead_code)]
+    #[inline(never)]
+    pub fn ct_eq(x: &[u8], y: &[u8]) -> bool {
+        if x.len() != y.len() {
+            return false;
+        }
+        let d = x
+            .iter()
+            .zip(y.iter())
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b));
+        unsafe { ptr::read_volatile(&d) == 0 }
+    }
 }
@@ -0,0 +1,528 @@
+//! COSE (RFC 9052/9053) support: `COSE_Key` encoding of Ed25519 and X25519
+//! keys, and `COSE_Sign1` signatures using the EdDSA algorithm.
+//!
+//! Everything works without allocations: `COSE_Sign1` structures are written
+//! into caller-supplied buffers, and parsed structures borrow from the input.
+
+use super::cbor::{self, Reader, Writer};
+use super::error::Error;
+
+/// COSE key type for Octet Key Pairs.
+pub const KTY_OKP: i64 = 1;
+/// COSE curve identifier for X25519.
+pub const CRV_X25519: i64 = 4;
+/// COSE curve identifier for Ed25519.
+pub const CRV_ED25519: i64 = 6;
+/// COSE algorithm identifier for EdDSA.
+pub const ALG_EDDSA: i64 = -8;
+/// CBOR tag for `COSE_Sign1` structures.
+pub const TAG_COSE_SIGN1: u64 = 18;
+
+const LABEL_KTY: i64 = 1;
+const LABEL_ALG: i64 = 3;
+const LABEL_CRV: i64 = -1;
+const LABEL_X: i64 = -2;
+const LABEL_D: i64 = -4;
+
+#[cfg(not(feature = "disable-signatures"))]
+const HEADER_ALG: i64 = 1;
+#[cfg(not(feature = "disable-signatures"))]
+const HEADER_CRIT: i64 = 2;
+#[cfg(not(feature = "disable-signatures"))]
+const HEADER_KID: i64 = 4;
+
+/// Key material extracted from an OKP `COSE_Key`.
+struct OkpKey<'a> {
+    x: Option<&'a [u8]>,
+    d: Option<&'a [u8]>,
+}
+
+fn parse_okp_key(bytes: &[u8], crv: i64, alg: Option<i64>) -> Result<OkpKey<'_>, Error> {
+    let mut r = Reader::new(bytes);
+    let (mut kty_, mut crv_, mut alg_) = (None, None, None);
+    let (mut x, mut d) = (None, None);
+    for _ in 0..r.map()? {
+        if r.peek_major()? == cbor::MAJOR_TEXT {
+            r.skip()?;
+            r.skip()?;
+            continue;
+        }
+        let label = r.int()?;
+        let (slot, value) = match label {
+            LABEL_KTY => (&mut kty_, Some(r.int()?)),
+            LABEL_CRV => (&mut crv_, Some(r.int()?)),
+            LABEL_ALG => (&mut alg_, Some(r.int()?)),
+            LABEL_X | LABEL_D => {
+                let slot = if label == LABEL_X { &mut x } else { &mut d };
+                if slot.replace(r.bytes()?).is_some() {
+                    return Err(Error::ParseError);
+                }
+                continue;
+            }
+            _ => {
+                r.skip()?;
+                continue;
+            }
+        };
+        if core::mem::replace(slot, value).is_some() {
+            return Err(Error::ParseError);
+        }
+    }
+    if !r.is_empty() || kty_ != Some(KTY_OKP) || crv_ != Some(crv) {
+        return Err(Error::ParseError);
+    }
+    if alg_.is_some() && alg_ != alg {
+        return Err(Error::ParseError);
+    }
+    Ok(OkpKey { x, d })
+}
+
+fn write_okp_key(
+    w: &mut Writer<'_>,
+    crv: i64,
+    alg: Option<i64>,
+    x: &[u8],
+    d: Option<&[u8]>,
+) -> Result<(), Error> {
+    // Map keys are written in the deterministic order of RFC 8949.
+    w.map(3 + alg.is_some() as usize + d.is_some() as usize)?;
+    w.int(LABEL_KTY)?;
+    w.int(KTY_OKP)?;
+    if let Some(alg) = alg {
+        w.int(LABEL_ALG)?;
+        w.int(alg)?;
+    }
+    w.int(LABEL_CRV)?;
+    w.int(crv)?;
+    w.int(LABEL_X)?;
+    w.bytes(x)?;
+    if let Some(d) = d {
+        w.int(LABEL_D)?;
+        w.bytes(d)?;
+    }
+    Ok(())
+}
+
+#[cfg(not(feature = "disable-signatures"))]
+mod eddsa {
+    use super::super::{KeyPair, Noise, PublicKey, SecretKey, Seed, Signature};
+    use super::*;
+
+    impl PublicKey {
+        /// Number of bytes in the `COSE_Key` encoding of a public key.
+        pub const COSE_KEY_BYTES: usize = 42;
+
+        /// Import a public key from a `COSE_Key` structure.
+        pub fn from_cose_key(cose_key: &[u8]) -> Result<Self, Error> {
+            let key = parse_okp_key(cose_key, CRV_ED25519, Some(ALG_EDDSA))?;
+            PublicKey::from_slice(key.x.ok_or(Error::ParseError)?)
+        }
+
+        /// Export a public key as a `COSE_Key` structure.
+        pub fn to_cose_key(&self) -> [u8; PublicKey::COSE_KEY_BYTES] {
+            let mut out = [0u8; PublicKey::COSE_KEY_BYTES];
+            let mut w = Writer::new(&mut out);
+            write_okp_key(&mut w, CRV_ED25519, Some(ALG_EDDSA), &self[..], None)
+                .expect("COSE_Key length changed");
+            out
+        }
+
+        /// Verifies a `COSE_Sign1` structure with an embedded payload, and
+        /// returns the payload.
+        pub fn verify_cose_sign1<'a>(
+            &self,
+            cose_sign1: &'a [u8],
+            external_aad: &[u8],
+        ) -> Result<&'a [u8], Error> {
+            let cose_sign1 = CoseSign1::from_bytes(cose_sign1)?;
+            cose_sign1.verify(self, external_aad)?;
+            cose_sign1.payload().ok_or(Error::ParseError)
+        }
+    }
+
+    impl KeyPair {
+        /// Number of bytes in the `COSE_Key` encoding of a key pair.
+        pub const COSE_KEY_BYTES: usize = 77;
+
+        /// Import a key pair from a `COSE_Key` structure.
+        /// If the public key is present, it must match the secret key.
+        pub fn from_cose_key(cose_key: &[u8]) -> Result<Self, Error> {
+            let key = parse_okp_key(cose_key, CRV_ED25519, Some(ALG_EDDSA))?;
+            let seed = Seed::from_slice(key.d.ok_or(Error::ParseError)?)?;
+            let kp = KeyPair::from_seed(seed);
+            if let Some(x) = key.x {
+                if x != &kp.pk[..] {
+                    return Err(Error::InvalidPublicKey);
+                }
+            }
+            Ok(kp)
+        }
+
+        /// Export a key pair as a `COSE_Key` structure.
+        pub fn to_cose_key(&self) -> [u8; KeyPair::COSE_KEY_BYTES] {
+            let mut out = [0u8; KeyPair::COSE_KEY_BYTES];
+            let mut w = Writer::new(&mut out);
+            write_okp_key(
+                &mut w,
+                CRV_ED25519,
+                Some(ALG_EDDSA),
+                &self.pk[..],
+                Some(&self.sk.seed()[..]),
+            )
+            .expect("COSE_Key length changed");
+            out
+        }
+    }
+
+    /// The encoded protected header `{ alg: EdDSA }`.
+    const PROTECTED_EDDSA: [u8; 3] = [0xa1, 0x01, 0x27];
+
+    /// The first elements of a `Sig_structure` for `COSE_Sign1`.
+    const SIG_STRUCTURE_SIGNATURE1: [u8; 12] = *b"\x84\x6aSignature1";
+
+    /// Calls `f` with the chunks of the `Sig_structure` to be signed.
+    fn with_sig_structure<T>(
+        protected: &[u8],
+        external_aad: &[u8],
+        payload: &[u8],
+        f: impl FnOnce(&[&[u8]]) -> T,
+    ) -> T {
+        let (protected_h, protected_hlen) = cbor::head(cbor::MAJOR_BYTES, protected.len() as u64);
+        let (aad_h, aad_hlen) = cbor::head(cbor::MAJOR_BYTES, external_aad.len() as u64);
+        let (payload_h, payload_hlen) = cbor::head(cbor::MAJOR_BYTES, payload.len() as u64);
+        f(&[
+            &SIG_STRUCTURE_SIGNATURE1,
+            &protected_h[..protected_hlen],
+            protected,
+            &aad_h[..aad_hlen],
+            external_aad,
+            &payload_h[..payload_hlen],
+            payload,
+        ])
+    }
+
+    impl SecretKey {
+        /// Signs `payload` and writes the resulting tagged `COSE_Sign1`
+        /// structure into `out`.
+        ///
+        /// `external_aad` is authenticated but not included in the output,
+        /// and `kid` is an optional key identifier stored in the unprotected
+        /// header. Returns the number of bytes written.
+        pub fn sign_cose_sign1(
+            &self,
+            payload: &[u8],
+            external_aad: &[u8],
+            kid: Option<&[u8]>,
+            noise: Option<Noise>,
+            out: &mut [u8],
+        ) -> Result<usize, Error> {
+            let signature = with_sig_structure(&PROTECTED_EDDSA, external_aad, payload, |chunks| {
+                self.sign_chunks(chunks, noise)
+            });
+            let mut w = Writer::new(out);
+            w.tag(TAG_COSE_SIGN1)?;
+            w.array(4)?;
+            w.bytes(&PROTECTED_EDDSA)?;
+            match kid {
+                Some(kid) => {
+                    w.map(1)?;
+                    w.int(HEADER_KID)?;
+                    w.bytes(kid)?;
+                }
+                None => w.map(0)?,
+            }
+            w.bytes(payload)?;
+            w.bytes(&signature[..])?;
+            Ok(w.len())
+        }
+
+        /// Signs `payload` and returns the resulting tagged `COSE_Sign1`
+        /// structure.
+        #[cfg(feature = "std")]
+        pub fn to_cose_sign1(
+            &self,
+            payload: &[u8],
+            external_aad: &[u8],
+            kid: Option<&[u8]>,
+            noise: Option<Noise>,
+        ) -> Vec<u8> {
+            let kid_len = kid.map_or(0, |kid| kid.len());
+            let mut out = vec![0u8; 96 + kid_len + payload.len()];
+            let len = self
+                .sign_cose_sign1(payload, external_aad, kid, noise, &mut out)
+                .expect("COSE_Sign1 length miscalculated");
+            out.truncate(len);
+            out
+        }
+    }
+
+    /// A parsed `COSE_Sign1` structure.
+    #[derive(Clone, Debug)]
+    pub struct CoseSign1<'a> {
+        protected: &'a [u8],
+        kid: Option<&'a [u8]>,
+        payload: Option<&'a [u8]>,
+        signature: Signature,
+    }
+
+    impl<'a> CoseSign1<'a> {
+        /// Parses a tagged or untagged `COSE_Sign1` structure.
+        /// The protected header must specify the EdDSA algorithm.
+        pub fn from_bytes(bytes: &'a [u8]) -> Result<Self, Error> {
+            let mut r = Reader::new(bytes);
+            r.optional_tag(TAG_COSE_SIGN1)?;
+            if r.array()? != 4 {
+                return Err(Error::ParseError);
+            }
+            let protected = r.bytes()?;
+            let (alg, mut kid) = parse_header(&mut Reader::new(protected), true)?;
+            if alg != Some(ALG_EDDSA) {
+                return Err(Error::ParseError);
+            }
+            let (alg, unprotected_kid) = parse_header(&mut r, false)?;
+            if alg.is_some() || (kid.is_some() && unprotected_kid.is_some()) {
+                return Err(Error::ParseError);
+            }
+            kid = kid.or(unprotected_kid);
+            let payload = if r.null()? { None } else { Some(r.bytes()?) };
+            let signature = Signature::from_slice(r.bytes()?)?;
+            if !r.is_empty() {
+                return Err(Error::ParseError);
+            }
+            Ok(CoseSign1 {
+                protected,
+                kid,
+                payload,
+                signature,
+            })
+        }
+
+        /// Returns the embedded payload, or `None` if it is detached.
+        pub fn payload(&self) -> Option<&'a [u8]> {
+            self.payload
+        }
+
+        /// Returns the key identifier, if present in either header.
+        pub fn kid(&self) -> Option<&'a [u8]> {
+            self.kid
+        }
+
+        /// Returns the signature.
+        pub fn signature(&self) -> &Signature {
+            &self.signature
+        }
+
+        /// Verifies the signature over the embedded payload.
+        pub fn verify(&self, pk: &PublicKey, external_aad: &[u8]) -> Result<(), Error> {
+            let payload = self.payload.ok_or(Error::ParseError)?;
+            self.verify_detached(pk, payload, external_aad)
+        }
+
+        /// Verifies the signature over a detached payload.
+        pub fn verify_detached(
+            &self,
+            pk: &PublicKey,
+            payload: &[u8],
+            external_aad: &[u8],
+        ) -> Result<(), Error> {
+            let mut st = pk.verify_incremental(&self.signature)?;
+            with_sig_structure(self.protected, external_aad, payload, |chunks| {
+                for chunk in chunks {
+                    st.absorb(chunk);
+                }
+            });
+            st.verify()
+        }
+    }
+
+    /// Parses a header map, returning the algorithm and the key identifier.
+    /// An empty protected header is encoded as a zero-length byte string.
+    fn parse_header<'a>(
+        r: &mut Reader<'a>,
+        protected: bool,
+    ) -> Result<(Option<i64>, Option<&'a [u8]>), Error> {
+        if protected && r.is_empty() {
+            return Ok((None, None));
+        }
+        let (mut alg, mut kid) = (None, None);
+        for _ in 0..r.map()? {
+            if r.peek_major()? == cbor::MAJOR_TEXT {
+                r.skip()?;
+                r.skip()?;
+                continue;
+            }
+            match r.int()? {
+                HEADER_ALG if alg.is_none() => alg = Some(r.int()?),
+                HEADER_KID if kid.is_none() => kid = Some(r.bytes()?),
+                HEADER_ALG | HEADER_KID | HEADER_CRIT => return Err(Error::ParseError),
+                _ => r.skip()?,
+            }
+        }
+        if protected && !r.is_empty() {
+            return Err(Error::ParseError);
+        }
+        Ok((alg, kid))
+    }
+}
+
+#[cfg(not(feature = "disable-signatures"))]
+pub use eddsa::*;
+
+#[cfg(feature = "x25519")]
+mod ecdh {
+    use super::super::x25519::{KeyPair, PublicKey, SecretKey};
+    use super::*;
+
+    impl PublicKey {
+        /// Number of bytes in the `COSE_Key` encoding of a public key.
+        pub const COSE_KEY_BYTES: usize = 40;
+
+        /// Import a public key from a `COSE_Key` structure.
+        pub fn from_cose_key(cose_key: &[u8]) -> Result<Self, Error> {
+            let key = parse_okp_key(cose_key, CRV_X25519, None)?;
+            PublicKey::from_slice(key.x.ok_or(Error::ParseError)?)
+        }
+
+        /// Export a public key as a `COSE_Key` structure.
+        pub fn to_cose_key(&self) -> [u8; PublicKey::COSE_KEY_BYTES] {
+            let mut out = [0u8; PublicKey::COSE_KEY_BYTES];
+            let mut w = Writer::new(&mut out);
+            write_okp_key(&mut w, CRV_X25519, None, &self[..], None)
+                .expect("COSE_Key length changed");
+            out
+        }
+    }
+
+    impl KeyPair {
+        /// Number of bytes in the `COSE_Key` encoding of a key pair.
+        pub const COSE_KEY_BYTES: usize = 75;
+
+        /// Import a key pair from a `COSE_Key` structure.
+        /// If the public key is present, it must match the secret key.
+        pub fn from_cose_key(cose_key: &[u8]) -> Result<Self, Error> {
+            let key = parse_okp_key(cose_key, CRV_X25519, None)?;
+            let sk = SecretKey::from_slice(key.d.ok_or(Error::ParseError)?)?;
+            let pk = sk.recover_public_key()?;
+            if let Some(x) = key.x {
+                if x != &pk[..] {
+                    return Err(Error::InvalidPublicKey);
+                }
+            }
+            Ok(KeyPair { pk, sk })
+        }
+
+        /// Export a key pair as a `COSE_Key` structure.
+        pub fn to_cose_key(&self) -> [u8; KeyPair::COSE_KEY_BYTES] {
+            let mut out = [0u8; KeyPair::COSE_KEY_BYTES];
+            let mut w = Writer::new(&mut out);
+            write_okp_key(&mut w, CRV_X25519, None, &self.pk[..], Some(&self.sk[..]))
+                .expect("COSE_Key length changed");
+            out
+        }
+    }
+}
+
+#[cfg(not(feature = "disable-signatures"))]
+#[test]
+fn test_cose_sign1() {
+    use super::KeyPair;
+    use ct_codecs::{Decoder, Hex};
+
+    let kp = KeyPair::from_seed([42u8; 32].into());
+    let payload = b"firmware manifest";
+    let mut out = [0u8; 256];
+    let len = kp
+        .sk
+        .sign_cose_sign1(payload, b"aad", Some(b"kid1"), None, &mut out)
+        .unwrap();
+    let expected = Hex::decode_to_vec("d28443a10127a104446b696431516669726d77617265206d616e6966657374584028f86f4972fb28715a2da9ebef8ad98eb76f3594a52a65d25c15b8a53f5cef93bfe7cac8a00b9021e1e3f53570132e898bdcb658f94a965b83c1ae4d13e05605", None).unwrap();
+    assert_eq!(&out[..len], &expected[..]);
+
+    assert_eq!(
+        kp.pk.verify_cose_sign1(&out[..len], b"aad").unwrap(),
+        payload
+    );
+    assert!(kp.pk.verify_cose_sign1(&out[..len], b"other").is_err());
+
+    let cose_sign1 = CoseSign1::from_bytes(&out[1..len]).unwrap();
+    assert_eq!(cose_sign1.kid(), Some(&b"kid1"[..]));
+    assert!(cose_sign1
+        .verify_detached(&kp.pk, b"firmware manifesT", b"aad")
+        .is_err());
+
+    out[len - 1] ^= 1;
+    assert!(kp.pk.verify_cose_sign1(&out[..len], b"aad").is_err());
+    assert_eq!(
+        kp.sk
+            .sign_cose_sign1(payload, b"", None, None, &mut out[..64])
+            .unwrap_err(),
+        Error::BufferTooSmall
+    );
+}
+
+#[cfg(not(feature = "disable-signatures"))]
+#[test]
+fn test_cose_sign1_wg_example() {
+    use super::{KeyPair, Seed};
+    use ct_codecs::{Decoder, Hex};
+
+    // Inputs of eddsa-examples/eddsa-sig-01.json from the COSE WG Examples
+    // repository (https://github.com/cose-wg/Examples): the key of RFC 8032
+    // test 1, `alg: EdDSA` in the protected header, `kid: "11"` in the
+    // unprotected header, and no external AAD. EdDSA is deterministic, so
+    // the output only depends on these. The expected structure was computed
+    // independently, by signing the hand-encoded `Sig_structure` with
+    // pyca/cryptography.
+    let seed = Hex::decode_to_vec(
+        "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60",
+        None,
+    )
+    .unwrap();
+    let kp = KeyPair::from_seed(Seed::from_slice(&seed).unwrap());
+    let expected = Hex::decode_to_vec("d28443a10127a10442313154546869732069732074686520636f6e74656e742e58406354488f9f290e36cd80e23762e664a5cb03e4267c66a8cffaef7c66d89a40bf2cbb8222432a08e5ee410d8b540c6931d26fb6af673f7e2100655d8bae765c04", None).unwrap();
+    let mut out = [0u8; 256];
+    let len = kp
+        .sk
+        .sign_cose_sign1(b"This is the content.", b"", Some(b"11"), None, &mut out)
+        .unwrap();
+    assert_eq!(&out[..len], &expected[..]);
+}
+
+#[test]
+fn test_cose_key() {
+    #[cfg(not(feature = "disable-signatures"))]
+    {
+        use super::{KeyPair, PublicKey};
+
+        let kp = KeyPair::from_seed([42u8; 32].into());
+        let cose_pk = kp.pk.to_cose_key();
+        assert_eq!(
+            &cose_pk[..10],
+            [0xa4, 0x01, 0x01, 0x03, 0x27, 0x20, 0x06, 0x21, 0x58, 0x20]
+        );
+        assert_eq!(PublicKey::from_cose_key(&cose_pk).unwrap(), kp.pk);
+        let cose_kp = kp.to_cose_key();
+        assert_eq!(KeyPair::from_cose_key(&cose_kp).unwrap(), kp);
+        assert!(KeyPair::from_cose_key(&cose_pk).is_err());
+    }
+
+    #[cfg(feature = "x25519")]
+    {
+        use super::x25519::{KeyPair, PublicKey, SecretKey};
+
+        let sk = SecretKey::new([42u8; 32]);
+        let kp = KeyPair {
+            pk: sk.recover_public_key().unwrap(),
+            sk,
+        };
+        let cose_pk = kp.pk.to_cose_key();
+        assert_eq!(PublicKey::from_cose_key(&cose_pk).unwrap(), kp.pk);
+        let mut cose_kp = kp.to_cose_key();
+        assert_eq!(KeyPair::from_cose_key(&cose_kp).unwrap(), kp);
+        cose_kp[10] ^= 1;
+        assert_eq!(
+            KeyPair::from_cose_key(&cose_kp).unwrap_err(),
+            Error::InvalidPublicKey
+        );
+    }
+}
@@ -0,0 +1,335 @@
+//! libsodium-compatible `crypto_box` and sealed boxes
+//! (`crypto_box_seal`), built on top of X25519 and XSalsa20-Poly1305.
+//!
+//! Boxes use the "combined" format: the authentication tag is followed by
+//! the ciphertext. Sealed boxes are prefixed with the ephemeral public key
+//! of the sender.
+
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::Deref;
+
+use super::blake2b;
+use super::common::*;
+use super::error::Error;
+use super::x25519::{KeyPair, PublicKey, SecretKey};
+use super::xsalsa20poly1305;
+
+/// Number of bytes added to a message by `crypto_box`.
+pub const MAC_BYTES: usize = xsalsa20poly1305::TAG_BYTES;
+
+/// Number of bytes added to a message by a sealed box.
+pub const SEAL_BYTES: usize = PublicKey::BYTES + MAC_BYTES;
+
+/// A nonce for `crypto_box`. A nonce must never be reused with the same key.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Nonce([u8; Nonce::BYTES]);
+
+impl Nonce {
+    /// Number of raw bytes in a nonce.
+    pub const BYTES: usize = xsalsa20poly1305::NONCE_BYTES;
+
+    /// Creates a nonce from raw bytes.
+    pub fn new(nonce: [u8; Nonce::BYTES]) -> Self {
+        Nonce(nonce)
+    }
+
+    /// Creates a nonce from a slice.
+    pub fn from_slice(nonce: &[u8]) -> Result<Self, Error> {
+        let mut nonce_ = [0u8; Nonce::BYTES];
+        if nonce.len() != nonce_.len() {
+            return Err(Error::InvalidParameters);
+        }
+        nonce_.copy_from_slice(nonce);
+        Ok(Nonce::new(nonce_))
+    }
+
+    /// Generates a new random nonce.
+    #[cfg(feature = "random")]
+    pub fn generate() -> Self {
+        let mut nonce = [0u8; Nonce::BYTES];
+        getrandom::fill(&mut nonce).expect("RNG failure");
+        Nonce(nonce)
+    }
+}
+
+#[cfg(feature = "random")]
+impl Default for Nonce {
+    fn default() -> Self {
+        Self::generate()
+    }
+}
+
+impl Deref for Nonce {
+    type Target = [u8; Nonce::BYTES];
+
+    /// Returns a nonce as bytes.
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// A key shared by two parties, computed from the public key of one and the
+/// secret key of the other (`crypto_box_beforenm`).
+#[derive(Clone, Eq)]
+pub struct SharedKey([u8; SharedKey::BYTES]);
+
+impl fmt::Debug for SharedKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SharedKey(..)")
+    }
+}
+
+impl PartialEq for SharedKey {
+    /// Compares two shared keys in constant time.
+    fn eq(&self, other: &Self) -> bool {
+        Mem::ct_eq(&self.0, &other.0)
+    }
+}
+
+impl Hash for SharedKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
+impl SharedKey {
+    /// Number of raw bytes in a shared key.
+    pub const BYTES: usize = xsalsa20poly1305::KEY_BYTES;
+
+    /// Computes the key shared between the owners of `pk` and `sk`.
+    pub fn new(pk: &PublicKey, sk: &SecretKey) -> Result<Self, Error> {
+        let dh = pk.dh(sk)?;
+        Ok(SharedKey(xsalsa20poly1305::hsalsa20(&dh, &[0u8; 16])))
+    }
+
+    /// Encrypts and authenticates `message` into `out`, and returns the
+    /// number of bytes written (`message.len() + MAC_BYTES`).
+    pub fn seal_into(&self, out: &mut [u8], message: &[u8], nonce: &Nonce) -> Result<usize, Error> {
+        let len = message.len() + MAC_BYTES;
+        if out.len() < len {
+            return Err(Error::BufferTooSmall);
+        }
+        let (tag, ciphertext) = out[..len].split_at_mut(MAC_BYTES);
+        ciphertext.copy_from_slice(message);
+        tag.copy_from_slice(&xsalsa20poly1305::seal(&self.0, nonce, ciphertext));
+        Ok(len)
+    }
+
+    /// Verifies and decrypts `ciphertext` into `out`, and returns the
+    /// number of bytes written (`ciphertext.len() - MAC_BYTES`).
+    pub fn open_into(
+        &self,
+        out: &mut [u8],
+        ciphertext: &[u8],
+        nonce: &Nonce,
+    ) -> Result<usize, Error> {
+        if ciphertext.len() < MAC_BYTES {
+            return Err(Error::DecryptionFailed);
+        }
+        let (tag, ciphertext) = ciphertext.split_at(MAC_BYTES);
+        if out.len() < ciphertext.len() {
+            return Err(Error::BufferTooSmall);
+        }
+        let mut tag_ = [0u8; MAC_BYTES];
+        tag_.copy_from_slice(tag);
+        let message = &mut out[..ciphertext.len()];
+        message.copy_from_slice(ciphertext);
+        if let Err(e) = xsalsa20poly1305::open(&self.0, nonce, message, &tag_) {
+            Mem::wipe(message);
+            return Err(e);
+        }
+        Ok(message.len())
+    }
+
+    /// Encrypts and authenticates `message`.
+    #[cfg(feature = "std")]
+    pub fn seal(&self, message: &[u8], nonce: &Nonce) -> Vec<u8> {
+        let mut out = vec![0u8; message.len() + MAC_BYTES];
+        self.seal_into(&mut out, message, nonce).unwrap();
+        out
+    }
+
+    /// Verifies and decrypts `ciphertext`.
+    #[cfg(feature = "std")]
+    pub fn open(&self, ciphertext: &[u8], nonce: &Nonce) -> Result<Vec<u8>, Error> {
+        let mut out = vec![0u8; ciphertext.len().saturating_sub(MAC_BYTES)];
+        self.open_into(&mut out, ciphertext, nonce)?;
+        Ok(out)
+    }
+}
+
+impl Drop for SharedKey {
+    fn drop(&mut self) {
+        Mem::wipe(&mut self.0)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for SharedKey {
+    fn zeroize(&mut self) {
+        Mem::wipe(&mut self.0)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for SharedKey {}
+
+impl Deref for SharedKey {
+    type Target = [u8; SharedKey::BYTES];
+
+    /// Returns a shared key as bytes.
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+fn seal_nonce(epk: &PublicKey, pk: &PublicKey) -> Nonce {
+    let mut st = blake2b::Hash::new(Nonce::BYTES);
+    st.update(&epk[..]);
+    st.update(&pk[..]);
+    Nonce(st.finalize())
+}
+
+impl PublicKey {
+    /// Encrypts `message` into `out` for the owner of this public key, using
+    /// a sealed box, and returns the number of bytes written
+    /// (`message.len() + SEAL_BYTES`).
+    #[cfg(feature = "random")]
+    pub fn seal_into(&self, out: &mut [u8], message: &[u8]) -> Result<usize, Error> {
+        self.seal_into_with_ephemeral(out, message, &KeyPair::generate())
+    }
+
+    /// Encrypts `message` into `out` using a sealed box, with a caller-supplied
+    /// ephemeral key pair. The ephemeral key pair must never be reused.
+    pub fn seal_into_with_ephemeral(
+        &self,
+        out: &mut [u8],
+        message: &[u8],
+        ephemeral: &KeyPair,
+    ) -> Result<usize, Error> {
+        if out.len() < message.len() + SEAL_BYTES {
+            return Err(Error::BufferTooSmall);
+        }
+        let nonce = seal_nonce(&ephemeral.pk, self);
+        let key = SharedKey::new(self, &ephemeral.sk)?;
+        let (epk, ciphertext) = out.split_at_mut(PublicKey::BYTES);
+        epk.copy_from_slice(&ephemeral.pk[..]);
+        Ok(PublicKey::BYTES + key.seal_into(ciphertext, message, &nonce)?)
+    }
+
+    /// Encrypts `message` for the owner of this public key, using a sealed box.
+    #[cfg(all(feature = "std", feature = "random"))]
+    pub fn seal(&self, message: &[u8]) -> Vec<u8> {
+        let mut out = vec![0u8; message.len() + SEAL_BYTES];
+        self.seal_into(&mut out, message).unwrap();
+        out
+    }
+}
+
+impl KeyPair {
+    /// Decrypts a sealed box into `out`, and returns the number of bytes
+    /// written (`ciphertext.len() - SEAL_BYTES`).
+    pub fn open_sealed_into(&self, out: &mut [u8], ciphertext: &[u8]) -> Result<usize, Error> {
+        if ciphertext.len() < SEAL_BYTES {
+            return Err(Error::DecryptionFailed);
+        }
+        let (epk, ciphertext) = ciphertext.split_at(PublicKey::BYTES);
+        let mut epk_ = [0u8; PublicKey::BYTES];
+        epk_.copy_from_slice(epk);
+        let epk = PublicKey::new(epk_);
+        let nonce = seal_nonce(&epk, &self.pk);
+        let key = SharedKey::new(&epk, &self.sk)?;
+        key.open_into(out, ciphertext, &nonce)
+    }
+
+    /// Decrypts a sealed box.
+    #[cfg(feature = "std")]
+    pub fn open_sealed(&self, ciphertext: &[u8]) -> Result<Vec<u8>, Error> {
+        let mut out = vec![0u8; ciphertext.len().saturating_sub(SEAL_BYTES)];
+        self.open_sealed_into(&mut out, ciphertext)?;
+        Ok(out)
+    }
+}
+
+#[test]
+fn test_crypto_box() {
+    use ct_codecs::{Decoder, Hex};
+
+    let keypair = |seed: u8| {
+        let sk = SecretKey::new([seed; SecretKey::BYTES]);
+        let pk = sk.recover_public_key().unwrap();
+        KeyPair { pk, sk }
+    };
+    let (alice, bob) = (keypair(0x11), keypair(0x22));
+    let nonce = Nonce::new([3u8; Nonce::BYTES]);
+    let mut message = [0u8; 100];
+    for (i, x) in message.iter_mut().enumerate() {
+        *x = i as u8;
+    }
+    let expected = Hex::decode_to_vec(
+        "81a171f8a647592d09cc2a99da0ead8cbbfb1e5e89df46b73ec35c769fd7ca5055bfa340731bc787cb5a82e2b9d008749d51b220bb7eb7d7456e723bd40274ce331e7ae0161fb33c49421249f23cf293e02b9fbde7a78ce3b97c4cc3dffaea53bd87041462fedb2a8e197d7a615441aeb47fe412",
+        None,
+    )
+    .unwrap();
+    let key = SharedKey::new(&bob.pk, &alice.sk).unwrap();
+    let mut ciphertext = [0u8; 100 + MAC_BYTES];
+    assert_eq!(
+        key.seal_into(&mut ciphertext[..100], &message, &nonce),
+        Err(Error::BufferTooSmall)
+    );
+    assert_eq!(key.seal_into(&mut ciphertext, &message, &nonce), Ok(116));
+    assert_eq!(&ciphertext[..], &expected[..]);
+
+    let key = SharedKey::new(&alice.pk, &bob.sk).unwrap();
+    let mut decrypted = [0u8; 100];
+    assert_eq!(key.open_into(&mut decrypted, &ciphertext, &nonce), Ok(100));
+    assert_eq!(decrypted, message);
+    ciphertext[50] ^= 1;
+    assert_eq!(
+        key.open_into(&mut decrypted, &ciphertext, &nonce),
+        Err(Error::DecryptionFailed)
+    );
+    assert_eq!(
+        key.open_into(&mut decrypted, &ciphertext[..15], &nonce),
+        Err(Error::DecryptionFailed)
+    );
+}
+
+#[test]
+fn test_sealed_box() {
+    use ct_codecs::{Decoder, Hex};
+
+    let sk = SecretKey::new([0x22; SecretKey::BYTES]);
+    let pk = sk.recover_public_key().unwrap();
+    let bob = KeyPair { pk, sk };
+    let sealed = Hex::decode_to_vec(
+        "19299abda845af7f5adaf6514017dc75204b750bb71040f4097f1bfcde9e751e5af3d9c06a9d63d0ad1b1590000a85e41f389f40589d5d7fc36033cb2dca",
+        None,
+    )
+    .unwrap();
+    let mut message = [0u8; 14];
+    assert_eq!(bob.open_sealed_into(&mut message, &sealed), Ok(14));
+    assert_eq!(&message, b"sealed message");
+
+    let sk = SecretKey::new([0x33; SecretKey::BYTES]);
+    let pk = sk.recover_public_key().unwrap();
+    let ephemeral = KeyPair { pk, sk };
+    let mut sealed2 = [0u8; 14 + SEAL_BYTES];
+    assert_eq!(
+        bob.pk
+            .seal_into_with_ephemeral(&mut sealed2, b"sealed message", &ephemeral),
+        Ok(sealed.len())
+    );
+    let expected = Hex::decode_to_vec(
+        "7b0d47d93427f8311160781c7c733fd89f88970aef490d8aa0ee19a4cb8a1b1476fc9e4e4feacee57cffe5290b53a1d87374d50ddef5ad07142181458722",
+        None,
+    )
+    .unwrap();
+    assert_eq!(&sealed2[..], &expected[..]);
+    sealed2[40] ^= 1;
+    assert_eq!(
+        bob.open_sealed_into(&mut message, &sealed2),
+        Err(Error::DecryptionFailed)
+    );
+}
@@ -1,8 +1,11 @@
 use core::convert::TryFrom;
 use core::fmt;
+use core::hash::{Hash, Hasher};
 use core::ops::{Deref, DerefMut};
 
 use super::common::*;
+#[cfg(feature = "std")]
+use super::edwards25519::{ge_multiscalar_mul, ge_multiscalar_mul_vartime};
 #[cfg(feature = "blind-keys")]
 use super::edwards25519::{ge_scalarmult, sc_invert, sc_mul};
 use super::edwards25519::{
@@ -52,10 +55,45 @@ impl DerefMut for PublicKey {
     }
 }
 
+#[cfg(feature = "subtle")]
+impl subtle::ConstantTimeEq for PublicKey {
+    fn ct_eq(&self, other: &Self) -> subtle::Choice {
+        subtle::ConstantTimeEq::ct_eq(&self.0[..], &other.0[..])
+    }
+}
+
+#[cfg(feature = "subtle")]
+impl subtle::ConditionallySelectable for PublicKey {
+    fn conditional_select(a: &Self, b: &Self, choice: subtle::Choice) -> Self {
+        PublicKey(subtle::ConditionallySelectable::conditional_select(
+            &a.0, &b.0, choice,
+        ))
+    }
+}
+
 /// A secret key.
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Eq)]
 pub struct SecretKey([u8; SecretKey::BYTES]);
 
+impl fmt::Debug for SecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SecretKey(..)")
+    }
+}
+
+impl PartialEq for SecretKey {
+    /// Compares two secret keys in constant time.
+    fn eq(&self, other: &Self) -> bool {
+        Mem::ct_eq(&self.0, &other.0)
+    }
+}
+
+impl Hash for SecretKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
 impl SecretKey {
     /// Number of bytes in a secret key.
     pub const BYTES: usize = 32 + PublicKey::BYTES;
@@ -94,7 +132,7 @@ impl SecretKey {
     /// so this will detect corruption of the secret key.
     pub fn validate_public_key(&self, pk: &PublicKey) -> Result<(), Error> {
         let kp = KeyPair::from_seed(self.seed());
-        if kp.pk != *pk {
+        if !Mem::ct_eq(&kp.pk[..], &pk[..]) {
             return Err(Error::InvalidPublicKey);
         }
         Ok(())
@@ -107,6 +145,16 @@ impl Drop for SecretKey {
     }
 }
 
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for SecretKey {
+    fn zeroize(&mut self) {
+        Mem::wipe(&mut self.0)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for SecretKey {}
+
 impl Deref for SecretKey {
     type Target = [u8; SecretKey::BYTES];
 
@@ -123,6 +171,13 @@ impl DerefMut for SecretKey {
     }
 }
 
+#[cfg(feature = "subtle")]
+impl subtle::ConstantTimeEq for SecretKey {
+    fn ct_eq(&self, other: &Self) -> subtle::Choice {
+        subtle::ConstantTimeEq::ct_eq(&self.0[..], &other.0[..])
+    }
+}
+
 /// A key pair.
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct KeyPair {
@@ -192,11 +247,45 @@ impl DerefMut for Signature {
     }
 }
 
+#[cfg(feature = "subtle")]
+impl subtle::ConstantTimeEq for Signature {
+    fn ct_eq(&self, other: &Self) -> subtle::Choice {
+        subtle::ConstantTimeEq::ct_eq(&self.0[..], &other.0[..])
+    }
+}
+
+#[cfg(feature = "subtle")]
+impl subtle::ConditionallySelectable for Signature {
+    fn conditional_select(a: &Self, b: &Self, choice: subtle::Choice) -> Self {
+        Signature(subtle::ConditionallySelectable::conditional_select(
+            &a.0, &b.0, choice,
+        ))
+    }
+}
+
+const DOM2_PREFIX: &[u8; 32] = b"SigEd25519 no Ed25519 collisions";
+
+/// Returns `dom2(1, context)`, the domain separator for Ed25519ph, along with
+/// its length.
+fn dom2_ph(context: Option<&[u8]>) -> Result<([u8; 32 + 2 + 255], usize), Error> {
+    let context = context.unwrap_or_default();
+    if context.len() > 255 {
+        return Err(Error::InvalidParameters);
+    }
+    let mut dom = [0u8; 32 + 2 + 255];
+    dom[..32].copy_from_slice(DOM2_PREFIX);
+    dom[32] = 1;
+    dom[33] = context.len() as u8;
+    dom[34..34 + context.len()].copy_from_slice(context);
+    Ok((dom, 34 + context.len()))
+}
+
 /// The state of a streaming verification operation.
 #[derive(Clone)]
 pub struct VerifyingState {
     hasher: sha512::Hash,
     signature: Signature,
+    pk: PublicKey,
     a: GeP3,
 }
 
@@ -207,7 +296,7 @@ impl Drop for VerifyingState {
 }
 
 impl VerifyingState {
-    fn new(pk: &PublicKey, signature: &Signature) -> Result<Self, Error> {
+    fn new(dom: &[u8], pk: &PublicKey, signature: &Signature) -> Result<Self, Error> {
         let r = &signature[0..32];
         let s = &signature[32..64];
         sc_reject_noncanonical(s)?;
@@ -221,11 +310,13 @@ impl VerifyingState {
             }
         };
         let mut hasher = sha512::Hash::new();
+        hasher.update(dom);
         hasher.update(r);
         hasher.update(&pk[..]);
         Ok(VerifyingState {
             hasher,
             signature: *signature,
+            pk: *pk,
             a,
         })
     }
@@ -258,16 +349,30 @@ impl VerifyingState {
 impl PublicKey {
     /// Verify the signature of a multi-part message (streaming).
     pub fn verify_incremental(&self, signature: &Signature) -> Result<VerifyingState, Error> {
-        VerifyingState::new(self, signature)
+        VerifyingState::new(&[], self, signature)
     }
 
     /// Verifies that the signature `signature` is valid for the message
     /// `message`.
     pub fn verify(&self, message: impl AsRef<[u8]>, signature: &Signature) -> Result<(), Error> {
-        let mut st = VerifyingState::new(self, signature)?;
+        let mut st = VerifyingState::new(&[], self, signature)?;
         st.absorb(message);
         st.verify()
     }
+
+    /// Verifies an Ed25519ph (RFC 8032) signature, given the SHA-512 hash of
+    /// the message and an optional context of up to 255 bytes.
+    pub fn verify_prehashed(
+        &self,
+        prehash: &[u8; 64],
+        context: Option<&[u8]>,
+        signature: &Signature,
+    ) -> Result<(), Error> {
+        let (dom, dom_len) = dom2_ph(context)?;
+        let mut st = VerifyingState::new(&dom[..dom_len], self, signature)?;
+        st.absorb(prehash);
+        st.verify()
+    }
 }
 
 /// The state of a streaming signature operation.
@@ -286,12 +391,24 @@ impl Drop for SigningState {
     }
 }
 
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for SigningState {
+    fn zeroize(&mut self) {
+        Mem::wipe(&mut self.az);
+        Mem::wipe(&mut self.nonce);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for SigningState {}
+
 impl SigningState {
-    fn new(nonce: [u8; 64], az: [u8; 64], pk_: &[u8]) -> Self {
+    fn new(dom: &[u8], nonce: [u8; 64], az: [u8; 64], pk_: &[u8]) -> Self {
         let r = ge_scalarmult_base(&nonce[0..32]).to_bytes();
 
         let mut st = sha512::Hash::new();
-        st.update(&r);
+        st.update(dom);
+        st.update(r);
         st.update(pk_);
 
         SigningState {
@@ -323,10 +440,180 @@ impl SigningState {
     }
 }
 
+impl VerifyingState {
+    /// Number of bytes in an exported state.
+    pub const EXPORTED_BYTES: usize = sha512::EXPORT_HEADER_BYTES
+        + Signature::BYTES
+        + sha512::Hash::STATE_BYTES
+        + sha512::EXPORT_TAG_BYTES;
+
+    /// Exports the state of a streaming verification operation, so that it
+    /// can be resumed later, possibly by another process.
+    ///
+    /// The state is authenticated using `key`, which will be required to
+    /// import it, along with the public key.
+    pub fn export(&self, key: &[u8]) -> [u8; VerifyingState::EXPORTED_BYTES] {
+        let mut out = [0u8; VerifyingState::EXPORTED_BYTES];
+        let payload = &mut out[sha512::EXPORT_HEADER_BYTES..];
+        payload[..Signature::BYTES].copy_from_slice(&self.signature[..]);
+        self.hasher.store_state(&mut payload[Signature::BYTES..]);
+        sha512::export_seal(
+            &mut out,
+            sha512::ExportKind::VerifyingState,
+            key,
+            &self.pk[..],
+        );
+        out
+    }
+
+    /// Imports a state previously exported with `export()`.
+    pub fn import(bytes: &[u8], pk: &PublicKey, key: &[u8]) -> Result<Self, Error> {
+        let payload = sha512::export_open(
+            bytes,
+            VerifyingState::EXPORTED_BYTES,
+            sha512::ExportKind::VerifyingState,
+            key,
+            &pk[..],
+        )?;
+        let signature = Signature::from_slice(&payload[..Signature::BYTES])?;
+        let mut st = VerifyingState::new(&[], pk, &signature)?;
+        st.hasher = sha512::Hash::load_state(&payload[Signature::BYTES..])?;
+        Ok(st)
+    }
+}
+
+/// Derives the key used to authenticate an exported signing state, and the
+/// mask used to encrypt its nonce, from the secret key.
+fn signing_state_export_keys(az: &[u8; 64], r: &[u8; 32]) -> ([u8; 64], [u8; 64]) {
+    let mut prk = sha512::Hmac::mac(&az[32..64], b"SigningState export");
+    let mac_key = sha512::Hmac::mac(&prk[..], b"mac");
+    let mut h = sha512::Hmac::new(&prk[..]);
+    h.update(b"nonce");
+    h.update(r);
+    let mask = h.finalize();
+    Mem::wipe(&mut prk);
+    (mac_key, mask)
+}
+
+impl SigningState {
+    /// Number of bytes in an exported state.
+    pub const EXPORTED_BYTES: usize = sha512::EXPORT_HEADER_BYTES
+        + 32
+        + 32
+        + sha512::Hash::STATE_BYTES
+        + sha512::EXPORT_TAG_BYTES;
+
+    /// Exports the state of a streaming signature operation, so that it can
+    /// be resumed later, possibly by another process.
+    ///
+    /// The secret scalar is not included. The secret nonce is encrypted, and
+    /// the state is authenticated, using keys derived from the secret key,
+    /// which will be required to import it.
+    ///
+    /// # Security
+    ///
+    /// An exported state MUST be imported at most once. The commitment to
+    /// the secret nonce is already part of the state, so two signatures
+    /// finished from the same exported state, with different messages,
+    /// share the same nonce, which reveals the secret key to anyone who
+    /// sees both. Nothing in the exported state prevents this: the
+    /// application must destroy it as soon as it has been imported, and
+    /// must never keep copies of it, or restore it from a backup.
+    pub fn export(&self) -> [u8; SigningState::EXPORTED_BYTES] {
+        let mut out = [0u8; SigningState::EXPORTED_BYTES];
+        let (mut mac_key, mut mask) = signing_state_export_keys(&self.az, &self.r);
+        let payload = &mut out[sha512::EXPORT_HEADER_BYTES..];
+        payload[..32].copy_from_slice(&self.r);
+        for (i, x) in payload[32..64].iter_mut().enumerate() {
+            *x = self.nonce[i] ^ mask[i];
+        }
+        self.hasher.store_state(&mut payload[64..]);
+        sha512::export_seal(&mut out, sha512::ExportKind::SigningState, &mac_key, &[]);
+        Mem::wipe(&mut mac_key);
+        Mem::wipe(&mut mask);
+        out
+    }
+
+    /// Imports a state previously exported with `export()`, using the secret
+    /// key that created it.
+    ///
+    /// # Security
+    ///
+    /// Importing the same exported state twice and signing different
+    /// messages with the resulting states reveals the secret key. See
+    /// `export()`.
+    pub fn import(bytes: &[u8], sk: &SecretKey) -> Result<Self, Error> {
+        if bytes.len() != SigningState::EXPORTED_BYTES {
+            return Err(Error::ParseError);
+        }
+        let az: [u8; 64] = {
+            let mut hash_output = sha512::Hash::hash(&sk[0..32]);
+            hash_output[0] &= 248;
+            hash_output[31] &= 63;
+            hash_output[31] |= 64;
+            hash_output
+        };
+        let mut r = [0u8; 32];
+        r.copy_from_slice(&bytes[sha512::EXPORT_HEADER_BYTES..][..32]);
+        let (mut mac_key, mut mask) = signing_state_export_keys(&az, &r);
+        let payload = sha512::export_open(
+            bytes,
+            SigningState::EXPORTED_BYTES,
+            sha512::ExportKind::SigningState,
+            &mac_key,
+            &[],
+        );
+        Mem::wipe(&mut mac_key);
+        let payload = match payload {
+            Ok(payload) => payload,
+            Err(e) => {
+                Mem::wipe(&mut mask);
+                return Err(e);
+            }
+        };
+        let mut nonce = [0u8; 64];
+        for (i, x) in nonce[..32].iter_mut().enumerate() {
+            *x = payload[32 + i] ^ mask[i];
+        }
+        Mem::wipe(&mut mask);
+        let mut st = SigningState::new(&[], nonce, az, &sk[32..64]);
+        if st.r != r {
+            return Err(Error::InvalidSecretKey);
+        }
+        st.hasher = sha512::Hash::load_state(&payload[64..])?;
+        Ok(st)
+    }
+}
+
 impl SecretKey {
     /// Sign a multi-part message (streaming API).
     /// It is critical for `noise` to never repeat.
     pub fn sign_incremental(&self, noise: Noise) -> SigningState {
+        #[cfg(feature = "random")]
+        let additional_noise = Some(Noise::generate());
+        #[cfg(not(feature = "random"))]
+        let additional_noise = None;
+        self.sign_incremental_with_additional_noise(noise, additional_noise.as_ref())
+    }
+
+    /// Sign a multi-part message (streaming API), using `rng` instead of the
+    /// system random number generator for the additional noise.
+    /// It is critical for `noise` to never repeat.
+    #[cfg(feature = "rand_core")]
+    pub fn sign_incremental_with_rng<R: rand_core::CryptoRng + ?Sized>(
+        &self,
+        noise: Noise,
+        rng: &mut R,
+    ) -> SigningState {
+        let additional_noise = Noise::generate_with_rng(rng);
+        self.sign_incremental_with_additional_noise(noise, Some(&additional_noise))
+    }
+
+    fn sign_incremental_with_additional_noise(
+        &self,
+        noise: Noise,
+        additional_noise: Option<&Noise>,
+    ) -> SigningState {
         let seed = &self[0..32];
         let pk = &self[32..64];
         let az: [u8; 64] = {
@@ -337,22 +624,49 @@ impl SecretKey {
             hash_output
         };
         let mut st = sha512::Hash::new();
-        #[cfg(feature = "random")]
-        {
-            let additional_noise = Noise::generate();
+        if let Some(additional_noise) = additional_noise {
             st.update(additional_noise.as_ref());
         }
         st.update(noise.as_ref());
         st.update(seed);
         let nonce = st.finalize();
-        SigningState::new(nonce, az, pk)
+        SigningState::new(&[], nonce, az, pk)
     }
 
     /// Computes a signature for the message `message` using the secret key.
     /// The noise parameter is optional, but recommended in order to mitigate
     /// fault attacks.
     pub fn sign(&self, message: impl AsRef<[u8]>, noise: Option<Noise>) -> Signature {
-        let message = message.as_ref();
+        self.sign_chunks(&[message.as_ref()], noise)
+    }
+
+    /// Computes a signature for the concatenation of `chunks`.
+    /// The output is the same as `sign()` over the concatenated message, but
+    /// the message doesn't have to be stored contiguously.
+    pub(crate) fn sign_chunks(&self, chunks: &[&[u8]], noise: Option<Noise>) -> Signature {
+        self.sign_chunks_with_dom(&[], chunks, noise)
+    }
+
+    /// Computes an Ed25519ph (RFC 8032) signature, given the SHA-512 hash of
+    /// the message and an optional context of up to 255 bytes.
+    /// The noise parameter is optional, but recommended in order to mitigate
+    /// fault attacks.
+    pub fn sign_prehashed(
+        &self,
+        prehash: &[u8; 64],
+        context: Option<&[u8]>,
+        noise: Option<Noise>,
+    ) -> Result<Signature, Error> {
+        let (dom, dom_len) = dom2_ph(context)?;
+        Ok(self.sign_chunks_with_dom(&dom[..dom_len], &[&prehash[..]], noise))
+    }
+
+    fn sign_chunks_with_dom(
+        &self,
+        dom: &[u8],
+        chunks: &[&[u8]],
+        noise: Option<Noise>,
+    ) -> Signature {
         let seed = &self[0..32];
         let pk = &self[32..64];
         let az: [u8; 64] = {
@@ -364,26 +678,35 @@ impl SecretKey {
         };
         let nonce = {
             let mut hasher = sha512::Hash::new();
+            hasher.update(dom);
             if let Some(noise) = noise {
                 hasher.update(&noise[..]);
                 hasher.update(&az[..]);
             } else {
                 hasher.update(&az[32..64]);
             }
-            hasher.update(message);
+            for chunk in chunks {
+                hasher.update(chunk);
+            }
             let mut hash_output = hasher.finalize();
             sc_reduce(&mut hash_output[0..64]);
             hash_output
         };
-        let mut st = SigningState::new(nonce, az, pk);
-        st.absorb(message);
+        let mut st = SigningState::new(dom, nonce, az, pk);
+        for chunk in chunks {
+            st.absorb(chunk);
+        }
         let signature = st.sign();
 
         #[cfg(feature = "self-verify")]
         {
-            PublicKey::from_slice(pk)
-                .expect("Key length changed")
-                .verify(message, &signature)
+            let pk = PublicKey::from_slice(pk).expect("Key length changed");
+            let mut st = VerifyingState::new(dom, &pk, &signature)
+                .expect("Newly created signature cannot be verified");
+            for chunk in chunks {
+                st.absorb(chunk);
+            }
+            st.verify()
                 .expect("Newly created signature cannot be verified");
         }
 
@@ -401,6 +724,12 @@ impl KeyPair {
         KeyPair::from_seed(Seed::default())
     }
 
+    /// Generates a new key pair using `rng`.
+    #[cfg(feature = "rand_core")]
+    pub fn generate_with_rng<R: rand_core::CryptoRng + ?Sized>(rng: &mut R) -> KeyPair {
+        KeyPair::from_seed(Seed::generate_with_rng(rng))
+    }
+
     /// Generates a new key pair using a secret seed.
     pub fn from_seed(seed: Seed) -> KeyPair {
         if seed.iter().fold(0, |acc, x| acc | x) == 0 {
@@ -472,10 +801,39 @@ impl DerefMut for KeyPair {
     }
 }
 
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for KeyPair {
+    fn zeroize(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.sk)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for KeyPair {}
+
 /// Noise, for non-deterministic signatures.
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Copy, Clone, Eq)]
 pub struct Noise([u8; Noise::BYTES]);
 
+impl fmt::Debug for Noise {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Noise(..)")
+    }
+}
+
+impl PartialEq for Noise {
+    /// Compares two noise components in constant time.
+    fn eq(&self, other: &Self) -> bool {
+        Mem::ct_eq(&self.0, &other.0)
+    }
+}
+
+impl Hash for Noise {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
 impl Noise {
     /// Number of raw bytes for a noise component.
     pub const BYTES: usize = 16;
@@ -512,6 +870,13 @@ impl DerefMut for Noise {
     }
 }
 
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for Noise {
+    fn zeroize(&mut self) {
+        Mem::wipe(&mut self.0)
+    }
+}
+
 #[cfg(feature = "random")]
 impl Default for Noise {
     /// Generates random noise.
@@ -530,11 +895,61 @@ impl Noise {
     }
 }
 
+#[cfg(feature = "rand_core")]
+impl Noise {
+    /// Generates random noise using `rng`.
+    pub fn generate_with_rng<R: rand_core::CryptoRng + ?Sized>(rng: &mut R) -> Self {
+        let mut noise = [0u8; Noise::BYTES];
+        rng.fill_bytes(&mut noise);
+        Noise(noise)
+    }
+}
+
+#[cfg(feature = "std")]
+fn multiscalar_mul_inputs(scalars: &[[u8; 32]], points: &[[u8; 32]]) -> Result<Vec<GeP3>, Error> {
+    if scalars.len() != points.len() {
+        return Err(Error::InvalidParameters);
+    }
+    for scalar in scalars {
+        sc_reject_noncanonical(scalar)?;
+    }
+    points
+        .iter()
+        .map(|p| GeP3::from_bytes_vartime(p).ok_or(Error::InvalidPublicKey))
+        .collect()
+}
+
+/// Computes `scalars[0] * points[0] + ... + scalars[n-1] * points[n-1]` and
+/// returns the encoded result.
+///
+/// Scalars must be reduced modulo the group order, and points must be valid
+/// encodings. The computation is done in variable time, using Straus' method
+/// for small inputs and Pippenger's method for large ones: it must only be
+/// used with public scalars. Use `multiscalar_mul_ct` for secret scalars.
+#[cfg(feature = "std")]
+pub fn multiscalar_mul(scalars: &[[u8; 32]], points: &[[u8; 32]]) -> Result<[u8; 32], Error> {
+    let points = multiscalar_mul_inputs(scalars, points)?;
+    Ok(ge_multiscalar_mul_vartime(scalars, &points).to_bytes())
+}
+
+/// Computes `scalars[0] * points[0] + ... + scalars[n-1] * points[n-1]` and
+/// returns the encoded result, in constant time with respect to the scalars.
+///
+/// Scalars must be reduced modulo the group order, and points must be valid
+/// encodings. This is slower than `multiscalar_mul`.
+#[cfg(feature = "std")]
+pub fn multiscalar_mul_ct(scalars: &[[u8; 32]], points: &[[u8; 32]]) -> Result<[u8; 32], Error> {
+    let points = multiscalar_mul_inputs(scalars, points)?;
+    Ok(ge_multiscalar_mul(scalars, &points).to_bytes())
+}
+
 #[cfg(feature = "traits")]
 mod ed25519_trait {
     use ::ed25519::signature as ed25519_trait;
 
-    use super::{PublicKey, SecretKey, Signature};
+    #[cfg(feature = "digest")]
+    use super::sha512;
+    use super::{Error, PublicKey, SecretKey, Signature};
 
     impl ed25519_trait::SignatureEncoding for Signature {
         type Repr = Signature;
@@ -552,19 +967,86 @@ mod ed25519_trait {
             message: &[u8],
             signature: &Signature,
         ) -> Result<(), ed25519_trait::Error> {
-            #[cfg(feature = "std")]
-            {
-                self.verify(message, signature)
-                    .map_err(ed25519_trait::Error::from_source)
-            }
+            self.verify(message, signature).map_err(trait_error)
+        }
+    }
 
-            #[cfg(not(feature = "std"))]
-            {
-                self.verify(message, signature)
-                    .map_err(|_| ed25519_trait::Error::new())
-            }
+    /// Ed25519ph, with an empty context.
+    #[cfg(feature = "digest")]
+    impl ed25519_trait::DigestSigner<sha512::Hash, Signature> for SecretKey {
+        fn try_sign_digest<F: Fn(&mut sha512::Hash) -> Result<(), ed25519_trait::Error>>(
+            &self,
+            f: F,
+        ) -> Result<Signature, ed25519_trait::Error> {
+            let mut h = sha512::Hash::new();
+            f(&mut h)?;
+            self.sign_prehashed(&h.finalize(), None, None)
+                .map_err(trait_error)
         }
     }
+
+    /// Ed25519ph, with an empty context.
+    #[cfg(feature = "digest")]
+    impl ed25519_trait::DigestVerifier<sha512::Hash, Signature> for PublicKey {
+        fn verify_digest<F: Fn(&mut sha512::Hash) -> Result<(), ed25519_trait::Error>>(
+            &self,
+            f: F,
+            signature: &Signature,
+        ) -> Result<(), ed25519_trait::Error> {
+            let mut h = sha512::Hash::new();
+            f(&mut h)?;
+            self.verify_prehashed(&h.finalize(), None, signature)
+                .map_err(trait_error)
+        }
+    }
+
+    fn trait_error(e: Error) -> ed25519_trait::Error {
+        #[cfg(feature = "std")]
+        {
+            ed25519_trait::Error::from_source(e)
+        }
+
+        #[cfg(not(feature = "std"))]
+        {
+            let _ = e;
+            ed25519_trait::Error::new()
+        }
+    }
+
+    #[cfg(feature = "digest")]
+    #[test]
+    fn test_digest_signer() {
+        use ed25519_trait::{DigestSigner, DigestVerifier};
+
+        use super::KeyPair;
+
+        let kp = KeyPair::from_seed([42u8; 32].into());
+        let signature = kp.sk.sign_digest(|h: &mut sha512::Hash| h.update(b"abc"));
+        let prehash = sha512::Hash::hash(b"abc");
+        assert_eq!(
+            signature,
+            kp.sk.sign_prehashed(&prehash, None, None).unwrap()
+        );
+        kp.pk
+            .verify_digest(
+                |h: &mut sha512::Hash| {
+                    h.update(b"abc");
+                    Ok(())
+                },
+                &signature,
+            )
+            .unwrap();
+        assert!(kp
+            .pk
+            .verify_digest(
+                |h: &mut sha512::Hash| {
+                    h.update(b"abd");
+                    Ok(())
+                },
+                &signature,
+            )
+            .is_err());
+    }
 }
 
 #[test]
@@ -589,9 +1071,28 @@ fn test_ed25519() {
 mod blind_keys {
     use super::*;
 
-    #[derive(Clone, Debug, Eq, PartialEq, Hash)]
+    #[derive(Clone, Eq)]
     pub struct Blind([u8; Blind::BYTES]);
 
+    impl fmt::Debug for Blind {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("Blind(..)")
+        }
+    }
+
+    impl PartialEq for Blind {
+        /// Compares two blinds in constant time.
+        fn eq(&self, other: &Self) -> bool {
+            Mem::ct_eq(&self.0, &other.0)
+        }
+    }
+
+    impl Hash for Blind {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            self.0.hash(state)
+        }
+    }
+
     impl From<[u8; 32]> for Blind {
         fn from(blind: [u8; 32]) -> Self {
             Blind(blind)
@@ -624,6 +1125,16 @@ mod blind_keys {
         }
     }
 
+    #[cfg(feature = "zeroize")]
+    impl zeroize::Zeroize for Blind {
+        fn zeroize(&mut self) {
+            Mem::wipe(&mut self.0)
+        }
+    }
+
+    #[cfg(feature = "zeroize")]
+    impl zeroize::ZeroizeOnDrop for Blind {}
+
     #[cfg(feature = "random")]
     impl Default for Blind {
         /// Generates a random blind.
@@ -642,6 +1153,16 @@ mod blind_keys {
         }
     }
 
+    #[cfg(feature = "rand_core")]
+    impl Blind {
+        /// Generates a random blind using `rng`.
+        pub fn generate_with_rng<R: rand_core::CryptoRng + ?Sized>(rng: &mut R) -> Self {
+            let mut blind = [0u8; Blind::BYTES];
+            rng.fill_bytes(&mut blind);
+            Blind(blind)
+        }
+    }
+
     impl Deref for Blind {
         type Target = [u8; Blind::BYTES];
 
@@ -658,6 +1179,13 @@ mod blind_keys {
         }
     }
 
+    #[cfg(feature = "subtle")]
+    impl subtle::ConstantTimeEq for Blind {
+        fn ct_eq(&self, other: &Self) -> subtle::Choice {
+            subtle::ConstantTimeEq::ct_eq(&self.0[..], &other.0[..])
+        }
+    }
+
     /// A blind public key.
     #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
     pub struct BlindPublicKey([u8; PublicKey::BYTES]);
@@ -734,13 +1262,38 @@ mod blind_keys {
     }
 
     /// A blind secret key.
-    #[derive(Clone, Debug, Eq, PartialEq, Hash)]
+    #[derive(Clone, Eq)]
     pub struct BlindSecretKey {
         pub prefix: [u8; 2 * Seed::BYTES],
         pub blind_scalar: [u8; 32],
         pub blind_pk: BlindPublicKey,
     }
 
+    impl fmt::Debug for BlindSecretKey {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("BlindSecretKey")
+                .field("blind_pk", &self.blind_pk)
+                .finish_non_exhaustive()
+        }
+    }
+
+    impl PartialEq for BlindSecretKey {
+        /// Compares two blind secret keys in constant time.
+        fn eq(&self, other: &Self) -> bool {
+            Mem::ct_eq(&self.prefix, &other.prefix)
+                & Mem::ct_eq(&self.blind_scalar, &other.blind_scalar)
+                & Mem::ct_eq(&self.blind_pk[..], &other.blind_pk[..])
+        }
+    }
+
+    impl Hash for BlindSecretKey {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            self.prefix.hash(state);
+            self.blind_scalar.hash(state);
+            self.blind_pk.hash(state);
+        }
+    }
+
     #[derive(Clone, Debug, Eq, PartialEq, Hash)]
     pub struct BlindKeyPair {
         /// Public key part of the blind key pair.
@@ -759,9 +1312,9 @@ mod blind_keys {
                 let mut hasher = sha512::Hash::new();
                 if let Some(noise) = noise {
                     hasher.update(&noise[..]);
-                    hasher.update(&self.prefix);
+                    hasher.update(&self.prefix[..]);
                 } else {
-                    hasher.update(&self.prefix);
+                    hasher.update(&self.prefix[..]);
                 }
                 hasher.update(message);
                 let mut hash_output = hasher.finalize();
@@ -803,6 +1356,27 @@ mod blind_keys {
         }
     }
 
+    #[cfg(feature = "zeroize")]
+    impl zeroize::Zeroize for BlindSecretKey {
+        fn zeroize(&mut self) {
+            Mem::wipe(&mut self.prefix);
+            Mem::wipe(&mut self.blind_scalar);
+        }
+    }
+
+    #[cfg(feature = "zeroize")]
+    impl zeroize::ZeroizeOnDrop for BlindSecretKey {}
+
+    #[cfg(feature = "zeroize")]
+    impl zeroize::Zeroize for BlindKeyPair {
+        fn zeroize(&mut self) {
+            zeroize::Zeroize::zeroize(&mut self.blind_sk)
+        }
+    }
+
+    #[cfg(feature = "zeroize")]
+    impl zeroize::ZeroizeOnDrop for BlindKeyPair {}
+
     impl PublicKey {
         /// Returns a blind version of the public key.
         pub fn blind(&self, blind: &Blind, ctx: impl AsRef<[u8]>) -> Result<BlindPublicKey, Error> {
@@ -940,6 +1514,85 @@ fn test_streaming() {
     assert!(st.verify().is_ok());
 }
 
+#[test]
+fn test_ed25519ph() {
+    // RFC 8032, section 7.3
+    let kp = KeyPair::from_seed(Seed::new([
+        0x83, 0x3f, 0xe6, 0x24, 0x09, 0x23, 0x7b, 0x9d, 0x62, 0xec, 0x77, 0x58, 0x75, 0x20, 0x91,
+        0x1e, 0x9a, 0x75, 0x9c, 0xec, 0x1d, 0x19, 0x75, 0x5b, 0x7d, 0xa9, 0x01, 0xb9, 0x6d, 0xca,
+        0x3d, 0x42,
+    ]));
+    let prehash = sha512::Hash::hash(b"abc");
+    let signature = kp.sk.sign_prehashed(&prehash, None, None).unwrap();
+    assert_eq!(
+        signature.as_ref(),
+        [
+            0x98, 0xa7, 0x02, 0x22, 0xf0, 0xb8, 0x12, 0x1a, 0xa9, 0xd3, 0x0f, 0x81, 0x3d, 0x68,
+            0x3f, 0x80, 0x9e, 0x46, 0x2b, 0x46, 0x9c, 0x7f, 0xf8, 0x76, 0x39, 0x49, 0x9b, 0xb9,
+            0x4e, 0x6d, 0xae, 0x41, 0x31, 0xf8, 0x50, 0x42, 0x46, 0x3c, 0x2a, 0x35, 0x5a, 0x20,
+            0x03, 0xd0, 0x62, 0xad, 0xf5, 0xaa, 0xa1, 0x0b, 0x8c, 0x61, 0xe6, 0x36, 0x06, 0x2a,
+            0xaa, 0xd1, 0x1c, 0x2a, 0x26, 0x08, 0x34, 0x06,
+        ]
+    );
+    kp.pk.verify_prehashed(&prehash, None, &signature).unwrap();
+    assert!(kp.pk.verify(prehash, &signature).is_err());
+
+    let signature = kp
+        .sk
+        .sign_prehashed(&prehash, Some(b"context"), None)
+        .unwrap();
+    assert_eq!(
+        signature.as_ref(),
+        [
+            0x36, 0x83, 0x4f, 0xb4, 0xac, 0xb8, 0x78, 0x26, 0x44, 0x40, 0xdc, 0x91, 0x64, 0x16,
+            0xda, 0x72, 0x8d, 0x56, 0xb0, 0xa3, 0x5c, 0x45, 0xa0, 0x15, 0x95, 0x88, 0x00, 0x60,
+            0xc4, 0xd1, 0xd4, 0x74, 0xe0, 0x1e, 0xa7, 0xa4, 0xb3, 0xd4, 0xad, 0x0b, 0xf5, 0x5a,
+            0x56, 0x76, 0x90, 0x2d, 0x4a, 0x05, 0x7a, 0xe4, 0xc3, 0x23, 0x54, 0x7a, 0x73, 0xec,
+            0x10, 0x4d, 0x56, 0xe8, 0x96, 0x3d, 0x63, 0x03,
+        ]
+    );
+    kp.pk
+        .verify_prehashed(&prehash, Some(b"context"), &signature)
+        .unwrap();
+    assert!(kp.pk.verify_prehashed(&prehash, None, &signature).is_err());
+    assert_eq!(
+        kp.sk.sign_prehashed(&prehash, Some(&[0u8; 256]), None),
+        Err(Error::InvalidParameters)
+    );
+}
+
+#[test]
+fn test_streaming_export() {
+    let kp = KeyPair::from_seed([42u8; 32].into());
+    let msg = [0x42u8; 1000];
+
+    let mut st = kp
+        .sk
+        .sign_incremental(Noise::from_slice(&[1u8; 16]).unwrap());
+    st.absorb(&msg[..300]);
+    let exported = st.export();
+    let other = KeyPair::from_seed([43u8; 32].into());
+    assert!(SigningState::import(&exported, &other.sk).is_err());
+    let mut tampered = exported;
+    tampered[100] ^= 1;
+    assert!(SigningState::import(&tampered, &kp.sk).is_err());
+    let mut st2 = SigningState::import(&exported, &kp.sk).unwrap();
+    st.absorb(&msg[300..]);
+    st2.absorb(&msg[300..]);
+    let signature = st2.sign();
+    assert_eq!(signature, st.sign());
+    kp.pk.verify(&msg[..], &signature).unwrap();
+
+    let mut st = kp.pk.verify_incremental(&signature).unwrap();
+    st.absorb(&msg[..700]);
+    let exported = st.export(b"key");
+    assert!(VerifyingState::import(&exported, &other.pk, b"key").is_err());
+    assert!(VerifyingState::import(&exported, &kp.pk, b"other key").is_err());
+    let mut st = VerifyingState::import(&exported, &kp.pk, b"key").unwrap();
+    st.absorb(&msg[700..]);
+    st.verify().unwrap();
+}
+
 #[test]
 #[cfg(feature = "random")]
 fn test_ed25519_invalid_keypair() {
@@ -972,3 +1625,166 @@ fn test_reject_noncanonical_identity_forgery() {
 
     assert!(pk.verify(b"any message", &signature).is_err());
 }
+
+#[cfg(feature = "std")]
+#[test]
+fn test_multiscalar_mul() {
+    const L_MINUS_1: [u8; 32] = [
+        0xec, 0xd3, 0xf5, 0x5c, 0x1a, 0x63, 0x12, 0x58, 0xd6, 0x9c, 0xf7, 0xa2, 0xde, 0xf9, 0xde,
+        0x14, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x10,
+    ];
+    let mut one = [0u8; 32];
+    one[0] = 1;
+    let base = ge_scalarmult_base(&one).to_bytes();
+
+    // S*B - k*A = R
+    for i in 0..4u8 {
+        let kp = KeyPair::from_seed([i + 1; 32].into());
+        let msg = [i; 100];
+        let signature = kp.sk.sign(msg, None);
+        let mut hasher = sha512::Hash::new();
+        hasher.update(&signature[..32]);
+        hasher.update(&kp.pk[..]);
+        hasher.update(msg);
+        let mut hram = hasher.finalize();
+        sc_reduce(&mut hram);
+        let mut neg_k = [0u8; 32];
+        sc_muladd(&mut neg_k, &hram[..32], &L_MINUS_1, &[0u8; 32]);
+        let mut s = [0u8; 32];
+        s.copy_from_slice(&signature[32..]);
+
+        let scalars = [s, neg_k];
+        let points = [base, *kp.pk];
+        assert_eq!(multiscalar_mul(&scalars, &points).unwrap(), signature[..32]);
+        assert_eq!(
+            multiscalar_mul_ct(&scalars, &points).unwrap(),
+            signature[..32]
+        );
+    }
+
+    let identity = multiscalar_mul(&[], &[]).unwrap();
+    assert!(is_identity(&identity));
+    assert_eq!(multiscalar_mul(&[one], &[]), Err(Error::InvalidParameters));
+    assert_eq!(
+        multiscalar_mul(&[[0xff; 32]], &[base]),
+        Err(Error::NonCanonical)
+    );
+    let mut invalid = [0u8; 32];
+    invalid[0] = 2;
+    assert_eq!(
+        multiscalar_mul_ct(&[one], &[invalid]),
+        Err(Error::InvalidPublicKey)
+    );
+}
+
+#[cfg(feature = "zeroize")]
+#[test]
+fn test_zeroize() {
+    use zeroize::Zeroize;
+
+    let mut kp = KeyPair::from_seed([42u8; 32].into());
+    let pk = kp.pk;
+    kp.zeroize();
+    assert_eq!(*kp.sk, [0u8; SecretKey::BYTES]);
+    assert_eq!(kp.pk, pk);
+
+    let mut seed = Seed::new([42u8; 32]);
+    seed.zeroize();
+    assert_eq!(*seed, [0u8; Seed::BYTES]);
+}
+
+#[cfg(feature = "subtle")]
+#[test]
+fn test_subtle() {
+    use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+
+    let kp1 = KeyPair::from_seed([1u8; 32].into());
+    let kp2 = KeyPair::from_seed([2u8; 32].into());
+    assert!(bool::from(kp1.sk.ct_eq(&kp1.sk.clone())));
+    assert!(!bool::from(kp1.sk.ct_eq(&kp2.sk)));
+    assert!(!bool::from(kp1.pk.ct_eq(&kp2.pk)));
+    assert_eq!(
+        PublicKey::conditional_select(&kp1.pk, &kp2.pk, Choice::from(0)),
+        kp1.pk
+    );
+    assert_eq!(
+        PublicKey::conditional_select(&kp1.pk, &kp2.pk, Choice::from(1)),
+        kp2.pk
+    );
+
+    let s1 = kp1.sk.sign(b"message", None);
+    let s2 = kp2.sk.sign(b"message", None);
+    assert!(bool::from(s1.ct_eq(&s1)));
+    assert!(!bool::from(s1.ct_eq(&s2)));
+    assert_eq!(Signature::conditional_select(&s1, &s2, Choice::from(1)), s2);
+}
+
+#[cfg(feature = "rand_core")]
+#[test]
+fn test_generate_with_rng() {
+    struct CounterRng(u8);
+
+    impl rand_core::TryRng for CounterRng {
+        type Error = core::convert::Infallible;
+
+        fn try_next_u32(&mut self) -> Result<u32, Self::Error> {
+            let mut b = [0u8; 4];
+            self.try_fill_bytes(&mut b)?;
+            Ok(u32::from_le_bytes(b))
+        }
+
+        fn try_next_u64(&mut self) -> Result<u64, Self::Error> {
+            let mut b = [0u8; 8];
+            self.try_fill_bytes(&mut b)?;
+            Ok(u64::from_le_bytes(b))
+        }
+
+        fn try_fill_bytes(&mut self, dst: &mut [u8]) -> Result<(), Self::Error> {
+            for x in dst {
+                self.0 = self.0.wrapping_add(1);
+                *x = self.0;
+            }
+            Ok(())
+        }
+    }
+
+    impl rand_core::TryCryptoRng for CounterRng {}
+
+    let seed = Seed::generate_with_rng(&mut CounterRng(0));
+    assert_eq!(seed[0], 1);
+    assert_eq!(seed[31], 32);
+    let kp = KeyPair::generate_with_rng(&mut CounterRng(0));
+    assert_eq!(kp, KeyPair::from_seed(seed));
+
+    let noise = Noise::generate_with_rng(&mut CounterRng(100));
+    let sign = |rng: &mut CounterRng| {
+        let mut st = kp.sk.sign_incremental_with_rng(noise, rng);
+        st.absorb(b"message");
+        st.sign()
+    };
+    let signature = sign(&mut CounterRng(7));
+    assert_eq!(signature, sign(&mut CounterRng(7)));
+    assert_ne!(signature, sign(&mut CounterRng(8)));
+    kp.pk.verify(b"message", &signature).unwrap();
+
+    #[cfg(feature = "x25519")]
+    {
+        let kp = crate::x25519::KeyPair::generate_with_rng(&mut CounterRng(0));
+        assert_eq!(*kp.sk, *seed);
+        kp.validate().unwrap();
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_debug_redacts_secrets() {
+    let kp = KeyPair::from_seed([42u8; 32].into());
+    assert_eq!(format!("{:?}", kp.sk), "SecretKey(..)");
+    assert_eq!(format!("{:?}", kp.sk.seed()), "Seed(..)");
+    assert_eq!(format!("{:?}", Noise::new([42u8; 16])), "Noise(..)");
+    assert_eq!(
+        format!("{:?}", kp),
+        format!("KeyPair {{ pk: {:?}, sk: SecretKey(..) }}", kp.pk)
+    );
+}
@@ -1,6 +1,8 @@
 use core::cmp::min;
 use core::ops::{Add, Sub};
 
+#[cfg(feature = "fast-base")]
+use super::basepoint_table::BASEPOINT_TABLE_LIMBS;
 use super::error::*;
 use super::field25519::*;
 
@@ -42,6 +44,12 @@ pub struct GeCached {
     t2d: Fe,
 }
 
+#[cfg(any(
+    feature = "blind-keys",
+    feature = "std",
+    test,
+    not(feature = "fast-base")
+))]
 impl GeCached {
     #[cfg_attr(feature = "opt_size", inline(never))]
     #[cfg_attr(not(feature = "opt_size"), inline(always))]
@@ -53,7 +61,45 @@ impl GeCached {
     }
 }
 
+#[cfg(feature = "fast-base")]
+impl GePrecomp {
+    const fn from_limbs51(limbs: &[[u64; 5]; 3]) -> GePrecomp {
+        GePrecomp {
+            y_plus_x: Fe::from_limbs51(limbs[0]),
+            y_minus_x: Fe::from_limbs51(limbs[1]),
+            xy2d: Fe::from_limbs51(limbs[2]),
+        }
+    }
+
+    fn zero() -> GePrecomp {
+        GePrecomp {
+            y_plus_x: FE_ONE,
+            y_minus_x: FE_ONE,
+            xy2d: FE_ZERO,
+        }
+    }
+
+    #[cfg_attr(feature = "opt_size", inline(never))]
+    #[cfg_attr(not(feature = "opt_size"), inline(always))]
+    fn maybe_set(&mut self, other: &GePrecomp, do_swap: u8) {
+        self.y_plus_x.maybe_set(&other.y_plus_x, do_swap);
+        self.y_minus_x.maybe_set(&other.y_minus_x, do_swap);
+        self.xy2d.maybe_set(&other.xy2d, do_swap);
+    }
+
+    #[cfg_attr(feature = "opt_size", inline(never))]
+    #[cfg_attr(not(feature = "opt_size"), inline(always))]
+    fn neg(&self) -> GePrecomp {
+        GePrecomp {
+            y_plus_x: self.y_minus_x,
+            y_minus_x: self.y_plus_x,
+            xy2d: self.xy2d.neg(),
+        }
+    }
+}
+
 impl GeP1P1 {
+    #[allow(clippy::wrong_self_convention)]
     #[cfg_attr(feature = "opt_size", inline(never))]
     #[cfg_attr(not(feature = "opt_size"), inline(always))]
     fn to_p2(&self) -> GeP2 {
@@ -64,6 +110,7 @@ impl GeP1P1 {
         }
     }
 
+    #[allow(clippy::wrong_self_convention)]
     #[cfg_attr(feature = "opt_size", inline(never))]
     #[cfg_attr(not(feature = "opt_size"), inline(always))]
     fn to_p3(&self) -> GeP3 {
@@ -158,6 +205,32 @@ impl GeP2 {
         let aslide = GeP2::slide(a_scalar);
         let bslide = GeP2::slide(b_scalar);
 
+        #[cfg(all(
+            feature = "simd",
+            any(
+                target_arch = "x86_64",
+                target_arch = "aarch64",
+                all(target_arch = "wasm32", target_feature = "simd128")
+            )
+        ))]
+        {
+            let bi = BI.map(|b| [b.y_minus_x, b.y_plus_x, FE_ONE + FE_ONE, b.xy2d]);
+            let a = [a_point.x, a_point.y, a_point.z, a_point.t];
+            if let Some([x, y, z, _]) =
+                super::edwards25519_simd::double_scalarmult_vartime(&aslide, &a, &bslide, &bi)
+            {
+                return GeP2 { x, y, z };
+            }
+        }
+        GeP2::double_scalarmult_vartime_slides(&aslide, a_point, &bslide)
+    }
+
+    #[allow(clippy::comparison_chain)]
+    fn double_scalarmult_vartime_slides(
+        aslide: &[i8; 256],
+        a_point: GeP3,
+        bslide: &[i8; 256],
+    ) -> GeP2 {
         let mut ai = [GeCached {
             y_plus_x: FE_ZERO,
             y_minus_x: FE_ZERO,
@@ -256,6 +329,7 @@ impl GeP3 {
         })
     }
 
+    #[allow(clippy::wrong_self_convention)]
     #[cfg_attr(feature = "opt_size", inline(never))]
     #[cfg_attr(not(feature = "opt_size"), inline(always))]
     fn to_p2(&self) -> GeP2 {
@@ -266,6 +340,7 @@ impl GeP3 {
         }
     }
 
+    #[allow(clippy::wrong_self_convention)]
     #[cfg_attr(feature = "opt_size", inline(never))]
     #[cfg_attr(not(feature = "opt_size"), inline(always))]
     fn to_cached(&self) -> GeCached {
@@ -294,6 +369,7 @@ impl GeP3 {
         self.to_p2().dbl()
     }
 
+    #[allow(clippy::wrong_self_convention)]
     pub fn to_bytes(&self) -> [u8; 32] {
         let recip = self.z.invert();
         let x = self.x * recip;
@@ -439,7 +515,12 @@ impl Sub<GePrecomp> for GeP3 {
     }
 }
 
-#[cfg(any(feature = "blind-keys", feature = "opt_size", test))]
+#[cfg(any(
+    feature = "blind-keys",
+    feature = "std",
+    all(feature = "opt_size", not(feature = "fast-base")),
+    test
+))]
 fn ge_precompute(base: &GeP3) -> [GeCached; 16] {
     let base_cached = base.to_cached();
     let mut pc = [GeP3::zero(); 16];
@@ -458,22 +539,64 @@ fn ge_precompute(base: &GeP3) -> [GeCached; 16] {
     pc_cached
 }
 
-#[cfg(any(feature = "blind-keys", feature = "opt_size", test))]
+#[cfg(any(
+    feature = "blind-keys",
+    all(feature = "opt_size", not(feature = "fast-base")),
+    test
+))]
 pub fn ge_scalarmult(scalar: &[u8], base: &GeP3) -> GeP3 {
     let pc = ge_precompute(base);
     ge_scalarmult_precomputed(scalar, &pc)
 }
 
+#[cfg(any(
+    feature = "blind-keys",
+    feature = "std",
+    test,
+    not(feature = "fast-base")
+))]
+#[cfg_attr(feature = "opt_size", inline(never))]
+#[cfg_attr(not(feature = "opt_size"), inline(always))]
+fn ge_select(pc: &[GeCached; 16], scalar: &[u8], pos: usize) -> GeCached {
+    let slot = ((scalar[pos >> 3] >> (pos & 7)) & 15) as usize;
+    let mut t = pc[0];
+    for i in 1..16 {
+        t.maybe_set(&pc[i], (((slot ^ i).wrapping_sub(1)) >> 8) as u8 & 1);
+    }
+    t
+}
+
+#[cfg(any(feature = "blind-keys", test, not(feature = "fast-base")))]
 fn ge_scalarmult_precomputed(scalar: &[u8], pc: &[GeCached; 16]) -> GeP3 {
     let mut q = GeP3::zero();
     let mut pos = 252;
     loop {
-        let slot = ((scalar[pos >> 3] >> (pos & 7)) & 15) as usize;
-        let mut t = pc[0];
-        for i in 1..16 {
-            t.maybe_set(&pc[i], (((slot ^ i).wrapping_sub(1)) >> 8) as u8 & 1);
+        q = q.add(ge_select(pc, scalar, pos)).to_p3();
+        if pos == 0 {
+            break;
+        }
+        q = q.dbl().to_p3().dbl().to_p3().dbl().to_p3().dbl().to_p3();
+        pos -= 4;
+    }
+    q
+}
+
+/// Number of points above which Pippenger's method is used instead of
+/// Straus' method.
+#[cfg(feature = "std")]
+const PIPPENGER_THRESHOLD: usize = 190;
+
+/// Computes `scalars[0] * points[0] + ... + scalars[n-1] * points[n-1]`, in
+/// constant time with respect to the scalars.
+#[cfg(feature = "std")]
+pub fn ge_multiscalar_mul(scalars: &[[u8; 32]], points: &[GeP3]) -> GeP3 {
+    let pcs: Vec<[GeCached; 16]> = points.iter().map(ge_precompute).collect();
+    let mut q = GeP3::zero();
+    let mut pos = 252;
+    loop {
+        for (scalar, pc) in scalars.iter().zip(pcs.iter()) {
+            q = q.add(ge_select(pc, scalar, pos)).to_p3();
         }
-        q = q.add(t).to_p3();
         if pos == 0 {
             break;
         }
@@ -483,37 +606,155 @@ fn ge_scalarmult_precomputed(scalar: &[u8], pc: &[GeCached; 16]) -> GeP3 {
     q
 }
 
-#[cfg(not(feature = "opt_size"))]
+/// Computes `scalars[0] * points[0] + ... + scalars[n-1] * points[n-1]`, in
+/// variable time. Scalars must be lower than `2^255`.
+#[cfg(feature = "std")]
+pub fn ge_multiscalar_mul_vartime(scalars: &[[u8; 32]], points: &[GeP3]) -> GeP3 {
+    if scalars.len() > PIPPENGER_THRESHOLD {
+        ge_multiscalar_mul_pippenger_vartime(scalars, points)
+    } else {
+        ge_multiscalar_mul_straus_vartime(scalars, points)
+    }
+}
+
+/// Straus' method: the doublings are shared, and every point is added using
+/// its own table of odd multiples, as in `double_scalarmult_vartime`.
+#[cfg(feature = "std")]
+#[allow(clippy::comparison_chain)]
+fn ge_multiscalar_mul_straus_vartime(scalars: &[[u8; 32]], points: &[GeP3]) -> GeP3 {
+    let slides: Vec<[i8; 256]> = scalars.iter().map(|s| GeP2::slide(s)).collect();
+    let tables: Vec<[GeCached; 8]> = points
+        .iter()
+        .map(|p| {
+            let mut pi = [p.to_cached(); 8]; // P,3P,5P,7P,9P,11P,13P,15P
+            let p2 = p.dbl().to_p3();
+            for i in 1..8 {
+                pi[i] = (p2 + pi[i - 1]).to_p3().to_cached();
+            }
+            pi
+        })
+        .collect();
+
+    let top = match (0..256)
+        .rev()
+        .find(|&i| slides.iter().any(|slide| slide[i] != 0))
+    {
+        None => return GeP3::zero(),
+        Some(top) => top,
+    };
+    let mut r = GeP2::zero();
+    for i in (0..=top).rev() {
+        let mut t = r.dbl();
+        for (slide, pi) in slides.iter().zip(tables.iter()) {
+            if slide[i] > 0 {
+                t = t.to_p3() + pi[(slide[i] / 2) as usize];
+            } else if slide[i] < 0 {
+                t = t.to_p3() - pi[(-slide[i] / 2) as usize];
+            }
+        }
+        r = t.to_p2();
+    }
+    GeP3::from(r)
+}
+
+/// Returns the signed radix `2^w` digits of `s`, in `[-2^(w-1), 2^(w-1))`,
+/// for `6 <= w <= 8`.
+#[cfg(feature = "std")]
+fn radix_2w(s: &[u8; 32], w: usize) -> [i8; 43] {
+    let mut digits = [0i8; 43];
+    let mut carry = 0i32;
+    for (i, digit) in digits.iter_mut().enumerate().take(256 / w + 1) {
+        let mut window = 0i32;
+        for j in (i * w..min((i + 1) * w, 256)).rev() {
+            window = (window << 1) | ((s[j >> 3] >> (j & 7)) & 1) as i32;
+        }
+        let coef = carry + window;
+        carry = (coef + (1 << (w - 1))) >> w;
+        *digit = (coef - (carry << w)) as i8;
+    }
+    digits
+}
+
+/// Pippenger's bucket method: for every window, points are accumulated into
+/// buckets indexed by their digit, and the buckets are then summed with their
+/// weights using two running sums.
+#[cfg(feature = "std")]
+#[allow(clippy::comparison_chain)]
+fn ge_multiscalar_mul_pippenger_vartime(scalars: &[[u8; 32]], points: &[GeP3]) -> GeP3 {
+    let w = if scalars.len() < 500 {
+        6
+    } else if scalars.len() < 800 {
+        7
+    } else {
+        8
+    };
+    let digits: Vec<[i8; 43]> = scalars.iter().map(|s| radix_2w(s, w)).collect();
+    let cached: Vec<GeCached> = points.iter().map(|p| p.to_cached()).collect();
+    let mut buckets = vec![GeP3::zero(); 1 << (w - 1)];
+
+    let mut r = GeP3::zero();
+    for i in (0..=256 / w).rev() {
+        let mut t = r.to_p2();
+        for _ in 1..w {
+            t = t.dbl().to_p2();
+        }
+        r = t.dbl().to_p3();
+
+        buckets.iter_mut().for_each(|b| *b = GeP3::zero());
+        for (d, p) in digits.iter().zip(cached.iter()) {
+            let d = d[i] as i32;
+            if d > 0 {
+                let b = &mut buckets[(d - 1) as usize];
+                *b = (*b + *p).to_p3();
+            } else if d < 0 {
+                let b = &mut buckets[(-d - 1) as usize];
+                *b = (*b - *p).to_p3();
+            }
+        }
+
+        // sum(k * buckets[k - 1])
+        let mut running = GeP3::zero();
+        let mut sum = GeP3::zero();
+        for b in buckets.iter().rev() {
+            running = running + *b;
+            sum = sum + running;
+        }
+        r = r + sum;
+    }
+    r
+}
+
+#[cfg(all(not(feature = "opt_size"), any(test, not(feature = "fast-base"))))]
 static BASEPOINT_PC: [GeCached; 16] = [
     GeCached {
-        y_plus_x: Fe([1, 0, 0, 0, 0]),
-        y_minus_x: Fe([
+        y_plus_x: Fe::from_limbs51([1, 0, 0, 0, 0]),
+        y_minus_x: Fe::from_limbs51([
             2251799813685230,
             2251799813685247,
             2251799813685247,
             2251799813685247,
             2251799813685247,
         ]),
-        z: Fe([1, 0, 0, 0, 0]),
-        t2d: Fe([0, 0, 0, 0, 0]),
+        z: Fe::from_limbs51([1, 0, 0, 0, 0]),
+        t2d: Fe::from_limbs51([0, 0, 0, 0, 0]),
     },
     GeCached {
-        y_plus_x: Fe([
+        y_plus_x: Fe::from_limbs51([
             3540182452943730,
             2497478415033846,
             2521227595762870,
             1462984067271729,
             2389212253076811,
         ]),
-        y_minus_x: Fe([
+        y_minus_x: Fe::from_limbs51([
             62697248952638,
             204681361388450,
             631292143396476,
             338455783676468,
             1213667448819585,
         ]),
-        z: Fe([1, 0, 0, 0, 0]),
-        t2d: Fe([
+        z: Fe::from_limbs51([1, 0, 0, 0, 0]),
+        t2d: Fe::from_limbs51([
             301289933810280,
             1259582250014073,
             1422107436869536,
@@ -522,28 +763,28 @@ static BASEPOINT_PC: [GeCached; 16] = [
         ]),
     },
     GeCached {
-        y_plus_x: Fe([
+        y_plus_x: Fe::from_limbs51([
             2899251262813612,
             2105814100803166,
             1713949677790798,
             3215891443006982,
             2739384323378089,
         ]),
-        y_minus_x: Fe([
+        y_minus_x: Fe::from_limbs51([
             2068674118847085,
             1963549090372877,
             1018819090357341,
             202036157367742,
             2216582574168879,
         ]),
-        z: Fe([
+        z: Fe::from_limbs51([
             939820407267714,
             2244711721325457,
             833935350215291,
             514982476710626,
             395358860036744,
         ]),
-        t2d: Fe([
+        t2d: Fe::from_limbs51([
             480126441932807,
             360939894744223,
             414963024589262,
@@ -552,28 +793,28 @@ static BASEPOINT_PC: [GeCached; 16] = [
         ]),
     },
     GeCached {
-        y_plus_x: Fe([
+        y_plus_x: Fe::from_limbs51([
             3203379641193801,
             2671375269558872,
             2070381889117393,
             3408704250464255,
             2000447963965959,
         ]),
-        y_minus_x: Fe([
+        y_minus_x: Fe::from_limbs51([
             904464151500333,
             1771035278322932,
             211167236544955,
             1536100403113214,
             1726723339398472,
         ]),
-        z: Fe([
+        z: Fe::from_limbs51([
             1061705710463460,
             150884959185907,
             158377546300656,
             2007006659873485,
             1408482789065644,
         ]),
-        t2d: Fe([
+        t2d: Fe::from_limbs51([
             1742083302726619,
             1616172736080930,
             1275485487654045,
@@ -582,28 +823,28 @@ static BASEPOINT_PC: [GeCached; 16] = [
         ]),
     },
     GeCached {
-        y_plus_x: Fe([
+        y_plus_x: Fe::from_limbs51([
             1695410796818793,
             2731695111583170,
             2714556126557059,
             451274161496800,
             2012078832798171,
         ]),
-        y_minus_x: Fe([
+        y_minus_x: Fe::from_limbs51([
             375025409391801,
             169834297521966,
             387980374534797,
             334628214200116,
             1830506172326927,
         ]),
-        z: Fe([
+        z: Fe::from_limbs51([
             1904279982576693,
             1467233916658614,
             1298643500842388,
             324859978027951,
             133149465829202,
         ]),
-        t2d: Fe([
+        t2d: Fe::from_limbs51([
             1995200425888795,
             1351231165224936,
             2176876606696969,
@@ -612,28 +853,28 @@ static BASEPOINT_PC: [GeCached; 16] = [
         ]),
     },
     GeCached {
-        y_plus_x: Fe([
+        y_plus_x: Fe::from_limbs51([
             2926516234834723,
             1450717128675707,
             3397919271948925,
             979738693950965,
             1347227353637357,
         ]),
-        y_minus_x: Fe([
+        y_minus_x: Fe::from_limbs51([
             1410465607609101,
             1665000734678196,
             803676666699956,
             2236342313567835,
             1245809528730440,
         ]),
-        z: Fe([
+        z: Fe::from_limbs51([
             882236476409592,
             1497675105009012,
             2181927182817440,
             722619068391161,
             819202421408532,
         ]),
-        t2d: Fe([
+        t2d: Fe::from_limbs51([
             546543842467106,
             1449778946980981,
             892502568874740,
@@ -642,28 +883,28 @@ static BASEPOINT_PC: [GeCached; 16] = [
         ]),
     },
     GeCached {
-        y_plus_x: Fe([
+        y_plus_x: Fe::from_limbs51([
             2609877896546266,
             1096697072067135,
             1971135628989201,
             3414701313108708,
             4210899004685168,
         ]),
-        y_minus_x: Fe([
+        y_minus_x: Fe::from_limbs51([
             599697439270285,
             1382998135852290,
             771968960200254,
             1878135829456840,
             2103521242054977,
         ]),
-        z: Fe([
+        z: Fe::from_limbs51([
             406273932530493,
             1601638185061342,
             1151347692721435,
             1495804819323688,
             118365337991367,
         ]),
-        t2d: Fe([
+        t2d: Fe::from_limbs51([
             783552521469343,
             550229790668946,
             2052808141901586,
@@ -672,28 +913,28 @@ static BASEPOINT_PC: [GeCached; 16] = [
         ]),
     },
     GeCached {
-        y_plus_x: Fe([
+        y_plus_x: Fe::from_limbs51([
             2159545607990893,
             3147448510622730,
             486881527044235,
             2248590592936728,
             1895208328891224,
         ]),
-        y_minus_x: Fe([
+        y_minus_x: Fe::from_limbs51([
             2186604932016262,
             1649225228573991,
             1873456856841000,
             1099532248606737,
             2213142988759011,
         ]),
-        z: Fe([
+        z: Fe::from_limbs51([
             960540512985497,
             1426503578465076,
             2188424813616240,
             1795326679408039,
             1607151223114465,
         ]),
-        t2d: Fe([
+        t2d: Fe::from_limbs51([
             1747734643317249,
             743380680120617,
             1580101169068086,
@@ -702,28 +943,28 @@ static BASEPOINT_PC: [GeCached; 16] = [
         ]),
     },
     GeCached {
-        y_plus_x: Fe([
+        y_plus_x: Fe::from_limbs51([
             3043749755220433,
             1938598694900172,
             3049626199986415,
             3047949431791564,
             2789682914382751,
         ]),
-        y_minus_x: Fe([
+        y_minus_x: Fe::from_limbs51([
             828387156123051,
             243955621460461,
             762099155843483,
             946477271729806,
             1701652729669002,
         ]),
-        z: Fe([
+        z: Fe::from_limbs51([
             207037345464830,
             1993080251615457,
             560932193579569,
             999626924140364,
             754134581836708,
         ]),
-        t2d: Fe([
+        t2d: Fe::from_limbs51([
             482766940000144,
             33540757945105,
             749360811823239,
@@ -732,28 +973,28 @@ static BASEPOINT_PC: [GeCached; 16] = [
         ]),
     },
     GeCached {
-        y_plus_x: Fe([
+        y_plus_x: Fe::from_limbs51([
             2240304515960624,
             3537386921097767,
             2695895682995147,
             1811157917985109,
             2779938286529648,
         ]),
-        y_minus_x: Fe([
+        y_minus_x: Fe::from_limbs51([
             1022781769132038,
             585623358173500,
             709581212592317,
             836667937950586,
             1408009946183497,
         ]),
-        z: Fe([
+        z: Fe::from_limbs51([
             1934056789485370,
             2187228471026461,
             79683303172571,
             246283662803711,
             749534771196139,
         ]),
-        t2d: Fe([
+        t2d: Fe::from_limbs51([
             540262790467214,
             290190952004667,
             216950454494661,
@@ -762,28 +1003,28 @@ static BASEPOINT_PC: [GeCached; 16] = [
         ]),
     },
     GeCached {
-        y_plus_x: Fe([
+        y_plus_x: Fe::from_limbs51([
             1512916939093555,
             2992645879250201,
             2528509081340787,
             2131332975592474,
             761336180782299,
         ]),
-        y_minus_x: Fe([
+        y_minus_x: Fe::from_limbs51([
             405457505626004,
             1019703549184813,
             1916987915502681,
             1839883741534417,
             2193392785174467,
         ]),
-        z: Fe([
+        z: Fe::from_limbs51([
             1818564342032891,
             1136491851523721,
             35093607622580,
             1441385653535882,
             732801009348817,
         ]),
-        t2d: Fe([
+        t2d: Fe::from_limbs51([
             253284364260683,
             1123031426303723,
             1341521644054804,
@@ -792,28 +1033,28 @@ static BASEPOINT_PC: [GeCached; 16] = [
         ]),
     },
     GeCached {
-        y_plus_x: Fe([
+        y_plus_x: Fe::from_limbs51([
             2365398187489437,
             1124628442973815,
             2499161664361135,
             1471589956131712,
             1510258093099827,
         ]),
-        y_minus_x: Fe([
+        y_minus_x: Fe::from_limbs51([
             905705748835765,
             1202252692568785,
             1536609018933038,
             1804479828235729,
             308758094835482,
         ]),
-        z: Fe([
+        z: Fe::from_limbs51([
             260719394240982,
             249358397423578,
             1898576669281045,
             1319825585617897,
             2203484913493586,
         ]),
-        t2d: Fe([
+        t2d: Fe::from_limbs51([
             1074592667060695,
             2190819092840175,
             1113659602279329,
@@ -822,28 +1063,28 @@ static BASEPOINT_PC: [GeCached; 16] = [
         ]),
     },
     GeCached {
-        y_plus_x: Fe([
+        y_plus_x: Fe::from_limbs51([
             363425857861601,
             1734418183714081,
             4179723901978219,
             2926060052259184,
             3820257750753629,
         ]),
-        y_minus_x: Fe([
+        y_minus_x: Fe::from_limbs51([
             2088069355629081,
             2131073356111154,
             273789259070562,
             1547386807830052,
             3563777439570,
         ]),
-        z: Fe([
+        z: Fe::from_limbs51([
             207103967810401,
             768727957346634,
             718644737663762,
             1362212140629448,
             1529120863451279,
         ]),
-        t2d: Fe([
+        t2d: Fe::from_limbs51([
             989664194820699,
             1717065545893193,
             1514630822111268,
@@ -852,28 +1093,28 @@ static BASEPOINT_PC: [GeCached; 16] = [
         ]),
     },
     GeCached {
-        y_plus_x: Fe([
+        y_plus_x: Fe::from_limbs51([
             2431640481383299,
             3535729509476129,
             3035644312764025,
             2313551322106050,
             2935089592225046,
         ]),
-        y_minus_x: Fe([
+        y_minus_x: Fe::from_limbs51([
             765935297493716,
             649629965652465,
             1104455927412399,
             95176349810862,
             1242329781295592,
         ]),
-        z: Fe([
+        z: Fe::from_limbs51([
             2250000172488624,
             1617330080438570,
             1107263496438848,
             2112155113306328,
             980993448222351,
         ]),
-        t2d: Fe([
+        t2d: Fe::from_limbs51([
             862537504556532,
             566266187869470,
             2038862002856850,
@@ -882,28 +1123,28 @@ static BASEPOINT_PC: [GeCached; 16] = [
         ]),
     },
     GeCached {
-        y_plus_x: Fe([
+        y_plus_x: Fe::from_limbs51([
             2315993817135055,
             2652722761171029,
             2522401301012109,
             1075741408880579,
             1706513857602015,
         ]),
-        y_minus_x: Fe([
+        y_minus_x: Fe::from_limbs51([
             311097472864451,
             1284388470688736,
             1100455435689831,
             1913025024780403,
             1582956969378808,
         ]),
-        z: Fe([
+        z: Fe::from_limbs51([
             1081431594790383,
             1952209085138192,
             1101000417182974,
             1343359737131623,
             837835975770184,
         ]),
-        t2d: Fe([
+        t2d: Fe::from_limbs51([
             1468367993771685,
             1651387822379794,
             2229056712608003,
@@ -912,28 +1153,28 @@ static BASEPOINT_PC: [GeCached; 16] = [
         ]),
     },
     GeCached {
-        y_plus_x: Fe([
+        y_plus_x: Fe::from_limbs51([
             2597095379176553,
             3281739572249654,
             3002841564715431,
             204337338762971,
             578630945781619,
         ]),
-        y_minus_x: Fe([
+        y_minus_x: Fe::from_limbs51([
             168655884267790,
             1669918081707416,
             420990948374884,
             2141888286050751,
             2040850418048232,
         ]),
-        z: Fe([
+        z: Fe::from_limbs51([
             684290857876024,
             657789607835756,
             1569561823708010,
             1217543024068483,
             2219703400977787,
         ]),
-        t2d: Fe([
+        t2d: Fe::from_limbs51([
             900369721197708,
             875098376162073,
             666599403867478,
@@ -943,13 +1184,80 @@ static BASEPOINT_PC: [GeCached; 16] = [
     },
 ];
 
+#[cfg(feature = "fast-base")]
+static BASEPOINT_TABLE: [[GePrecomp; 8]; 32] = {
+    let zero = GePrecomp::from_limbs51(&[[0; 5]; 3]);
+    let mut table = [[zero; 8]; 32];
+    let mut i = 0;
+    while i < 32 {
+        let mut j = 0;
+        while j < 8 {
+            table[i][j] = GePrecomp::from_limbs51(&BASEPOINT_TABLE_LIMBS[i][j]);
+            j += 1;
+        }
+        i += 1;
+    }
+    table
+};
+
+/// Returns `b * 256^pos * B` for `b` in `[-8, 8]`, in constant time.
+#[cfg(feature = "fast-base")]
+fn ge_select_base(pos: usize, b: i8) -> GePrecomp {
+    let b_negative = ((b as u8) >> 7) & 1;
+    let b_abs = (b as i16 - ((-(b_negative as i16) & (b as i16)) << 1)) as usize;
+    let mut t = GePrecomp::zero();
+    for (i, p) in BASEPOINT_TABLE[pos].iter().enumerate() {
+        t.maybe_set(p, (((b_abs ^ (i + 1)).wrapping_sub(1)) >> 8) as u8 & 1);
+    }
+    let minus_t = t.neg();
+    t.maybe_set(&minus_t, b_negative);
+    t
+}
+
+/// Fixed-base scalar multiplication with signed radix-16 digits, using the
+/// 32x8 precomputed table: only 4 doublings are required.
+#[cfg(feature = "fast-base")]
+fn ge_scalarmult_base_table(scalar: &[u8]) -> GeP3 {
+    let mut a = [0u8; 32];
+    a.copy_from_slice(&scalar[..32]);
+    sc_reduce32(&mut a);
+
+    let mut e = [0i8; 64];
+    for i in 0..32 {
+        e[2 * i] = (a[i] & 15) as i8;
+        e[2 * i + 1] = ((a[i] >> 4) & 15) as i8;
+    }
+    let mut carry = 0i8;
+    for ei in e.iter_mut().take(63) {
+        *ei += carry;
+        carry = (*ei + 8) >> 4;
+        *ei -= carry << 4;
+    }
+    e[63] += carry;
+
+    let mut h = GeP3::zero();
+    for i in (1..64).step_by(2) {
+        h = h.add(ge_select_base(i / 2, e[i])).to_p3();
+    }
+    h = h.dbl().to_p2().dbl().to_p2().dbl().to_p2().dbl().to_p3();
+    for i in (0..64).step_by(2) {
+        h = h.add(ge_select_base(i / 2, e[i])).to_p3();
+    }
+    h
+}
+
 pub fn ge_scalarmult_base(scalar: &[u8]) -> GeP3 {
-    #[cfg(not(feature = "opt_size"))]
+    #[cfg(feature = "fast-base")]
+    {
+        ge_scalarmult_base_table(scalar)
+    }
+
+    #[cfg(all(not(feature = "fast-base"), not(feature = "opt_size")))]
     {
-        return ge_scalarmult_precomputed(scalar, &BASEPOINT_PC);
+        ge_scalarmult_precomputed(scalar, &BASEPOINT_PC)
     }
 
-    #[cfg(feature = "opt_size")]
+    #[cfg(all(not(feature = "fast-base"), feature = "opt_size"))]
     {
         const BXP: [u8; 32] = [
             0x1a, 0xd5, 0x25, 0x8f, 0x60, 0x2d, 0x56, 0xc9, 0xb2, 0xa7, 0x25, 0x95, 0x60, 0xc7,
@@ -994,7 +1302,15 @@ fn test_basepoint_pc_matches_runtime_precompute() {
         z: FE_ONE,
         t: bx * by,
     };
-    assert!(BASEPOINT_PC == ge_precompute(&base));
+    let pc = ge_precompute(&base);
+    #[cfg(all(target_pointer_width = "64", not(feature = "u32-backend")))]
+    assert!(BASEPOINT_PC == pc);
+    for (a, b) in BASEPOINT_PC.iter().zip(pc.iter()) {
+        assert_eq!(a.y_plus_x.to_bytes(), b.y_plus_x.to_bytes());
+        assert_eq!(a.y_minus_x.to_bytes(), b.y_minus_x.to_bytes());
+        assert_eq!(a.z.to_bytes(), b.z.to_bytes());
+        assert_eq!(a.t2d.to_bytes(), b.t2d.to_bytes());
+    }
     for scalar in [
         [0u8; 32],
         [1u8; 32],
@@ -1013,6 +1329,49 @@ fn test_basepoint_pc_matches_runtime_precompute() {
     }
 }
 
+#[cfg(feature = "fast-base")]
+#[test]
+fn test_basepoint_table_matches_generic_path() {
+    use super::sha512;
+
+    const BXP: [u8; 32] = [
+        0x1a, 0xd5, 0x25, 0x8f, 0x60, 0x2d, 0x56, 0xc9, 0xb2, 0xa7, 0x25, 0x95, 0x60, 0xc7, 0x2c,
+        0x69, 0x5c, 0xdc, 0xd6, 0xfd, 0x31, 0xe2, 0xa4, 0xc0, 0xfe, 0x53, 0x6e, 0xcd, 0xd3, 0x36,
+        0x69, 0x21,
+    ];
+    const BYP: [u8; 32] = [
+        0x58, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66,
+        0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66, 0x66,
+        0x66, 0x66,
+    ];
+    let bx = Fe::from_bytes(&BXP);
+    let by = Fe::from_bytes(&BYP);
+    let base = GeP3 {
+        x: bx,
+        y: by,
+        z: FE_ONE,
+        t: bx * by,
+    };
+    let mut scalars = [[0u8; 32]; 4 + 32];
+    scalars[1][0] = 1;
+    scalars[2] = [0x88; 32];
+    scalars[3] = [0xff; 32];
+    for (i, scalar) in scalars[4..].iter_mut().enumerate() {
+        scalar.copy_from_slice(&sha512::Hash::hash([i as u8])[..32]);
+    }
+    for scalar in &scalars {
+        #[cfg(not(feature = "opt_size"))]
+        assert_eq!(
+            ge_scalarmult_base(scalar).to_bytes(),
+            ge_scalarmult_precomputed(scalar, &BASEPOINT_PC).to_bytes()
+        );
+        assert_eq!(
+            ge_scalarmult_base(scalar).to_bytes(),
+            ge_scalarmult(scalar, &base).to_bytes()
+        );
+    }
+}
+
 #[cfg(feature = "x25519")]
 pub fn ge_to_x25519_vartime(s: &[u8; 32]) -> Option<[u8; 32]> {
     let p = GeP3::from_bytes_vartime(s)?;
@@ -1021,6 +1380,16 @@ pub fn ge_to_x25519_vartime(s: &[u8; 32]) -> Option<[u8; 32]> {
     Some(x_mont.to_bytes())
 }
 
+/// Computes the X25519 public key for a clamped scalar, using the Edwards
+/// fixed-base table instead of the Montgomery ladder. This is only faster
+/// with the large table of the `fast-base` feature.
+#[cfg(all(feature = "x25519", feature = "fast-base"))]
+pub fn ge_scalarmult_base_x25519(scalar: &[u8]) -> [u8; 32] {
+    let p = ge_scalarmult_base(scalar);
+    let x_mont = (p.z + p.y) * ((p.z - p.y).invert());
+    x_mont.to_bytes()
+}
+
 pub fn sc_reduce32(s: &mut [u8; 32]) {
     let mut t = [0u8; 64];
     t[0..32].copy_from_slice(s);
@@ -1355,6 +1724,7 @@ pub fn sc_sqmul(s: &[u8], n: usize, a: &[u8]) -> [u8; 32] {
 }
 
 #[cfg(feature = "blind-keys")]
+#[allow(clippy::just_underscores_and_digits)]
 pub fn sc_invert(s: &[u8; 32]) -> [u8; 32] {
     let _10 = sc_sq(s);
     let _11 = sc_mul(s, &_10);
@@ -1914,21 +2284,21 @@ pub fn is_identity(s: &[u8; 32]) -> bool {
 
 static BI: [GePrecomp; 8] = [
     GePrecomp {
-        y_plus_x: Fe([
+        y_plus_x: Fe::from_limbs51([
             1288382639258501,
             245678601348599,
             269427782077623,
             1462984067271730,
             137412439391563,
         ]),
-        y_minus_x: Fe([
+        y_minus_x: Fe::from_limbs51([
             62697248952638,
             204681361388450,
             631292143396476,
             338455783676468,
             1213667448819585,
         ]),
-        xy2d: Fe([
+        xy2d: Fe::from_limbs51([
             301289933810280,
             1259582250014073,
             1422107436869536,
@@ -1937,21 +2307,21 @@ static BI: [GePrecomp; 8] = [
         ]),
     },
     GePrecomp {
-        y_plus_x: Fe([
+        y_plus_x: Fe::from_limbs51([
             1601611775252272,
             1720807796594148,
             1132070835939856,
             1260455018889551,
             2147779492816911,
         ]),
-        y_minus_x: Fe([
+        y_minus_x: Fe::from_limbs51([
             316559037616741,
             2177824224946892,
             1459442586438991,
             1461528397712656,
             751590696113597,
         ]),
-        xy2d: Fe([
+        xy2d: Fe::from_limbs51([
             1850748884277385,
             1200145853858453,
             1068094770532492,
@@ -1960,21 +2330,21 @@ static BI: [GePrecomp; 8] = [
         ]),
     },
     GePrecomp {
-        y_plus_x: Fe([
+        y_plus_x: Fe::from_limbs51([
             769950342298419,
             132954430919746,
             844085933195555,
             974092374476333,
             726076285546016,
         ]),
-        y_minus_x: Fe([
+        y_minus_x: Fe::from_limbs51([
             425251763115706,
             608463272472562,
             442562545713235,
             837766094556764,
             374555092627893,
         ]),
-        xy2d: Fe([
+        xy2d: Fe::from_limbs51([
             1086255230780037,
             274979815921559,
             1960002765731872,
@@ -1983,21 +2353,21 @@ static BI: [GePrecomp; 8] = [
         ]),
     },
     GePrecomp {
-        y_plus_x: Fe([
+        y_plus_x: Fe::from_limbs51([
             665000864555967,
             2065379846933859,
             370231110385876,
             350988370788628,
             1233371373142985,
         ]),
-        y_minus_x: Fe([
+        y_minus_x: Fe::from_limbs51([
             2019367628972465,
             676711900706637,
             110710997811333,
             1108646842542025,
             517791959672113,
         ]),
-        xy2d: Fe([
+        xy2d: Fe::from_limbs51([
             965130719900578,
             247011430587952,
             526356006571389,
@@ -2006,21 +2376,21 @@ static BI: [GePrecomp; 8] = [
         ]),
     },
     GePrecomp {
-        y_plus_x: Fe([
+        y_plus_x: Fe::from_limbs51([
             1802695059465007,
             1664899123557221,
             593559490740857,
             2160434469266659,
             927570450755031,
         ]),
-        y_minus_x: Fe([
+        y_minus_x: Fe::from_limbs51([
             1725674970513508,
             1933645953859181,
             1542344539275782,
             1767788773573747,
             1297447965928905,
         ]),
-        xy2d: Fe([
+        xy2d: Fe::from_limbs51([
             1381809363726107,
             1430341051343062,
             2061843536018959,
@@ -2029,21 +2399,21 @@ static BI: [GePrecomp; 8] = [
         ]),
     },
     GePrecomp {
-        y_plus_x: Fe([
+        y_plus_x: Fe::from_limbs51([
             1970894096313054,
             528066325833207,
             1619374932191227,
             2207306624415883,
             1169170329061080,
         ]),
-        y_minus_x: Fe([
+        y_minus_x: Fe::from_limbs51([
             2070390218572616,
             1458919061857835,
             624171843017421,
             1055332792707765,
             433987520732508,
         ]),
-        xy2d: Fe([
+        xy2d: Fe::from_limbs51([
             893653801273833,
             1168026499324677,
             1242553501121234,
@@ -2052,21 +2422,21 @@ static BI: [GePrecomp; 8] = [
         ]),
     },
     GePrecomp {
-        y_plus_x: Fe([
+        y_plus_x: Fe::from_limbs51([
             213454002618221,
             939771523987438,
             1159882208056014,
             317388369627517,
             621213314200687,
         ]),
-        y_minus_x: Fe([
+        y_minus_x: Fe::from_limbs51([
             1971678598905747,
             338026507889165,
             762398079972271,
             655096486107477,
             42299032696322,
         ]),
-        xy2d: Fe([
+        xy2d: Fe::from_limbs51([
             177130678690680,
             1754759263300204,
             1864311296286618,
@@ -2075,21 +2445,21 @@ static BI: [GePrecomp; 8] = [
         ]),
     },
     GePrecomp {
-        y_plus_x: Fe([
+        y_plus_x: Fe::from_limbs51([
             1913163449625248,
             460779200291993,
             2193883288642314,
             1008900146920800,
             1721983679009502,
         ]),
-        y_minus_x: Fe([
+        y_minus_x: Fe::from_limbs51([
             1070401523076875,
             1272492007800961,
             1910153608563310,
             2075579521696771,
             1191169788841221,
         ]),
-        xy2d: Fe([
+        xy2d: Fe::from_limbs51([
             692896803108118,
             500174642072499,
             2068223309439677,
@@ -2098,3 +2468,155 @@ static BI: [GePrecomp; 8] = [
         ]),
     },
 ];
+
+#[cfg(all(
+    feature = "simd",
+    any(
+        target_arch = "x86_64",
+        target_arch = "aarch64",
+        all(target_arch = "wasm32", target_feature = "simd128")
+    )
+))]
+#[test]
+fn test_double_scalarmult_vartime_simd() {
+    use super::edwards25519_simd;
+    use super::sha512;
+
+    let bi = BI.map(|b| [b.y_minus_x, b.y_plus_x, FE_ONE + FE_ONE, b.xy2d]);
+    let mut scalars = [[0u8; 32]; 4 + 16];
+    scalars[1][0] = 1;
+    scalars[2] = [0x88; 32];
+    scalars[3] = [0xff; 32];
+    scalars[3][31] = 0x0f;
+    for (i, scalar) in scalars[4..].iter_mut().enumerate() {
+        scalar.copy_from_slice(&sha512::Hash::hash([i as u8])[..32]);
+        scalar[31] &= 0x1f;
+    }
+    for (i, a_scalar) in scalars.iter().enumerate() {
+        let b_scalar = &scalars[(i * 7 + 3) % scalars.len()];
+        let a_point = ge_scalarmult_base(&scalars[(i + 5) % scalars.len()]);
+        let expected = GeP3::from(GeP2::double_scalarmult_vartime_slides(
+            &GeP2::slide(a_scalar),
+            a_point,
+            &GeP2::slide(b_scalar),
+        ))
+        .to_bytes();
+        let a = [a_point.x, a_point.y, a_point.z, a_point.t];
+        let [x, y, z, _] = edwards25519_simd::double_scalarmult_vartime_portable(
+            &GeP2::slide(a_scalar),
+            &a,
+            &GeP2::slide(b_scalar),
+            &bi,
+        );
+        assert_eq!(GeP3::from(GeP2 { x, y, z }).to_bytes(), expected);
+        assert_eq!(
+            GeP3::from(GeP2::double_scalarmult_vartime(a_scalar, a_point, b_scalar)).to_bytes(),
+            expected
+        );
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_multiscalar_mul() {
+    use super::sha512;
+
+    let mut scalars = [[0u8; 32]; 4 + 36];
+    scalars[1][0] = 1;
+    scalars[2] = [0x88; 32];
+    scalars[3] = [0xff; 32];
+    scalars[3][31] = 0x0f;
+    let mut points = [GeP3::zero(); 4 + 36];
+    for (i, (scalar, point)) in scalars[4..]
+        .iter_mut()
+        .zip(points[4..].iter_mut())
+        .enumerate()
+    {
+        scalar.copy_from_slice(&sha512::Hash::hash([i as u8])[..32]);
+        sc_reduce32(scalar);
+        *point = ge_scalarmult_base(&sha512::Hash::hash([i as u8, 1])[..32]);
+    }
+    points[1] = ge_scalarmult_base(&[7u8; 32]);
+    points[2] = ge_scalarmult_base(&[9u8; 32]);
+    points[3] = GeP3::from_bytes_vartime(&[
+        0xc7, 0x17, 0x6a, 0x70, 0x3d, 0x4d, 0xd8, 0x4f, 0xba, 0x3c, 0x0b, 0x76, 0x0d, 0x10, 0x67,
+        0x0f, 0x2a, 0x20, 0x53, 0xfa, 0x2c, 0x39, 0xcc, 0xc6, 0x4e, 0xc7, 0xfd, 0x77, 0x92, 0xac,
+        0x03, 0x7a,
+    ])
+    .unwrap(); // point of order 8
+
+    for n in [0, 1, 2, 4, 5, 17, scalars.len()] {
+        let (scalars, points) = (&scalars[..n], &points[..n]);
+        let expected = scalars
+            .iter()
+            .zip(points.iter())
+            .fold(GeP3::zero(), |acc, (s, p)| acc + ge_scalarmult(s, p))
+            .to_bytes();
+        assert_eq!(ge_multiscalar_mul(scalars, points).to_bytes(), expected);
+        assert_eq!(
+            ge_multiscalar_mul_straus_vartime(scalars, points).to_bytes(),
+            expected
+        );
+        assert_eq!(
+            ge_multiscalar_mul_pippenger_vartime(scalars, points).to_bytes(),
+            expected
+        );
+    }
+
+    // Pippenger, through the dispatch, with every window size.
+    let low_order = points[3];
+    let mut scalars = vec![[0u8; 32]; 1000];
+    let mut points = vec![GeP3::zero(); 1000];
+    let step = ge_scalarmult_base(&[5u8; 32]).to_cached();
+    for i in 0..scalars.len() {
+        scalars[i].copy_from_slice(&sha512::Hash::hash((i as u32).to_le_bytes())[..32]);
+        scalars[i][31] &= 0x7f;
+        points[i] = if i == 0 {
+            low_order
+        } else {
+            (points[i - 1] + step).to_p3()
+        };
+    }
+    scalars[1] = [0xff; 32];
+    scalars[1][31] = 0x7f;
+    scalars[2] = [0u8; 32];
+    for n in [PIPPENGER_THRESHOLD + 1, 600, scalars.len()] {
+        let (scalars, points) = (&scalars[..n], &points[..n]);
+        assert_eq!(
+            ge_multiscalar_mul_vartime(scalars, points).to_bytes(),
+            ge_multiscalar_mul_straus_vartime(scalars, points).to_bytes()
+        );
+    }
+
+    for s in [[0u8; 32], [0x7f; 32], [0xff; 32], [0x80; 32]] {
+        for w in 6..=8 {
+            let digits = radix_2w(&s, w);
+            let mut t = [0i64; 43];
+            for (i, d) in digits.iter().enumerate() {
+                assert!(-(1 << (w - 1)) <= *d as i64 && (*d as i64) < (1 << (w - 1)));
+                t[i] = *d as i64;
+            }
+            let mut bytes = [0u8; 33];
+            let mut acc = 0i128;
+            let mut bits = 0;
+            let mut pos = 0;
+            for d in t.iter().take(256 / w + 1) {
+                acc += (*d as i128) << bits;
+                bits += w;
+                while bits >= 8 && pos < bytes.len() {
+                    bytes[pos] = acc as u8;
+                    acc >>= 8;
+                    bits -= 8;
+                    pos += 1;
+                }
+            }
+            while pos < bytes.len() {
+                bytes[pos] = acc as u8;
+                acc >>= 8;
+                pos += 1;
+            }
+            assert_eq!(&bytes[..32], &s[..]);
+            assert_eq!(bytes[32], 0);
+        }
+    }
+}
@@ -0,0 +1,216 @@
+//! Parallel Edwards point arithmetic, using four-way field arithmetic.
+//!
+//! The four coordinates of a point are kept in the lanes of a single `FeX4`,
+//! and the formulas from Hisil, Wong, Carter and Dawson ("Twisted Edwards
+//! Curves Revisited", section 4.2) compute the four multiplications of each
+//! step at once.
+
+use super::field25519::{Fe, FE_D, FE_ONE};
+use super::field25519_simd::{lanes, select, Backend, FeX4, Lanes};
+
+/// `(X : Y : Z : T)`.
+#[derive(Clone, Copy)]
+struct ExtendedPoint<V>(FeX4<V>);
+
+/// `(Y - X, Y + X, 2Z, 2dT)`.
+#[derive(Clone, Copy)]
+struct CachedPoint<V>(FeX4<V>);
+
+impl<V: Lanes> ExtendedPoint<V> {
+    #[inline(always)]
+    unsafe fn identity() -> Self {
+        let zero = Fe::default();
+        ExtendedPoint(FeX4::new(&[zero, FE_ONE, FE_ONE, zero]))
+    }
+
+    #[allow(clippy::wrong_self_convention)]
+    #[inline(always)]
+    unsafe fn to_cached(&self) -> CachedPoint<V> {
+        let p = &self.0;
+        let yyzt = p.permute::<{ lanes(1, 1, 2, 3) }>();
+        let xxzt = p.permute::<{ lanes(0, 0, 2, 3) }>();
+        let t = yyzt
+            .sub(&xxzt)
+            .blend::<{ select(false, true, true, true) }>(&yyzt.add(&xxzt));
+        CachedPoint(t.mul(&FeX4::new(&[FE_ONE, FE_ONE, FE_ONE, FE_D])))
+    }
+
+    /// Returns `P + Q`.
+    #[inline(always)]
+    unsafe fn add(&self, q: &CachedPoint<V>) -> Self {
+        let p = &self.0;
+
+        // (Y1 - X1, Y1 + X1, Z1, T1)
+        let yyzt = p.permute::<{ lanes(1, 1, 2, 3) }>();
+        let xx = p.permute::<{ lanes(0, 0, 2, 3) }>();
+        let s = yyzt
+            .sub(&xx)
+            .blend::<{ select(false, true, false, false) }>(&yyzt.add(&xx))
+            .blend::<{ select(false, false, true, true) }>(&yyzt);
+
+        // (A, B, D, C)
+        let abdc = s.mul(&q.0);
+        Self::finish(&abdc)
+    }
+
+    /// Returns `(X3 : Y3 : Z3 : T3)` = `(EF : GH : FG : EH)`, given
+    /// `(A, B, D, C)` with `E = B - A`, `F = D - C`, `G = D + C` and
+    /// `H = B + A`.
+    #[inline(always)]
+    unsafe fn finish(abdc: &FeX4<V>) -> Self {
+        let badc = abdc.permute::<{ lanes(1, 0, 3, 2) }>();
+        // (E, H, G, F)
+        let t = badc
+            .sub(abdc)
+            .blend::<{ select(false, true, true, false) }>(&abdc.add(&badc));
+        let egfe = t.permute::<{ lanes(0, 2, 3, 0) }>();
+        let fhgh = t.permute::<{ lanes(3, 1, 2, 1) }>();
+        ExtendedPoint(egfe.mul(&fhgh))
+    }
+
+    /// Returns `P - Q`.
+    #[inline(always)]
+    unsafe fn sub(&self, q: &CachedPoint<V>) -> Self {
+        self.add(&q.neg())
+    }
+
+    /// Returns `2P`.
+    #[inline(always)]
+    unsafe fn dbl(&self) -> Self {
+        let p = &self.0;
+        let zero = FeX4::zero();
+
+        // (X, Y, Z, X + Y)
+        let xyzy = p.permute::<{ lanes(0, 1, 2, 1) }>();
+        let xxxx = p.permute::<{ lanes(0, 0, 0, 0) }>();
+        let s = xyzy.blend::<{ select(false, false, false, true) }>(&xyzy.add(&xxxx));
+
+        // (A, B, Z^2, (X + Y)^2)
+        let sq = s.square();
+
+        // (E, H, G, F) = ((X + Y)^2 - (A + B), A + B, B - A, 2Z^2 + A - B)
+        let sabz = sq.permute::<{ lanes(3, 0, 1, 2) }>();
+        let zbzz = sq
+            .permute::<{ lanes(2, 1, 2, 2) }>()
+            .blend::<{ select(true, false, true, false) }>(&zero);
+        let m0 = sabz.add(&zbzz);
+        let a = sq
+            .permute::<{ lanes(0, 0, 0, 0) }>()
+            .blend::<{ select(true, true, true, false) }>(&zero);
+        let m = m0.add(&a);
+        let n = sq
+            .permute::<{ lanes(0, 0, 0, 1) }>()
+            .blend::<{ select(true, false, false, false) }>(&m0.permute::<{ lanes(1, 1, 1, 1) }>())
+            .blend::<{ select(false, true, false, false) }>(&zero);
+        let t = m.sub(&n);
+
+        let egfe = t.permute::<{ lanes(0, 2, 3, 0) }>();
+        let fhgh = t.permute::<{ lanes(3, 1, 2, 1) }>();
+        ExtendedPoint(egfe.mul(&fhgh))
+    }
+}
+
+impl<V: Lanes> CachedPoint<V> {
+    /// Returns `-Q` = `(Y + X, Y - X, 2Z, -2dT)`.
+    #[inline(always)]
+    unsafe fn neg(&self) -> Self {
+        let q = self.0.permute::<{ lanes(1, 0, 2, 3) }>();
+        CachedPoint(q.blend::<{ select(false, false, false, true) }>(&q.neg()))
+    }
+}
+
+/// Computes `a*A + b*B`, given signed sliding window representations of `a`
+/// and `b`, the extended coordinates of `A`, and the cached coordinates of
+/// the odd multiples of `B`. Returns `(X : Y : Z : T)`.
+#[inline(always)]
+unsafe fn double_scalarmult_vartime_generic<V: Lanes>(
+    aslide: &[i8; 256],
+    a: &[Fe; 4],
+    bslide: &[i8; 256],
+    bi: &[[Fe; 4]; 8],
+) -> [Fe; 4] {
+    let a = ExtendedPoint::<V>(FeX4::new(a));
+    let mut ai = [a.to_cached(); 8];
+    let a2 = a.dbl();
+    for i in 1..8 {
+        ai[i] = a2.add(&ai[i - 1]).to_cached();
+    }
+    let mut bi_ = [CachedPoint::<V>(FeX4::zero()); 8];
+    for (b, bi) in bi_.iter_mut().zip(bi.iter()) {
+        *b = CachedPoint(FeX4::new(bi));
+    }
+
+    let mut r = ExtendedPoint::identity();
+    let top = match (0..256).rev().find(|&i| aslide[i] != 0 || bslide[i] != 0) {
+        None => return r.0.split(),
+        Some(top) => top,
+    };
+    for i in (0..=top).rev() {
+        r = r.dbl();
+        if aslide[i] > 0 {
+            r = r.add(&ai[(aslide[i] / 2) as usize]);
+        } else if aslide[i] < 0 {
+            r = r.sub(&ai[(-aslide[i] / 2) as usize]);
+        }
+        if bslide[i] > 0 {
+            r = r.add(&bi_[(bslide[i] / 2) as usize]);
+        } else if bslide[i] < 0 {
+            r = r.sub(&bi_[(-bslide[i] / 2) as usize]);
+        }
+    }
+    r.0.split()
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn double_scalarmult_vartime_avx2(
+    aslide: &[i8; 256],
+    a: &[Fe; 4],
+    bslide: &[i8; 256],
+    bi: &[[Fe; 4]; 8],
+) -> [Fe; 4] {
+    double_scalarmult_vartime_generic::<core::arch::x86_64::__m256i>(aslide, a, bslide, bi)
+}
+
+#[cfg(target_arch = "aarch64")]
+#[target_feature(enable = "neon")]
+unsafe fn double_scalarmult_vartime_neon(
+    aslide: &[i8; 256],
+    a: &[Fe; 4],
+    bslide: &[i8; 256],
+    bi: &[[Fe; 4]; 8],
+) -> [Fe; 4] {
+    double_scalarmult_vartime_generic::<super::field25519_simd::U64x4>(aslide, a, bslide, bi)
+}
+
+/// Computes `a*A + b*B` using the best available backend, or returns `None`
+/// if the CPU doesn't support any.
+pub(crate) fn double_scalarmult_vartime(
+    aslide: &[i8; 256],
+    a: &[Fe; 4],
+    bslide: &[i8; 256],
+    bi: &[[Fe; 4]; 8],
+) -> Option<[Fe; 4]> {
+    match Backend::detect()? {
+        #[cfg(target_arch = "x86_64")]
+        Backend::Avx2 => Some(unsafe { double_scalarmult_vartime_avx2(aslide, a, bslide, bi) }),
+        #[cfg(target_arch = "aarch64")]
+        Backend::Neon => Some(unsafe { double_scalarmult_vartime_neon(aslide, a, bslide, bi) }),
+        #[cfg(target_arch = "wasm32")]
+        Backend::Simd128 => Some(unsafe {
+            double_scalarmult_vartime_generic::<super::field25519_simd::U64x4>(
+                aslide, a, bslide, bi,
+            )
+        }),
+    }
+}
+
+#[cfg(test)]
+pub(crate) fn double_scalarmult_vartime_portable(
+    aslide: &[i8; 256],
+    a: &[Fe; 4],
+    bslide: &[i8; 256],
+    bi: &[[Fe; 4]; 8],
+) -> [Fe; 4] {
+    unsafe { double_scalarmult_vartime_generic::<[u64; 4]>(aslide, a, bslide, bi) }
+}
@@ -1,6 +1,7 @@
 use core::fmt::{self, Display};
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[non_exhaustive]
 pub enum Error {
     /// The signature doesn't verify.
     SignatureMismatch,
@@ -22,6 +23,18 @@ pub enum Error {
     ParseError,
     /// Non-canonical encoding
     NonCanonical,
+    /// The output buffer is too small
+    BufferTooSmall,
+    /// Unsupported or out-of-range parameters
+    InvalidParameters,
+    /// The checksum of a file doesn't match
+    ChecksumMismatch,
+    /// The ciphertext couldn't be authenticated
+    DecryptionFailed,
+    /// The authentication tag doesn't match
+    TagMismatch,
+    /// Memory couldn't be allocated or locked
+    MemoryLockFailed,
 }
 
 #[cfg(feature = "std")]
@@ -40,6 +53,12 @@ impl Display for Error {
             Error::InvalidNoise => write!(f, "Invalid noise length"),
             Error::ParseError => write!(f, "Parse error"),
             Error::NonCanonical => write!(f, "Non-canonical encoding"),
+            Error::BufferTooSmall => write!(f, "Output buffer is too small"),
+            Error::InvalidParameters => write!(f, "Invalid parameters"),
+            Error::ChecksumMismatch => write!(f, "Checksum mismatch"),
+            Error::DecryptionFailed => write!(f, "Decryption failed"),
+            Error::TagMismatch => write!(f, "Authentication tag doesn't match"),
+            Error::MemoryLockFailed => write!(f, "Memory couldn't be locked"),
         }
     }
 }
@@ -0,0 +1,923 @@
+//! Four-way parallel field arithmetic, using AVX2, NEON or WebAssembly SIMD.
+//!
+//! `FeX4` holds four field elements as ten limbs, alternately 26 and 25 bits
+//! wide. Vector `i` holds limb `i` of the four elements, one per 64-bit lane,
+//! so that lane-wise operations compute the same operation on four field
+//! elements at once, and lane shuffles mix them, as required by the parallel
+//! Edwards formulas and by the Montgomery ladder.
+//!
+//! Backends are selected at runtime when the `std` feature is enabled, and at
+//! compile time (using the enabled target features) otherwise. WebAssembly
+//! doesn't support runtime detection, so this module is only compiled when
+//! the `simd128` target feature is enabled.
+
+#![allow(clippy::many_single_char_names)]
+
+use super::field25519::Fe;
+
+/// Backends, in order of preference.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub(crate) enum Backend {
+    /// 4 lanes in a 256-bit register.
+    #[cfg(target_arch = "x86_64")]
+    Avx2,
+    /// 4 lanes in a pair of 128-bit registers.
+    #[cfg(target_arch = "aarch64")]
+    Neon,
+    /// 4 lanes in a pair of 128-bit values.
+    #[cfg(target_arch = "wasm32")]
+    Simd128,
+}
+
+impl Backend {
+    /// Returns the best backend supported by the current CPU.
+    pub(crate) fn detect() -> Option<Backend> {
+        #[cfg(all(target_arch = "x86_64", feature = "std"))]
+        {
+            if std::is_x86_feature_detected!("avx2") {
+                return Some(Backend::Avx2);
+            }
+        }
+        #[cfg(all(target_arch = "x86_64", not(feature = "std")))]
+        {
+            if cfg!(target_feature = "avx2") {
+                return Some(Backend::Avx2);
+            }
+        }
+        #[cfg(all(target_arch = "aarch64", feature = "std"))]
+        {
+            if std::arch::is_aarch64_feature_detected!("neon") {
+                return Some(Backend::Neon);
+            }
+        }
+        #[cfg(all(target_arch = "aarch64", not(feature = "std")))]
+        {
+            if cfg!(target_feature = "neon") {
+                return Some(Backend::Neon);
+            }
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            if cfg!(target_feature = "simd128") {
+                return Some(Backend::Simd128);
+            }
+        }
+        None
+    }
+}
+
+/// Operations on a vector of four 64-bit lanes.
+pub(crate) trait Lanes: Copy {
+    unsafe fn splat(x: u64) -> Self;
+    unsafe fn load(x: &[u64; 4]) -> Self;
+    unsafe fn store(self, out: &mut [u64; 4]);
+    unsafe fn add(self, y: Self) -> Self;
+    unsafe fn sub(self, y: Self) -> Self;
+    unsafe fn and(self, y: Self) -> Self;
+    #[cfg(any(test, feature = "x25519"))]
+    unsafe fn xor(self, y: Self) -> Self;
+    /// Multiplies the low 32 bits of every lane, into a 64-bit product.
+    unsafe fn mul32(self, y: Self) -> Self;
+    unsafe fn shr<const S: i32>(self) -> Self;
+    unsafe fn shl<const S: i32>(self) -> Self;
+    /// Permutes the lanes. `P` is encoded as for `_mm256_permute4x64_epi64`:
+    /// lane `i` of the result is lane `(P >> 2i) & 3` of `self`.
+    unsafe fn permute<const P: i32>(self) -> Self;
+    /// Replaces the 32-bit halves of `self` selected by the bits of `M` with
+    /// the ones of `y`, as `_mm256_blend_epi32` does.
+    unsafe fn blend<const M: i32>(self, y: Self) -> Self;
+}
+
+/// Builds a lane permutation for `Lanes::permute()`.
+pub(crate) const fn lanes(l0: i32, l1: i32, l2: i32, l3: i32) -> i32 {
+    l0 | (l1 << 2) | (l2 << 4) | (l3 << 6)
+}
+
+/// Builds a lane selection mask for `Lanes::blend()`.
+pub(crate) const fn select(l0: bool, l1: bool, l2: bool, l3: bool) -> i32 {
+    (l0 as i32 * 0b11) | (l1 as i32 * 0b1100) | (l2 as i32 * 0b11_0000) | (l3 as i32 * 0b1100_0000)
+}
+
+const MASK26: u64 = (1 << 26) - 1;
+const MASK25: u64 = (1 << 25) - 1;
+
+/// `16 * p`, in the limb representation.
+const P16: [u64; 10] = [
+    (MASK26 - 18) << 4,
+    MASK25 << 4,
+    MASK26 << 4,
+    MASK25 << 4,
+    MASK26 << 4,
+    MASK25 << 4,
+    MASK26 << 4,
+    MASK25 << 4,
+    MASK26 << 4,
+    MASK25 << 4,
+];
+
+/// Four field elements.
+///
+/// Every operation but `add()` returns reduced limbs, of at most 26 bits
+/// (even limbs) and 25 bits (odd limbs, plus a small carry in limb 1).
+/// `add()` doesn't carry, so the sum of two reduced elements has limbs of at
+/// most 27 and 26 bits. `mul()` and `square()` accept such sums, but not
+/// more: their largest multiplier inputs are the `mul19(f*_2)` / `*_38` terms
+/// of `square()`, `38 * f7` and `38 * f9`, which stay below 2^32 only as long
+/// as odd limbs are at most 26 bits. Products of 64-bit lanes, and their sums
+/// (below 2^62), are only carried back to 26 and 25 bits by `reduce()`.
+#[derive(Clone, Copy)]
+pub(crate) struct FeX4<V>([V; 10]);
+
+impl<V: Lanes> FeX4<V> {
+    #[inline(always)]
+    pub(crate) unsafe fn new(fe: &[Fe; 4]) -> Self {
+        let limbs = [
+            unpack(&fe[0].to_bytes()),
+            unpack(&fe[1].to_bytes()),
+            unpack(&fe[2].to_bytes()),
+            unpack(&fe[3].to_bytes()),
+        ];
+        let mut v = [V::splat(0); 10];
+        for (i, v) in v.iter_mut().enumerate() {
+            *v = V::load(&[limbs[0][i], limbs[1][i], limbs[2][i], limbs[3][i]]);
+        }
+        FeX4(v)
+    }
+
+    #[cfg(any(test, not(feature = "disable-signatures")))]
+    #[inline(always)]
+    pub(crate) unsafe fn zero() -> Self {
+        FeX4([V::splat(0); 10])
+    }
+
+    /// Returns the four field elements.
+    #[inline(always)]
+    pub(crate) unsafe fn split(&self) -> [Fe; 4] {
+        let mut limbs = [[0u64; 4]; 10];
+        for (v, limbs) in self.0.iter().zip(limbs.iter_mut()) {
+            v.store(limbs);
+        }
+        let mut fe = [Fe::default(); 4];
+        for (j, fe) in fe.iter_mut().enumerate() {
+            let mut l = [0u64; 5];
+            for (i, l) in l.iter_mut().enumerate() {
+                *l = limbs[2 * i][j] + (limbs[2 * i + 1][j] << 26);
+            }
+            *fe = Fe::from_limbs51(l);
+        }
+        fe
+    }
+
+    #[inline(always)]
+    pub(crate) unsafe fn add(&self, other: &Self) -> Self {
+        let mut h = self.0;
+        for (h, g) in h.iter_mut().zip(other.0.iter()) {
+            *h = h.add(*g);
+        }
+        FeX4(h)
+    }
+
+    #[inline(always)]
+    pub(crate) unsafe fn sub(&self, other: &Self) -> Self {
+        let mut h = self.0;
+        for ((h, g), p) in h.iter_mut().zip(other.0.iter()).zip(P16.iter()) {
+            *h = h.add(V::splat(*p)).sub(*g);
+        }
+        Self::reduce(h)
+    }
+
+    #[cfg(any(test, not(feature = "disable-signatures")))]
+    #[inline(always)]
+    pub(crate) unsafe fn neg(&self) -> Self {
+        Self::zero().sub(self)
+    }
+
+    #[cfg_attr(target_arch = "wasm32", inline(never))]
+    #[cfg_attr(not(target_arch = "wasm32"), inline(always))]
+    pub(crate) unsafe fn mul(&self, other: &Self) -> Self {
+        let [f0, f1, f2, f3, f4, f5, f6, f7, f8, f9] = self.0;
+        let [g0, g1, g2, g3, g4, g5, g6, g7, g8, g9] = other.0;
+        let f1_2 = f1.add(f1);
+        let f3_2 = f3.add(f3);
+        let f5_2 = f5.add(f5);
+        let f7_2 = f7.add(f7);
+        let f9_2 = f9.add(f9);
+        let g1_19 = mul19(g1);
+        let g2_19 = mul19(g2);
+        let g3_19 = mul19(g3);
+        let g4_19 = mul19(g4);
+        let g5_19 = mul19(g5);
+        let g6_19 = mul19(g6);
+        let g7_19 = mul19(g7);
+        let g8_19 = mul19(g8);
+        let g9_19 = mul19(g9);
+        let h0 = f0
+            .mul32(g0)
+            .add(f1_2.mul32(g9_19))
+            .add(f2.mul32(g8_19).add(f3_2.mul32(g7_19)))
+            .add(
+                f4.mul32(g6_19)
+                    .add(f5_2.mul32(g5_19))
+                    .add(f6.mul32(g4_19).add(f7_2.mul32(g3_19))),
+            )
+            .add(f8.mul32(g2_19).add(f9_2.mul32(g1_19)));
+        let h1 = f0
+            .mul32(g1)
+            .add(f1.mul32(g0))
+            .add(f2.mul32(g9_19).add(f3.mul32(g8_19)))
+            .add(
+                f4.mul32(g7_19)
+                    .add(f5.mul32(g6_19))
+                    .add(f6.mul32(g5_19).add(f7.mul32(g4_19))),
+            )
+            .add(f8.mul32(g3_19).add(f9.mul32(g2_19)));
+        let h2 = f0
+            .mul32(g2)
+            .add(f1_2.mul32(g1))
+            .add(f2.mul32(g0).add(f3_2.mul32(g9_19)))
+            .add(
+                f4.mul32(g8_19)
+                    .add(f5_2.mul32(g7_19))
+                    .add(f6.mul32(g6_19).add(f7_2.mul32(g5_19))),
+            )
+            .add(f8.mul32(g4_19).add(f9_2.mul32(g3_19)));
+        let h3 = f0
+            .mul32(g3)
+            .add(f1.mul32(g2))
+            .add(f2.mul32(g1).add(f3.mul32(g0)))
+            .add(
+                f4.mul32(g9_19)
+                    .add(f5.mul32(g8_19))
+                    .add(f6.mul32(g7_19).add(f7.mul32(g6_19))),
+            )
+            .add(f8.mul32(g5_19).add(f9.mul32(g4_19)));
+        let h4 = f0
+            .mul32(g4)
+            .add(f1_2.mul32(g3))
+            .add(f2.mul32(g2).add(f3_2.mul32(g1)))
+            .add(
+                f4.mul32(g0)
+                    .add(f5_2.mul32(g9_19))
+                    .add(f6.mul32(g8_19).add(f7_2.mul32(g7_19))),
+            )
+            .add(f8.mul32(g6_19).add(f9_2.mul32(g5_19)));
+        let h5 = f0
+            .mul32(g5)
+            .add(f1.mul32(g4))
+            .add(f2.mul32(g3).add(f3.mul32(g2)))
+            .add(
+                f4.mul32(g1)
+                    .add(f5.mul32(g0))
+                    .add(f6.mul32(g9_19).add(f7.mul32(g8_19))),
+            )
+            .add(f8.mul32(g7_19).add(f9.mul32(g6_19)));
+        let h6 = f0
+            .mul32(g6)
+            .add(f1_2.mul32(g5))
+            .add(f2.mul32(g4).add(f3_2.mul32(g3)))
+            .add(
+                f4.mul32(g2)
+                    .add(f5_2.mul32(g1))
+                    .add(f6.mul32(g0).add(f7_2.mul32(g9_19))),
+            )
+            .add(f8.mul32(g8_19).add(f9_2.mul32(g7_19)));
+        let h7 = f0
+            .mul32(g7)
+            .add(f1.mul32(g6))
+            .add(f2.mul32(g5).add(f3.mul32(g4)))
+            .add(
+                f4.mul32(g3)
+                    .add(f5.mul32(g2))
+                    .add(f6.mul32(g1).add(f7.mul32(g0))),
+            )
+            .add(f8.mul32(g9_19).add(f9.mul32(g8_19)));
+        let h8 = f0
+            .mul32(g8)
+            .add(f1_2.mul32(g7))
+            .add(f2.mul32(g6).add(f3_2.mul32(g5)))
+            .add(
+                f4.mul32(g4)
+                    .add(f5_2.mul32(g3))
+                    .add(f6.mul32(g2).add(f7_2.mul32(g1))),
+            )
+            .add(f8.mul32(g0).add(f9_2.mul32(g9_19)));
+        let h9 = f0
+            .mul32(g9)
+            .add(f1.mul32(g8))
+            .add(f2.mul32(g7).add(f3.mul32(g6)))
+            .add(
+                f4.mul32(g5)
+                    .add(f5.mul32(g4))
+                    .add(f6.mul32(g3).add(f7.mul32(g2))),
+            )
+            .add(f8.mul32(g1).add(f9.mul32(g0)));
+        Self::reduce([h0, h1, h2, h3, h4, h5, h6, h7, h8, h9])
+    }
+
+    #[cfg(any(test, not(feature = "disable-signatures")))]
+    #[cfg_attr(target_arch = "wasm32", inline(never))]
+    #[cfg_attr(not(target_arch = "wasm32"), inline(always))]
+    pub(crate) unsafe fn square(&self) -> Self {
+        let [f0, f1, f2, f3, f4, f5, f6, f7, f8, f9] = self.0;
+        let f0_2 = f0.add(f0);
+        let f1_2 = f1.add(f1);
+        let f2_2 = f2.add(f2);
+        let f3_2 = f3.add(f3);
+        let f4_2 = f4.add(f4);
+        let f5_2 = f5.add(f5);
+        let f6_2 = f6.add(f6);
+        let f7_2 = f7.add(f7);
+        let f8_2 = f8.add(f8);
+        let f9_2 = f9.add(f9);
+        let f5_19 = mul19(f5);
+        let f6_19 = mul19(f6);
+        let f7_19 = mul19(f7);
+        let f8_19 = mul19(f8);
+        let f9_19 = mul19(f9);
+        let f7_38 = mul19(f7_2);
+        let f9_38 = mul19(f9_2);
+        let h0 = f0
+            .mul32(f0)
+            .add(f1_2.mul32(f9_38))
+            .add(f2_2.mul32(f8_19).add(f3_2.mul32(f7_38)))
+            .add(f4_2.mul32(f6_19).add(f5_2.mul32(f5_19)));
+        let h1 = f0_2
+            .mul32(f1)
+            .add(f2_2.mul32(f9_19))
+            .add(f3_2.mul32(f8_19).add(f4_2.mul32(f7_19)))
+            .add(f5_2.mul32(f6_19));
+        let h2 = f0_2
+            .mul32(f2)
+            .add(f1_2.mul32(f1))
+            .add(f3_2.mul32(f9_38).add(f4_2.mul32(f8_19)))
+            .add(f5_2.mul32(f7_38).add(f6.mul32(f6_19)));
+        let h3 = f0_2
+            .mul32(f3)
+            .add(f1_2.mul32(f2))
+            .add(f4_2.mul32(f9_19).add(f5_2.mul32(f8_19)))
+            .add(f6_2.mul32(f7_19));
+        let h4 = f0_2
+            .mul32(f4)
+            .add(f1_2.mul32(f3_2))
+            .add(f2.mul32(f2).add(f5_2.mul32(f9_38)))
+            .add(f6_2.mul32(f8_19).add(f7_2.mul32(f7_19)));
+        let h5 = f0_2
+            .mul32(f5)
+            .add(f1_2.mul32(f4))
+            .add(f2_2.mul32(f3).add(f6_2.mul32(f9_19)))
+            .add(f7_2.mul32(f8_19));
+        let h6 = f0_2
+            .mul32(f6)
+            .add(f1_2.mul32(f5_2))
+            .add(f2_2.mul32(f4).add(f3_2.mul32(f3)))
+            .add(f7_2.mul32(f9_38).add(f8.mul32(f8_19)));
+        let h7 = f0_2
+            .mul32(f7)
+            .add(f1_2.mul32(f6))
+            .add(f2_2.mul32(f5).add(f3_2.mul32(f4)))
+            .add(f8_2.mul32(f9_19));
+        let h8 = f0_2
+            .mul32(f8)
+            .add(f1_2.mul32(f7_2))
+            .add(f2_2.mul32(f6).add(f3_2.mul32(f5_2)))
+            .add(f4.mul32(f4).add(f9_2.mul32(f9_19)));
+        let h9 = f0_2
+            .mul32(f9)
+            .add(f1_2.mul32(f8))
+            .add(f2_2.mul32(f7).add(f3_2.mul32(f6)))
+            .add(f4_2.mul32(f5));
+        Self::reduce([h0, h1, h2, h3, h4, h5, h6, h7, h8, h9])
+    }
+
+    /// Multiplies all the lanes by a small constant.
+    #[cfg(feature = "x25519")]
+    #[inline(always)]
+    pub(crate) unsafe fn mul_small(&self, n: u32) -> Self {
+        let mut h = self.0;
+        for h in h.iter_mut() {
+            *h = h.mul32(V::splat(n as u64));
+        }
+        Self::reduce(h)
+    }
+
+    #[inline(always)]
+    pub(crate) unsafe fn permute<const P: i32>(&self) -> Self {
+        let mut h = self.0;
+        for h in h.iter_mut() {
+            *h = h.permute::<P>();
+        }
+        FeX4(h)
+    }
+
+    #[inline(always)]
+    pub(crate) unsafe fn blend<const M: i32>(&self, other: &Self) -> Self {
+        let mut h = self.0;
+        for (h, g) in h.iter_mut().zip(other.0.iter()) {
+            *h = h.blend::<M>(*g);
+        }
+        FeX4(h)
+    }
+
+    /// Swaps lanes 0 and 1 with lanes 2 and 3 if `swap` is `1`, in constant
+    /// time.
+    #[cfg(any(test, feature = "x25519"))]
+    #[inline(always)]
+    pub(crate) unsafe fn cswap_halves(&self, swap: u8) -> Self {
+        let mask = V::splat(0u64.wrapping_sub(swap as u64));
+        let mut h = self.0;
+        for h in h.iter_mut() {
+            let x = h.xor(h.permute::<{ lanes(2, 3, 0, 1) }>()).and(mask);
+            *h = h.xor(x);
+        }
+        FeX4(h)
+    }
+
+    #[inline(always)]
+    unsafe fn reduce(h: [V; 10]) -> Self {
+        let [mut h0, mut h1, mut h2, mut h3, mut h4, mut h5, mut h6, mut h7, mut h8, mut h9] = h;
+        let (mask26, mask25) = (V::splat(MASK26), V::splat(MASK25));
+        let mut c;
+
+        c = h0.shr::<26>();
+        h1 = h1.add(c);
+        h0 = h0.and(mask26);
+        c = h4.shr::<26>();
+        h5 = h5.add(c);
+        h4 = h4.and(mask26);
+
+        c = h1.shr::<25>();
+        h2 = h2.add(c);
+        h1 = h1.and(mask25);
+        c = h5.shr::<25>();
+        h6 = h6.add(c);
+        h5 = h5.and(mask25);
+
+        c = h2.shr::<26>();
+        h3 = h3.add(c);
+        h2 = h2.and(mask26);
+        c = h6.shr::<26>();
+        h7 = h7.add(c);
+        h6 = h6.and(mask26);
+
+        c = h3.shr::<25>();
+        h4 = h4.add(c);
+        h3 = h3.and(mask25);
+        c = h7.shr::<25>();
+        h8 = h8.add(c);
+        h7 = h7.and(mask25);
+
+        c = h4.shr::<26>();
+        h5 = h5.add(c);
+        h4 = h4.and(mask26);
+        c = h8.shr::<26>();
+        h9 = h9.add(c);
+        h8 = h8.and(mask26);
+
+        c = h9.shr::<25>();
+        h0 = h0.add(mul19(c));
+        h9 = h9.and(mask25);
+
+        c = h0.shr::<26>();
+        h1 = h1.add(c);
+        h0 = h0.and(mask26);
+
+        FeX4([h0, h1, h2, h3, h4, h5, h6, h7, h8, h9])
+    }
+}
+
+/// Returns `19 * x`, without using multiplications.
+#[inline(always)]
+unsafe fn mul19<V: Lanes>(x: V) -> V {
+    x.add(x.shl::<1>()).add(x.shl::<4>())
+}
+
+/// Splits a 255-bit little-endian value into limbs.
+#[inline(always)]
+fn unpack(s: &[u8; 32]) -> [u64; 10] {
+    let mut w = [0u64; 4];
+    for (w, s) in w.iter_mut().zip(s.chunks_exact(8)) {
+        let mut t = [0u8; 8];
+        t.copy_from_slice(s);
+        *w = u64::from_le_bytes(t);
+    }
+    let mut limbs = [0u64; 10];
+    let mut offset = 0;
+    for (i, limb) in limbs.iter_mut().enumerate() {
+        let (q, r) = (offset / 64, offset % 64);
+        let mut x = w[q] >> r;
+        if r > 38 && q < 3 {
+            x |= w[q + 1] << (64 - r);
+        }
+        let bits = 26 - (i & 1);
+        *limb = x & ((1 << bits) - 1);
+        offset += bits;
+    }
+    limbs
+}
+
+#[cfg(target_arch = "x86_64")]
+mod x86_64 {
+    use core::arch::x86_64::*;
+
+    use super::Lanes;
+
+    impl Lanes for __m256i {
+        #[inline(always)]
+        unsafe fn splat(x: u64) -> Self {
+            _mm256_set1_epi64x(x as i64)
+        }
+
+        #[inline(always)]
+        unsafe fn load(x: &[u64; 4]) -> Self {
+            _mm256_loadu_si256(x.as_ptr() as *const __m256i)
+        }
+
+        #[inline(always)]
+        unsafe fn store(self, out: &mut [u64; 4]) {
+            _mm256_storeu_si256(out.as_mut_ptr() as *mut __m256i, self)
+        }
+
+        #[inline(always)]
+        unsafe fn add(self, y: Self) -> Self {
+            _mm256_add_epi64(self, y)
+        }
+
+        #[inline(always)]
+        unsafe fn sub(self, y: Self) -> Self {
+            _mm256_sub_epi64(self, y)
+        }
+
+        #[inline(always)]
+        unsafe fn and(self, y: Self) -> Self {
+            _mm256_and_si256(self, y)
+        }
+
+        #[cfg(any(test, feature = "x25519"))]
+        #[inline(always)]
+        unsafe fn xor(self, y: Self) -> Self {
+            _mm256_xor_si256(self, y)
+        }
+
+        #[inline(always)]
+        unsafe fn mul32(self, y: Self) -> Self {
+            _mm256_mul_epu32(self, y)
+        }
+
+        #[inline(always)]
+        unsafe fn shr<const S: i32>(self) -> Self {
+            _mm256_srli_epi64::<S>(self)
+        }
+
+        #[inline(always)]
+        unsafe fn shl<const S: i32>(self) -> Self {
+            _mm256_slli_epi64::<S>(self)
+        }
+
+        #[inline(always)]
+        unsafe fn permute<const P: i32>(self) -> Self {
+            _mm256_permute4x64_epi64::<P>(self)
+        }
+
+        #[inline(always)]
+        unsafe fn blend<const M: i32>(self, y: Self) -> Self {
+            _mm256_blend_epi32::<M>(self, y)
+        }
+    }
+}
+
+#[cfg(target_arch = "aarch64")]
+mod aarch64 {
+    use core::arch::aarch64::*;
+
+    use super::Lanes;
+
+    /// Lanes 0 and 1, and lanes 2 and 3.
+    #[derive(Clone, Copy)]
+    pub(crate) struct U64x4(uint64x2_t, uint64x2_t);
+
+    impl U64x4 {
+        #[inline(always)]
+        unsafe fn lane(self, i: i32) -> uint64x2_t {
+            match i & 3 {
+                0 => vdupq_laneq_u64::<0>(self.0),
+                1 => vdupq_laneq_u64::<1>(self.0),
+                2 => vdupq_laneq_u64::<0>(self.1),
+                _ => vdupq_laneq_u64::<1>(self.1),
+            }
+        }
+    }
+
+    impl Lanes for U64x4 {
+        #[inline(always)]
+        unsafe fn splat(x: u64) -> Self {
+            U64x4(vdupq_n_u64(x), vdupq_n_u64(x))
+        }
+
+        #[inline(always)]
+        unsafe fn load(x: &[u64; 4]) -> Self {
+            U64x4(vld1q_u64(x.as_ptr()), vld1q_u64(x[2..].as_ptr()))
+        }
+
+        #[inline(always)]
+        unsafe fn store(self, out: &mut [u64; 4]) {
+            vst1q_u64(out.as_mut_ptr(), self.0);
+            vst1q_u64(out[2..].as_mut_ptr(), self.1);
+        }
+
+        #[inline(always)]
+        unsafe fn add(self, y: Self) -> Self {
+            U64x4(vaddq_u64(self.0, y.0), vaddq_u64(self.1, y.1))
+        }
+
+        #[inline(always)]
+        unsafe fn sub(self, y: Self) -> Self {
+            U64x4(vsubq_u64(self.0, y.0), vsubq_u64(self.1, y.1))
+        }
+
+        #[inline(always)]
+        unsafe fn and(self, y: Self) -> Self {
+            U64x4(vandq_u64(self.0, y.0), vandq_u64(self.1, y.1))
+        }
+
+        #[cfg(any(test, feature = "x25519"))]
+        #[inline(always)]
+        unsafe fn xor(self, y: Self) -> Self {
+            U64x4(veorq_u64(self.0, y.0), veorq_u64(self.1, y.1))
+        }
+
+        #[inline(always)]
+        unsafe fn mul32(self, y: Self) -> Self {
+            U64x4(
+                vmull_u32(vmovn_u64(self.0), vmovn_u64(y.0)),
+                vmull_u32(vmovn_u64(self.1), vmovn_u64(y.1)),
+            )
+        }
+
+        #[inline(always)]
+        unsafe fn shr<const S: i32>(self) -> Self {
+            U64x4(vshrq_n_u64::<S>(self.0), vshrq_n_u64::<S>(self.1))
+        }
+
+        #[inline(always)]
+        unsafe fn shl<const S: i32>(self) -> Self {
+            U64x4(vshlq_n_u64::<S>(self.0), vshlq_n_u64::<S>(self.1))
+        }
+
+        #[inline(always)]
+        unsafe fn permute<const P: i32>(self) -> Self {
+            let (l0, l1) = (self.lane(P), self.lane(P >> 2));
+            let (l2, l3) = (self.lane(P >> 4), self.lane(P >> 6));
+            U64x4(vzip1q_u64(l0, l1), vzip1q_u64(l2, l3))
+        }
+
+        #[inline(always)]
+        unsafe fn blend<const M: i32>(self, y: Self) -> Self {
+            let m = |bits: i32| if M & bits != 0 { u64::MAX } else { 0 };
+            let m0 = vld1q_u64([m(0b11), m(0b1100)].as_ptr());
+            let m1 = vld1q_u64([m(0b11_0000), m(0b1100_0000)].as_ptr());
+            U64x4(vbslq_u64(m0, y.0, self.0), vbslq_u64(m1, y.1, self.1))
+        }
+    }
+}
+
+#[cfg(target_arch = "aarch64")]
+pub(crate) use aarch64::U64x4;
+
+#[cfg(target_arch = "wasm32")]
+mod wasm32 {
+    use core::arch::wasm32::*;
+
+    use super::Lanes;
+
+    /// Lanes 0 and 1, and lanes 2 and 3.
+    #[derive(Clone, Copy)]
+    pub(crate) struct U64x4(v128, v128);
+
+    /// Returns lanes `l & 3` and `(l >> 2) & 3` of `x`.
+    #[inline(always)]
+    fn pick(x: U64x4, l: i32) -> v128 {
+        macro_rules! pick {
+            ($($i:literal, $j:literal);*) => {
+                match l & 15 {
+                    $(n if n == $i | ($j << 2) => i64x2_shuffle::<$i, $j>(x.0, x.1),)*
+                    _ => unreachable!(),
+                }
+            };
+        }
+        pick!(0, 0; 0, 1; 0, 2; 0, 3; 1, 0; 1, 1; 1, 2; 1, 3;
+              2, 0; 2, 1; 2, 2; 2, 3; 3, 0; 3, 1; 3, 2; 3, 3)
+    }
+
+    impl Lanes for U64x4 {
+        #[inline(always)]
+        unsafe fn splat(x: u64) -> Self {
+            U64x4(u64x2_splat(x), u64x2_splat(x))
+        }
+
+        #[inline(always)]
+        unsafe fn load(x: &[u64; 4]) -> Self {
+            U64x4(u64x2(x[0], x[1]), u64x2(x[2], x[3]))
+        }
+
+        #[inline(always)]
+        unsafe fn store(self, out: &mut [u64; 4]) {
+            *out = [
+                u64x2_extract_lane::<0>(self.0),
+                u64x2_extract_lane::<1>(self.0),
+                u64x2_extract_lane::<0>(self.1),
+                u64x2_extract_lane::<1>(self.1),
+            ];
+        }
+
+        #[inline(always)]
+        unsafe fn add(self, y: Self) -> Self {
+            U64x4(i64x2_add(self.0, y.0), i64x2_add(self.1, y.1))
+        }
+
+        #[inline(always)]
+        unsafe fn sub(self, y: Self) -> Self {
+            U64x4(i64x2_sub(self.0, y.0), i64x2_sub(self.1, y.1))
+        }
+
+        #[inline(always)]
+        unsafe fn and(self, y: Self) -> Self {
+            U64x4(v128_and(self.0, y.0), v128_and(self.1, y.1))
+        }
+
+        #[cfg(any(test, feature = "x25519"))]
+        #[inline(always)]
+        unsafe fn xor(self, y: Self) -> Self {
+            U64x4(v128_xor(self.0, y.0), v128_xor(self.1, y.1))
+        }
+
+        #[inline(always)]
+        unsafe fn mul32(self, y: Self) -> Self {
+            let low = |x: v128| i32x4_shuffle::<0, 2, 0, 2>(x, x);
+            U64x4(
+                u64x2_extmul_low_u32x4(low(self.0), low(y.0)),
+                u64x2_extmul_low_u32x4(low(self.1), low(y.1)),
+            )
+        }
+
+        #[inline(always)]
+        unsafe fn shr<const S: i32>(self) -> Self {
+            U64x4(u64x2_shr(self.0, S as u32), u64x2_shr(self.1, S as u32))
+        }
+
+        #[inline(always)]
+        unsafe fn shl<const S: i32>(self) -> Self {
+            U64x4(i64x2_shl(self.0, S as u32), i64x2_shl(self.1, S as u32))
+        }
+
+        #[inline(always)]
+        unsafe fn permute<const P: i32>(self) -> Self {
+            U64x4(pick(self, P), pick(self, P >> 4))
+        }
+
+        #[inline(always)]
+        unsafe fn blend<const M: i32>(self, y: Self) -> Self {
+            let m = |bits: i32| if M & bits != 0 { u64::MAX } else { 0 };
+            let m0 = u64x2(m(0b11), m(0b1100));
+            let m1 = u64x2(m(0b11_0000), m(0b1100_0000));
+            U64x4(
+                v128_bitselect(y.0, self.0, m0),
+                v128_bitselect(y.1, self.1, m1),
+            )
+        }
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+pub(crate) use wasm32::U64x4;
+
+/// Portable lanes, to check the generic code on any target.
+#[cfg(test)]
+impl Lanes for [u64; 4] {
+    unsafe fn splat(x: u64) -> Self {
+        [x; 4]
+    }
+
+    unsafe fn load(x: &[u64; 4]) -> Self {
+        *x
+    }
+
+    unsafe fn store(self, out: &mut [u64; 4]) {
+        *out = self
+    }
+
+    unsafe fn add(self, y: Self) -> Self {
+        [0, 1, 2, 3].map(|i| self[i].wrapping_add(y[i]))
+    }
+
+    unsafe fn sub(self, y: Self) -> Self {
+        [0, 1, 2, 3].map(|i| self[i].wrapping_sub(y[i]))
+    }
+
+    unsafe fn and(self, y: Self) -> Self {
+        [0, 1, 2, 3].map(|i| self[i] & y[i])
+    }
+
+    #[cfg(any(test, feature = "x25519"))]
+    unsafe fn xor(self, y: Self) -> Self {
+        [0, 1, 2, 3].map(|i| self[i] ^ y[i])
+    }
+
+    unsafe fn mul32(self, y: Self) -> Self {
+        [0, 1, 2, 3].map(|i| (self[i] as u32 as u64) * (y[i] as u32 as u64))
+    }
+
+    unsafe fn shr<const S: i32>(self) -> Self {
+        self.map(|x| x >> S)
+    }
+
+    unsafe fn shl<const S: i32>(self) -> Self {
+        self.map(|x| x << S)
+    }
+
+    unsafe fn permute<const P: i32>(self) -> Self {
+        [0, 1, 2, 3].map(|i| self[((P >> (2 * i)) & 3) as usize])
+    }
+
+    unsafe fn blend<const M: i32>(self, y: Self) -> Self {
+        [0, 1, 2, 3].map(|i| {
+            if (M >> (2 * i)) & 3 != 0 {
+                y[i]
+            } else {
+                self[i]
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+fn check_fex4<V: Lanes>() {
+    use super::sha512;
+
+    let mut fe = [Fe::default(); 12];
+    for (i, fe) in fe.iter_mut().enumerate() {
+        *fe = Fe::from_bytes(&sha512::Hash::hash([i as u8])[..32]);
+    }
+    let mut p_minus_1 = [0xffu8; 32];
+    p_minus_1[0] = 0xec;
+    p_minus_1[31] = 0x7f;
+    fe[0] = Fe::from_bytes(&p_minus_1);
+    fe[5] = Fe::default();
+
+    for chunk in fe.chunks_exact(4) {
+        let f = [chunk[0], chunk[1], chunk[2], chunk[3]];
+        let g = [chunk[3], chunk[0], chunk[1], chunk[2]];
+        unsafe {
+            let (fv, gv) = (FeX4::<V>::new(&f), FeX4::<V>::new(&g));
+            let eq = |v: FeX4<V>, expected: [Fe; 4]| {
+                for (x, y) in v.split().iter().zip(expected.iter()) {
+                    assert_eq!(x.to_bytes(), y.to_bytes());
+                }
+            };
+            eq(fv, f);
+            eq(fv.mul(&gv), [0, 1, 2, 3].map(|i| f[i] * g[i]));
+            eq(fv.square(), [0, 1, 2, 3].map(|i| f[i].square()));
+            eq(fv.sub(&gv), [0, 1, 2, 3].map(|i| f[i] - g[i]));
+            eq(fv.neg(), [0, 1, 2, 3].map(|i| f[i].neg()));
+            #[cfg(feature = "x25519")]
+            eq(
+                fv.mul_small(121666),
+                [0, 1, 2, 3].map(|i| f[i].mul32(121666)),
+            );
+            let s = fv.add(&gv);
+            let s_ = [0, 1, 2, 3].map(|i| f[i] + g[i]);
+            eq(s, s_);
+            eq(s.mul(&s), [0, 1, 2, 3].map(|i| s_[i] * s_[i]));
+            eq(s.sub(&s.add(&s)), [0, 1, 2, 3].map(|i| s_[i].neg()));
+            eq(
+                fv.permute::<{ lanes(3, 3, 0, 1) }>(),
+                [f[3], f[3], f[0], f[1]],
+            );
+            eq(
+                fv.blend::<{ select(false, true, true, false) }>(&gv),
+                [f[0], g[1], g[2], f[3]],
+            );
+            eq(fv.cswap_halves(0), f);
+            eq(fv.cswap_halves(1), [f[2], f[3], f[0], f[1]]);
+        }
+    }
+}
+
+#[test]
+fn test_fex4() {
+    check_fex4::<[u64; 4]>();
+    match Backend::detect() {
+        #[cfg(target_arch = "x86_64")]
+        Some(Backend::Avx2) => {
+            #[target_feature(enable = "avx2")]
+            unsafe fn check_avx2() {
+                check_fex4::<core::arch::x86_64::__m256i>()
+            }
+            unsafe { check_avx2() }
+        }
+        #[cfg(target_arch = "aarch64")]
+        Some(Backend::Neon) => check_fex4::<U64x4>(),
+        #[cfg(target_arch = "wasm32")]
+        Some(Backend::Simd128) => check_fex4::<U64x4>(),
+        None => {}
+    }
+}
@@ -0,0 +1,629 @@
+//! Hybrid Public Key Encryption (RFC 9180) with the
+//! DHKEM(X25519, HKDF-SHA256) KEM and the HKDF-SHA256 KDF.
+//!
+//! The base, PSK, auth and auth-PSK modes are supported, with
+//! ChaCha20-Poly1305 or the export-only AEAD.
+//!
+//! The mode is selected by the presence of a pre-shared key and of a sender
+//! key pair (or of the sender public key, on the recipient side).
+//! The encapsulated key sent to the recipient is the ephemeral X25519 public
+//! key returned by the sender setup functions.
+
+use core::fmt;
+
+use super::chacha20poly1305;
+use super::common::*;
+use super::error::Error;
+use super::sha256::{Hkdf, Hmac};
+use super::x25519::{KeyPair, PublicKey, SecretKey};
+
+const VERSION_LABEL: &[u8] = b"HPKE-v1";
+const KEM_ID: u16 = 0x0020;
+const KDF_ID: u16 = 0x0001;
+const KEM_SUITE_ID: [u8; 5] = [b'K', b'E', b'M', (KEM_ID >> 8) as u8, KEM_ID as u8];
+
+const HASH_BYTES: usize = 32;
+
+/// An AEAD algorithm.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Aead {
+    /// ChaCha20-Poly1305.
+    ChaCha20Poly1305,
+    /// No encryption, only the secret export interface is available.
+    ExportOnly,
+}
+
+impl Aead {
+    /// Number of bytes added to a message by the AEAD.
+    pub const TAG_BYTES: usize = chacha20poly1305::TAG_BYTES;
+
+    /// Returns the IANA identifier of the AEAD.
+    pub fn id(self) -> u16 {
+        match self {
+            Aead::ChaCha20Poly1305 => 0x0003,
+            Aead::ExportOnly => 0xffff,
+        }
+    }
+}
+
+/// A pre-shared key, along with its identifier.
+#[derive(Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Psk<'a> {
+    /// The pre-shared key. It must have at least 32 bytes of entropy.
+    pub key: &'a [u8],
+    /// The pre-shared key identifier.
+    pub id: &'a [u8],
+}
+
+impl fmt::Debug for Psk<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Psk")
+            .field("id", &self.id)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Mode {
+    Base = 0,
+    Psk = 1,
+    Auth = 2,
+    AuthPsk = 3,
+}
+
+impl Mode {
+    fn new(psk: bool, auth: bool) -> Mode {
+        match (psk, auth) {
+            (false, false) => Mode::Base,
+            (true, false) => Mode::Psk,
+            (false, true) => Mode::Auth,
+            (true, true) => Mode::AuthPsk,
+        }
+    }
+}
+
+fn suite_id(aead: Aead) -> [u8; 10] {
+    let mut suite_id = *b"HPKE\0\0\0\0\0\0";
+    suite_id[4..6].copy_from_slice(&KEM_ID.to_be_bytes());
+    suite_id[6..8].copy_from_slice(&KDF_ID.to_be_bytes());
+    suite_id[8..].copy_from_slice(&aead.id().to_be_bytes());
+    suite_id
+}
+
+fn labeled_extract(suite_id: &[u8], salt: &[u8], label: &[u8], ikm: &[&[u8]]) -> [u8; HASH_BYTES] {
+    let mut h = Hmac::new(salt);
+    h.update(VERSION_LABEL);
+    h.update(suite_id);
+    h.update(label);
+    for ikm in ikm {
+        h.update(ikm);
+    }
+    h.finalize()
+}
+
+fn labeled_expand(
+    out: &mut [u8],
+    suite_id: &[u8],
+    prk: &[u8; HASH_BYTES],
+    label: &[u8],
+    info: &[u8],
+) -> Result<(), Error> {
+    if out.len() > 255 * HASH_BYTES {
+        return Err(Error::InvalidParameters);
+    }
+    let len = (out.len() as u16).to_be_bytes();
+    Hkdf::expand_parts(out, prk, &[&len, VERSION_LABEL, suite_id, label, info]);
+    Ok(())
+}
+
+/// Deterministically derives a key pair from input keying material.
+pub fn derive_key_pair(ikm: &[u8]) -> Result<KeyPair, Error> {
+    let mut prk = labeled_extract(&KEM_SUITE_ID, &[], b"dkp_prk", &[ikm]);
+    let mut sk = [0u8; SecretKey::BYTES];
+    labeled_expand(&mut sk, &KEM_SUITE_ID, &prk, b"sk", &[])?;
+    Mem::wipe(&mut prk);
+    let sk = SecretKey::new(sk);
+    let pk = sk.recover_public_key()?;
+    Ok(KeyPair { pk, sk })
+}
+
+/// Computes the KEM shared secret from the concatenated DH outputs.
+fn extract_and_expand(
+    dh: &[u8],
+    enc: &PublicKey,
+    pk_r: &PublicKey,
+    pk_s: Option<&PublicKey>,
+) -> [u8; HASH_BYTES] {
+    let mut prk = labeled_extract(&KEM_SUITE_ID, &[], b"eae_prk", &[dh]);
+    let mut kem_context = [0u8; 3 * PublicKey::BYTES];
+    kem_context[..32].copy_from_slice(&enc[..]);
+    kem_context[32..64].copy_from_slice(&pk_r[..]);
+    let kem_context = match pk_s {
+        Some(pk_s) => {
+            kem_context[64..].copy_from_slice(&pk_s[..]);
+            &kem_context[..]
+        }
+        None => &kem_context[..64],
+    };
+    let mut shared_secret = [0u8; HASH_BYTES];
+    labeled_expand(
+        &mut shared_secret,
+        &KEM_SUITE_ID,
+        &prk,
+        b"shared_secret",
+        kem_context,
+    )
+    .unwrap();
+    Mem::wipe(&mut prk);
+    shared_secret
+}
+
+struct Context {
+    aead: Aead,
+    key: [u8; chacha20poly1305::KEY_BYTES],
+    base_nonce: [u8; chacha20poly1305::NONCE_BYTES],
+    exporter_secret: [u8; HASH_BYTES],
+    seq: u64,
+}
+
+impl Context {
+    fn new(
+        aead: Aead,
+        mode: Mode,
+        shared_secret: &[u8; HASH_BYTES],
+        info: &[u8],
+        psk: Option<Psk<'_>>,
+    ) -> Result<Self, Error> {
+        let psk = psk.unwrap_or(Psk { key: &[], id: &[] });
+        if (mode == Mode::Psk || mode == Mode::AuthPsk) && (psk.key.is_empty() || psk.id.is_empty())
+        {
+            return Err(Error::InvalidParameters);
+        }
+        let suite_id = suite_id(aead);
+        let mut ks_context = [0u8; 1 + 2 * HASH_BYTES];
+        ks_context[0] = mode as u8;
+        ks_context[1..33].copy_from_slice(&labeled_extract(
+            &suite_id,
+            &[],
+            b"psk_id_hash",
+            &[psk.id],
+        ));
+        ks_context[33..].copy_from_slice(&labeled_extract(&suite_id, &[], b"info_hash", &[info]));
+        let mut secret = labeled_extract(&suite_id, shared_secret, b"secret", &[psk.key]);
+        let mut context = Context {
+            aead,
+            key: [0u8; chacha20poly1305::KEY_BYTES],
+            base_nonce: [0u8; chacha20poly1305::NONCE_BYTES],
+            exporter_secret: [0u8; HASH_BYTES],
+            seq: 0,
+        };
+        if aead != Aead::ExportOnly {
+            labeled_expand(&mut context.key, &suite_id, &secret, b"key", &ks_context)?;
+            labeled_expand(
+                &mut context.base_nonce,
+                &suite_id,
+                &secret,
+                b"base_nonce",
+                &ks_context,
+            )?;
+        }
+        labeled_expand(
+            &mut context.exporter_secret,
+            &suite_id,
+            &secret,
+            b"exp",
+            &ks_context,
+        )?;
+        Mem::wipe(&mut secret);
+        Ok(context)
+    }
+
+    /// Returns the nonce for the current sequence number, and increments it.
+    fn next_nonce(&mut self) -> Result<[u8; chacha20poly1305::NONCE_BYTES], Error> {
+        if self.aead == Aead::ExportOnly || self.seq == u64::MAX {
+            return Err(Error::InvalidParameters);
+        }
+        let mut nonce = self.base_nonce;
+        for (n, s) in nonce[4..].iter_mut().zip(self.seq.to_be_bytes().iter()) {
+            *n ^= s;
+        }
+        self.seq += 1;
+        Ok(nonce)
+    }
+
+    fn export(&self, out: &mut [u8], exporter_context: &[u8]) -> Result<(), Error> {
+        let suite_id = suite_id(self.aead);
+        labeled_expand(
+            out,
+            &suite_id,
+            &self.exporter_secret,
+            b"sec",
+            exporter_context,
+        )
+    }
+}
+
+impl Drop for Context {
+    fn drop(&mut self) {
+        Mem::wipe(&mut self.key);
+        Mem::wipe(&mut self.base_nonce);
+        Mem::wipe(&mut self.exporter_secret);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for Context {
+    fn zeroize(&mut self) {
+        Mem::wipe(&mut self.key);
+        Mem::wipe(&mut self.base_nonce);
+        Mem::wipe(&mut self.exporter_secret);
+    }
+}
+
+/// An encryption context, for the sender.
+pub struct SenderContext(Context);
+
+impl SenderContext {
+    /// Encrypts `message` into `out`, and returns the number of bytes written
+    /// (`message.len() + Aead::TAG_BYTES`).
+    pub fn seal_into(
+        &mut self,
+        out: &mut [u8],
+        aad: &[u8],
+        message: &[u8],
+    ) -> Result<usize, Error> {
+        let len = message.len() + Aead::TAG_BYTES;
+        if out.len() < len {
+            return Err(Error::BufferTooSmall);
+        }
+        let nonce = self.0.next_nonce()?;
+        let (ciphertext, tag) = out[..len].split_at_mut(message.len());
+        ciphertext.copy_from_slice(message);
+        tag.copy_from_slice(&chacha20poly1305::seal(
+            &self.0.key,
+            &nonce,
+            aad,
+            ciphertext,
+        ));
+        Ok(len)
+    }
+
+    /// Encrypts `message`.
+    #[cfg(feature = "std")]
+    pub fn seal(&mut self, aad: &[u8], message: &[u8]) -> Result<Vec<u8>, Error> {
+        let mut out = vec![0u8; message.len() + Aead::TAG_BYTES];
+        self.seal_into(&mut out, aad, message)?;
+        Ok(out)
+    }
+
+    /// Derives a secret of length `out.len()` from the context.
+    pub fn export(&self, out: &mut [u8], exporter_context: &[u8]) -> Result<(), Error> {
+        self.0.export(out, exporter_context)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for SenderContext {
+    fn zeroize(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.0)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for SenderContext {}
+
+/// A decryption context, for the recipient.
+pub struct RecipientContext(Context);
+
+impl RecipientContext {
+    /// Verifies and decrypts `ciphertext` into `out`, and returns the number
+    /// of bytes written (`ciphertext.len() - Aead::TAG_BYTES`).
+    pub fn open_into(
+        &mut self,
+        out: &mut [u8],
+        aad: &[u8],
+        ciphertext: &[u8],
+    ) -> Result<usize, Error> {
+        if ciphertext.len() < Aead::TAG_BYTES {
+            return Err(Error::DecryptionFailed);
+        }
+        let (ciphertext, tag) = ciphertext.split_at(ciphertext.len() - Aead::TAG_BYTES);
+        if out.len() < ciphertext.len() {
+            return Err(Error::BufferTooSmall);
+        }
+        let mut tag_ = [0u8; Aead::TAG_BYTES];
+        tag_.copy_from_slice(tag);
+        let message = &mut out[..ciphertext.len()];
+        message.copy_from_slice(ciphertext);
+        let seq = self.0.seq;
+        let nonce = self.0.next_nonce()?;
+        if let Err(e) = chacha20poly1305::open(&self.0.key, &nonce, aad, message, &tag_) {
+            self.0.seq = seq;
+            Mem::wipe(message);
+            return Err(e);
+        }
+        Ok(message.len())
+    }
+
+    /// Verifies and decrypts `ciphertext`.
+    #[cfg(feature = "std")]
+    pub fn open(&mut self, aad: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>, Error> {
+        let mut out = vec![0u8; ciphertext.len().saturating_sub(Aead::TAG_BYTES)];
+        self.open_into(&mut out, aad, ciphertext)?;
+        Ok(out)
+    }
+
+    /// Derives a secret of length `out.len()` from the context.
+    pub fn export(&self, out: &mut [u8], exporter_context: &[u8]) -> Result<(), Error> {
+        self.0.export(out, exporter_context)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for RecipientContext {
+    fn zeroize(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.0)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for RecipientContext {}
+
+/// Sets up an encryption context for `pk_r`, and returns the encapsulated
+/// key along with the context.
+///
+/// If `sender` is set, the sender is authenticated using that key pair.
+#[cfg(feature = "random")]
+pub fn setup_sender(
+    aead: Aead,
+    pk_r: &PublicKey,
+    info: &[u8],
+    psk: Option<Psk<'_>>,
+    sender: Option<&KeyPair>,
+) -> Result<(PublicKey, SenderContext), Error> {
+    setup_sender_with_ephemeral(aead, pk_r, info, psk, sender, &KeyPair::generate())
+}
+
+/// Sets up an encryption context for `pk_r` with a caller-supplied ephemeral
+/// key pair. The ephemeral key pair must never be reused.
+pub fn setup_sender_with_ephemeral(
+    aead: Aead,
+    pk_r: &PublicKey,
+    info: &[u8],
+    psk: Option<Psk<'_>>,
+    sender: Option<&KeyPair>,
+    ephemeral: &KeyPair,
+) -> Result<(PublicKey, SenderContext), Error> {
+    let mut dh = [0u8; 2 * HASH_BYTES];
+    dh[..32].copy_from_slice(&pk_r.dh(&ephemeral.sk)?[..]);
+    let dh_len = match sender {
+        Some(sender) => {
+            dh[32..].copy_from_slice(&pk_r.dh(&sender.sk)?[..]);
+            64
+        }
+        None => 32,
+    };
+    let enc = ephemeral.pk;
+    let mut shared_secret = extract_and_expand(&dh[..dh_len], &enc, pk_r, sender.map(|kp| &kp.pk));
+    Mem::wipe(&mut dh);
+    let mode = Mode::new(psk.is_some(), sender.is_some());
+    let context = Context::new(aead, mode, &shared_secret, info, psk);
+    Mem::wipe(&mut shared_secret);
+    Ok((enc, SenderContext(context?)))
+}
+
+/// Sets up a decryption context from the encapsulated key `enc`.
+///
+/// If `pk_s` is set, the sender must have been authenticated with the
+/// matching key pair.
+pub fn setup_recipient(
+    aead: Aead,
+    enc: &PublicKey,
+    kp_r: &KeyPair,
+    info: &[u8],
+    psk: Option<Psk<'_>>,
+    pk_s: Option<&PublicKey>,
+) -> Result<RecipientContext, Error> {
+    let mut dh = [0u8; 2 * HASH_BYTES];
+    dh[..32].copy_from_slice(&enc.dh(&kp_r.sk)?[..]);
+    let dh_len = match pk_s {
+        Some(pk_s) => {
+            dh[32..].copy_from_slice(&pk_s.dh(&kp_r.sk)?[..]);
+            64
+        }
+        None => 32,
+    };
+    let mut shared_secret = extract_and_expand(&dh[..dh_len], enc, &kp_r.pk, pk_s);
+    Mem::wipe(&mut dh);
+    let mode = Mode::new(psk.is_some(), pk_s.is_some());
+    let context = Context::new(aead, mode, &shared_secret, info, psk);
+    Mem::wipe(&mut shared_secret);
+    Ok(RecipientContext(context?))
+}
+
+#[test]
+fn test_hpke() {
+    use ct_codecs::{Decoder, Hex};
+
+    struct Vector {
+        mode: Mode,
+        aead: Aead,
+        ikm_e: &'static str,
+        ikm_r: &'static str,
+        ikm_s: &'static str,
+        pk_r: &'static str,
+        pk_s: &'static str,
+        enc: &'static str,
+        ciphertexts: [&'static str; 2],
+        exports: [&'static str; 3],
+    }
+
+    // RFC 9180, appendix A
+    let vectors = [
+        Vector {
+            mode: Mode::Base,
+            aead: Aead::ChaCha20Poly1305,
+            ikm_e: "909a9b35d3dc4713a5e72a4da274b55d3d3821a37e5d099e74a647db583a904b",
+            ikm_r: "1ac01f181fdf9f352797655161c58b75c656a6cc2716dcb66372da835542e1df",
+            ikm_s: "",
+            pk_r: "4310ee97d88cc1f088a5576c77ab0cf5c3ac797f3d95139c6c84b5429c59662a",
+            pk_s: "",
+            enc: "1afa08d3dec047a643885163f1180476fa7ddb54c6a8029ea33f95796bf2ac4a",
+            ciphertexts: [
+                "1c5250d8034ec2b784ba2cfd69dbdb8af406cfe3ff938e131f0def8c8b60b4db21993c62ce81883d2dd1b51a28",
+                "6b53c051e4199c518de79594e1c4ab18b96f081549d45ce015be002090bb119e85285337cc95ba5f59992dc98c",
+            ],
+            exports: [
+                "4bbd6243b8bb54cec311fac9df81841b6fd61f56538a775e7c80a9f40160606e",
+                "8c1df14732580e5501b00f82b10a1647b40713191b7c1240ac80e2b68808ba69",
+                "5acb09211139c43b3090489a9da433e8a30ee7188ba8b0a9a1ccf0c229283e53",
+            ],
+        },
+        Vector {
+            mode: Mode::Psk,
+            aead: Aead::ChaCha20Poly1305,
+            ikm_e: "35706a0b09fb26fb45c39c2f5079c709c7cf98e43afa973f14d88ece7e29c2e3",
+            ikm_r: "26b923eade72941c8a85b09986cdfa3f1296852261adedc52d58d2930269812b",
+            ikm_s: "",
+            pk_r: "13640af826b722fc04feaa4de2f28fbd5ecc03623b317834e7ff4120dbe73062",
+            pk_s: "",
+            enc: "2261299c3f40a9afc133b969a97f05e95be2c514e54f3de26cbe5644ac735b04",
+            ciphertexts: [
+                "4a177f9c0d6f15cfdf533fb65bf84aecdc6ab16b8b85b4cf65a370e07fc1d78d28fb073214525276f4a89608ff",
+                "5c3cabae2f0b3e124d8d864c116fd8f20f3f56fda988c3573b40b09997fd6c769e77c8eda6cda4f947f5b704a8",
+            ],
+            exports: [
+                "813c1bfc516c99076ae0f466671f0ba5ff244a41699f7b2417e4c59d46d39f40",
+                "2745cf3d5bb65c333658732954ee7af49eb895ce77f8022873a62a13c94cb4e1",
+                "ad40e3ae14f21c99bfdebc20ae14ab86f4ca2dc9a4799d200f43a25f99fa78ae",
+            ],
+        },
+        Vector {
+            mode: Mode::Auth,
+            aead: Aead::ChaCha20Poly1305,
+            ikm_e: "938d3daa5a8904540bc24f48ae90eed3f4f7f11839560597b55e7c9598c996c0",
+            ikm_r: "64835d5ee64aa7aad57c6f2e4f758f7696617f8829e70bc9ac7a5ef95d1c756c",
+            ikm_s: "9d8f94537d5a3ddef71234c0baedfad4ca6861634d0b94c3007fed557ad17df6",
+            pk_r: "1a478716d63cb2e16786ee93004486dc151e988b34b475043d3e0175bdb01c44",
+            pk_s: "f0f4f9e96c54aeed3f323de8534fffd7e0577e4ce269896716bcb95643c8712b",
+            enc: "f7674cc8cd7baa5872d1f33dbaffe3314239f6197ddf5ded1746760bfc847e0e",
+            ciphertexts: [
+                "ab1a13c9d4f01a87ec3440dbd756e2677bd2ecf9df0ce7ed73869b98e00c09be111cb9fdf077347aeb88e61bdf",
+                "3265c7807ffff7fdace21659a2c6ccffee52a26d270c76468ed74202a65478bfaedfff9c2b7634e24f10b71016",
+            ],
+            exports: [
+                "070cffafd89b67b7f0eeb800235303a223e6ff9d1e774dce8eac585c8688c872",
+                "2852e728568d40ddb0edde284d36a4359c56558bb2fb8837cd3d92e46a3a14a8",
+                "1df39dc5dd60edcbf5f9ae804e15ada66e885b28ed7929116f768369a3f950ee",
+            ],
+        },
+        Vector {
+            mode: Mode::AuthPsk,
+            aead: Aead::ChaCha20Poly1305,
+            ikm_e: "49d6eac8c6c558c953a0a252929a818745bb08cd3d29e15f9f5db5eb2e7d4b84",
+            ikm_r: "f3304ddcf15848488271f12b75ecaf72301faabf6ad283654a14c398832eb184",
+            ikm_s: "20ade1d5203de1aadfb261c4700b6432e260d0d317be6ebbb8d7fffb1f86ad9d",
+            pk_r: "a5099431c35c491ec62ca91df1525d6349cb8aa170c51f9581f8627be6334851",
+            pk_s: "3ac5bd4dd66ff9f2740bef0d6ccb66daa77bff7849d7895182b07fb74d087c45",
+            enc: "656a2e00dc9990fd189e6e473459392df556e9a2758754a09db3f51179a3fc02",
+            ciphertexts: [
+                "9aa52e29274fc6172e38a4461361d2342585d3aeec67fb3b721ecd63f059577c7fe886be0ede01456ebc67d597",
+                "59460bacdbe7a920ef2806a74937d5a691d6d5062d7daafcad7db7e4d8c649adffe575c1889c5c2e3a49af8e3e",
+            ],
+            exports: [
+                "c23ebd4e7a0ad06a5dddf779f65004ce9481069ce0f0e6dd51a04539ddcbd5cd",
+                "ed7ff5ca40a3d84561067ebc8e01702bc36cf1eb99d42a92004642b9dfaadd37",
+                "d3bae066aa8da27d527d85c040f7dd6ccb60221c902ee36a82f70bcd62a60ee4",
+            ],
+        },
+        Vector {
+            mode: Mode::AuthPsk,
+            aead: Aead::ExportOnly,
+            ikm_e: "94efae91e96811a3a49fd1b20eb0344d68ead6ac01922c2360779aa172487f40",
+            ikm_r: "4dfde6fadfe5cb50fced4034e84e6d3a104aa4bf2971360032c1c0580e286663",
+            ikm_s: "26c12fef8d71d13bbbf08ce8157a283d5e67ecf0f345366b0e90341911110f1b",
+            pk_r: "f47cd9d6993d2e2234eb122b425accfb486ee80f89607b087094e9f413253c2d",
+            pk_s: "29a5bf3867a6128bbdf8e070abe7fe70ca5e07b629eba5819af73810ee20112f",
+            enc: "81cbf4bd7eee97dd0b600252a1c964ea186846252abb340be47087cc78f3d87c",
+            ciphertexts: ["", ""],
+            exports: [
+                "dafd8beb94c5802535c22ff4c1af8946c98df2c417e187c6ccafe45335810b58",
+                "7346bb0b56caf457bcc1aa63c1b97d9834644bdacac8f72dbbe3463e4e46b0dd",
+                "84f3466bd5a03bde6444324e63d7560e7ac790da4e5bbab01e7c4d575728c34a",
+            ],
+        },
+    ];
+    let hex = |s: &str| Hex::decode_to_vec(s, None).unwrap();
+    let info = b"Ode on a Grecian Urn";
+    let psk_key = hex("0247fd33b913760fa1fa51e1892d9f307fbe65eb171e8132c2af18555a738b82");
+    let psk = Psk {
+        key: &psk_key,
+        id: b"Ennyn Durin aran Moria",
+    };
+    let message = b"Beauty is truth, truth beauty";
+    let exporter_contexts: [&[u8]; 3] = [b"", b"\x00", b"TestContext"];
+
+    for v in vectors.iter() {
+        let psk = match v.mode {
+            Mode::Psk | Mode::AuthPsk => Some(psk),
+            _ => None,
+        };
+        let ephemeral = derive_key_pair(&hex(v.ikm_e)).unwrap();
+        let kp_r = derive_key_pair(&hex(v.ikm_r)).unwrap();
+        assert_eq!(&kp_r.pk[..], &hex(v.pk_r)[..]);
+        let kp_s = match v.mode {
+            Mode::Auth | Mode::AuthPsk => Some(derive_key_pair(&hex(v.ikm_s)).unwrap()),
+            _ => None,
+        };
+        if let Some(kp_s) = &kp_s {
+            assert_eq!(&kp_s.pk[..], &hex(v.pk_s)[..]);
+        }
+        let (enc, mut sender) =
+            setup_sender_with_ephemeral(v.aead, &kp_r.pk, info, psk, kp_s.as_ref(), &ephemeral)
+                .unwrap();
+        assert_eq!(&enc[..], &hex(v.enc)[..]);
+        let mut recipient = setup_recipient(
+            v.aead,
+            &enc,
+            &kp_r,
+            info,
+            psk,
+            kp_s.as_ref().map(|kp| &kp.pk),
+        )
+        .unwrap();
+
+        let mut ciphertext = [0u8; 29 + Aead::TAG_BYTES];
+        let mut decrypted = [0u8; 29];
+        if v.aead == Aead::ExportOnly {
+            assert_eq!(
+                sender.seal_into(&mut ciphertext, b"", message),
+                Err(Error::InvalidParameters)
+            );
+        } else {
+            for (i, expected) in v.ciphertexts.iter().enumerate() {
+                let aad = [b'C', b'o', b'u', b'n', b't', b'-', b'0' + i as u8];
+                assert_eq!(
+                    sender.seal_into(&mut ciphertext, &aad, message),
+                    Ok(ciphertext.len())
+                );
+                assert_eq!(&ciphertext[..], &hex(expected)[..]);
+                assert_eq!(
+                    recipient.open_into(&mut decrypted, b"", &ciphertext),
+                    Err(Error::DecryptionFailed)
+                );
+                assert_eq!(
+                    recipient.open_into(&mut decrypted, &aad, &ciphertext),
+                    Ok(decrypted.len())
+                );
+                assert_eq!(&decrypted, message);
+            }
+        }
+        for (exporter_context, expected) in exporter_contexts.iter().zip(v.exports.iter()) {
+            let mut exported = [0u8; 32];
+            sender.export(&mut exported, exporter_context).unwrap();
+            assert_eq!(&exported[..], &hex(expected)[..]);
+            recipient.export(&mut exported, exporter_context).unwrap();
+            assert_eq!(&exported[..], &hex(expected)[..]);
+        }
+    }
+}
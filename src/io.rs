@@ -0,0 +1,109 @@
+//! `std::io` adapters for streaming hashing, signing and verification.
+//!
+//! The hash function and the streaming states implement `std::io::Write`,
+//! so that data can be piped into them with `std::io::copy()`.
+
+#[cfg(not(feature = "disable-signatures"))]
+use std::io::Read;
+use std::io::{self, Write};
+
+use super::sha512;
+#[cfg(not(feature = "disable-signatures"))]
+use super::{Noise, PublicKey, SecretKey, Signature, SigningState, VerifyingState};
+
+impl Write for sha512::Hash {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(not(feature = "disable-signatures"))]
+impl Write for SigningState {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.absorb(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(not(feature = "disable-signatures"))]
+impl Write for VerifyingState {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.absorb(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(not(feature = "disable-signatures"))]
+impl SecretKey {
+    /// Computes a signature for everything that can be read from `reader`.
+    /// It is critical for `noise` to never repeat.
+    ///
+    /// The message is read only once, so the signature is computed using the
+    /// streaming API, and differs from the one `sign()` would return.
+    pub fn sign_reader(&self, mut reader: impl Read, noise: Noise) -> io::Result<Signature> {
+        let mut st = self.sign_incremental(noise);
+        io::copy(&mut reader, &mut st)?;
+        Ok(st.sign())
+    }
+}
+
+#[cfg(not(feature = "disable-signatures"))]
+impl PublicKey {
+    /// Verifies that the signature `signature` is valid for everything that
+    /// can be read from `reader`.
+    ///
+    /// An invalid signature is reported as an `InvalidData` error, wrapping
+    /// the underlying `Error`.
+    pub fn verify_reader(&self, mut reader: impl Read, signature: &Signature) -> io::Result<()> {
+        let invalid = |e| io::Error::new(io::ErrorKind::InvalidData, e);
+        let mut st = self.verify_incremental(signature).map_err(invalid)?;
+        io::copy(&mut reader, &mut st)?;
+        st.verify().map_err(invalid)
+    }
+}
+
+#[test]
+fn test_hash_write() {
+    let msg = [0x42u8; 1000];
+    let mut h = sha512::Hash::new();
+    io::copy(&mut &msg[..], &mut h).unwrap();
+    assert_eq!(h.finalize(), sha512::Hash::hash(&msg[..]));
+}
+
+#[cfg(not(feature = "disable-signatures"))]
+#[test]
+fn test_sign_reader() {
+    use super::{Error, KeyPair};
+
+    let kp = KeyPair::from_seed([42u8; 32].into());
+    let msg = [0x42u8; 100_000];
+    let signature = kp
+        .sk
+        .sign_reader(&msg[..], Noise::from_slice(&[1u8; 16]).unwrap())
+        .unwrap();
+    kp.pk.verify(&msg[..], &signature).unwrap();
+    kp.pk.verify_reader(&msg[..], &signature).unwrap();
+    let err = kp.pk.verify_reader(&msg[1..], &signature).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert_eq!(
+        err.into_inner().unwrap().downcast::<Error>().unwrap(),
+        Box::new(Error::SignatureMismatch)
+    );
+
+    let mut st = kp.pk.verify_incremental(&signature).unwrap();
+    st.write_all(&msg[..]).unwrap();
+    st.verify().unwrap();
+}
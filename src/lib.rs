@@ -80,7 +80,8 @@
 //!   valid. This is slower, but improves resilience against fault attacks. It
 //!   is enabled by default on WebAssembly targets.
 //! * `std`: disables `no_std` compatibility in order to make errors implement
-//!   the standard `Error` trait.
+//!   the standard `Error` trait. This also adds the `multiscalar_mul` and
+//!   `multiscalar_mul_ct` functions, that need to allocate memory.
 //! * `random` (enabled by default): adds `Default` and `generate`
 //!   implementations to the `Seed` and `Noise` objects, in order to securely
 //!   create random keys and noise.
@@ -91,9 +92,65 @@
 //! * `blind-keys`: add support for key blinding.
 //! * `opt_size`: Enable size optimizations (based on benchmarks, 8-15% size
 //!   reduction at the cost of 6.5-7% performance).
+//! * `u32-backend`: use the 32-bit (10 limbs of 25.5 bits) field arithmetic
+//!   instead of the 64-bit one. This is the default on targets whose pointers
+//!   are not 64-bit wide, such as WebAssembly and most embedded targets.
+//! * `fast-base`: use a 30 KB table of precomputed multiples of the base
+//!   point, making key generation and signing about 4 times faster. With
+//!   `x25519`, this table is also used to compute X25519 public keys.
+//!   Without this feature, a 16-entry table is used, or no table at all with
+//!   `opt_size`. This feature takes precedence over `opt_size`.
+//! * `simd`: use AVX2 (x86_64) or NEON (AArch64), when supported by the CPU,
+//!   to compute four field operations at once in signature verification and
+//!   in X25519. Signature verification always uses it, but the X25519 ladder
+//!   only does with the 32-bit field arithmetic, as the 64-bit one is faster
+//!   than four-way arithmetic for it. On WebAssembly, SIMD128 is used if the `simd128`
+//!   target feature is also enabled. Independently of this feature, that
+//!   target feature enables a vectorized SHA-512 message schedule.
 //! * `x25519`: Enable support for the X25519 key exchange system.
 //! * `disable-signatures`: Disable support for signatures, and only compile
 //!   support for X25519.
+//! * `cose`: add support for `COSE_Key` and `COSE_Sign1` (EdDSA) structures.
+//! * `minisign`: add support for minisign keys and signatures.
+//! * `signify`: add support for OpenBSD signify keys, signatures and signed
+//!   checksum files.
+//! * `age`: add support for age `X25519` and `ssh-ed25519` recipient stanzas.
+//! * `box`: add support for libsodium-compatible `crypto_box` and sealed
+//!   boxes.
+//! * `hpke`: add support for HPKE (RFC 9180) with X25519, HKDF-SHA256 and
+//!   ChaCha20-Poly1305.
+//! * `async`: implement `futures::io::AsyncWrite` for the hash function and
+//!   the streaming states, and add helpers to sign and verify `AsyncRead`
+//!   streams.
+//! * `digest`: implement the traits from the `digest` crate for `Hash`,
+//!   `Sha384`, `Sha512_256`, and `Sha512T` with the standard SHA-512/224 and
+//!   SHA-512/256 lengths. Combined with `traits`, Ed25519ph signatures can be
+//!   computed and verified using `DigestSigner` and `DigestVerifier`.
+//! * `zeroize`: implement the `Zeroize` trait from the `zeroize` crate for
+//!   all the types holding secrets, and `ZeroizeOnDrop` for those that are
+//!   wiped when dropped. `Seed` and `Noise` are `Copy`, so they only
+//!   implement `Zeroize`.
+//! * `rand_core`: add `generate_with_rng` functions, that use a random number
+//!   generator implementing the `CryptoRng` trait from the `rand_core` crate
+//!   instead of `getrandom`, and `sign_incremental_with_rng`, that uses it
+//!   for the additional noise that `sign_incremental` otherwise gets from
+//!   `getrandom`. This doesn't require the `random` feature.
+//! * `subtle`: implement `ConstantTimeEq` from the `subtle` crate for keys,
+//!   signatures, blinds and DH outputs, and `ConditionallySelectable` for
+//!   those that are `Copy`. Even without this feature, comparing types
+//!   holding secrets with `==` is done in constant time.
+//! * `mlock`: add `LockedKeyPair`, a key pair stored in memory that is locked
+//!   in RAM and surrounded by guard pages. Only available on Unix-like
+//!   systems.
+//! * `serde`: implement `Serialize` and `Deserialize` from the `serde` crate
+//!   for public keys and signatures, as hex strings with human-readable
+//!   formats and as fixed-size byte arrays with binary formats. Seeds, noise,
+//!   secret keys and key pairs have to be wrapped in `ExposeSecret` to be
+//!   serialized.
+//! * `text`: implement `Display`, `LowerHex` and `FromStr` for public keys and
+//!   signatures, as hex strings, add `to_base64` and `from_base64` to keys,
+//!   seeds, noise and signatures, and add multibase and `did:key` encodings
+//!   of Ed25519 public keys.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(
@@ -109,27 +166,138 @@
 
 mod common;
 mod error;
+#[cfg(any(test, not(target_pointer_width = "64"), feature = "u32-backend"))]
+mod fiat_25519_32;
+#[cfg(any(test, all(target_pointer_width = "64", not(feature = "u32-backend"))))]
+mod fiat_25519_64;
 mod field25519;
+#[cfg(all(
+    feature = "simd",
+    any(
+        target_arch = "x86_64",
+        target_arch = "aarch64",
+        all(target_arch = "wasm32", target_feature = "simd128")
+    )
+))]
+mod field25519_simd;
 pub mod sha512;
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+mod sha512_hw;
+mod sha512_mb;
 
 pub use crate::common::*;
 pub use crate::error::*;
 
+#[cfg(all(feature = "fast-base", not(feature = "disable-signatures")))]
+mod basepoint_table;
 #[cfg(not(feature = "disable-signatures"))]
 mod ed25519;
 #[cfg(not(feature = "disable-signatures"))]
 mod edwards25519;
+#[cfg(all(
+    feature = "simd",
+    any(
+        target_arch = "x86_64",
+        target_arch = "aarch64",
+        all(target_arch = "wasm32", target_feature = "simd128")
+    ),
+    not(feature = "disable-signatures")
+))]
+mod edwards25519_simd;
 
 #[cfg(not(feature = "disable-signatures"))]
 pub use crate::ed25519::*;
 
+#[cfg(all(feature = "mlock", unix, not(feature = "disable-signatures")))]
+mod locked;
+#[cfg(all(feature = "mlock", unix, not(feature = "disable-signatures")))]
+pub use crate::locked::*;
+
 #[cfg(feature = "x25519")]
 pub mod x25519;
+#[cfg(all(
+    feature = "simd",
+    any(
+        target_arch = "x86_64",
+        target_arch = "aarch64",
+        all(target_arch = "wasm32", target_feature = "simd128")
+    ),
+    feature = "x25519"
+))]
+mod x25519_simd;
 
 #[cfg(not(feature = "disable-signatures"))]
 #[cfg(feature = "pem")]
 mod pem;
 
+#[cfg(feature = "std")]
+mod io;
+
+#[cfg(feature = "async")]
+mod async_io;
+
+#[cfg(feature = "cose")]
+mod cbor;
+#[cfg(feature = "cose")]
+pub mod cose;
+
+#[cfg(any(
+    all(feature = "minisign", not(feature = "disable-signatures")),
+    feature = "box"
+))]
+mod blake2b;
+#[cfg(not(feature = "disable-signatures"))]
+#[cfg(feature = "minisign")]
+pub mod minisign;
+#[cfg(not(feature = "disable-signatures"))]
+#[cfg(all(feature = "minisign", feature = "std"))]
+mod scrypt;
+#[cfg(any(
+    all(
+        feature = "minisign",
+        feature = "std",
+        not(feature = "disable-signatures")
+    ),
+    all(feature = "signify", not(feature = "disable-signatures")),
+    feature = "age",
+    feature = "hpke"
+))]
+mod sha256;
+
+#[cfg(not(feature = "disable-signatures"))]
+#[cfg(feature = "signify")]
+mod bcrypt_pbkdf;
+#[cfg(not(feature = "disable-signatures"))]
+#[cfg(feature = "signify")]
+pub mod signify;
+
+#[cfg(feature = "age")]
+pub mod age;
+#[cfg(feature = "age")]
+mod bech32;
+#[cfg(any(feature = "age", feature = "hpke"))]
+mod chacha20poly1305;
+#[cfg(any(feature = "age", feature = "box", feature = "hpke"))]
+mod poly1305;
+
+#[cfg(feature = "box")]
+pub mod crypto_box;
+#[cfg(feature = "box")]
+mod xsalsa20poly1305;
+
+#[cfg(feature = "hpke")]
+pub mod hpke;
+
+#[cfg(feature = "serde")]
+mod serialization;
+#[cfg(feature = "serde")]
+pub use crate::serialization::*;
+
+#[cfg(all(feature = "text", not(feature = "disable-signatures")))]
+mod base58;
+#[cfg(feature = "text")]
+mod text;
+
 pub mod reexports {
     pub use crate::sha512;
 
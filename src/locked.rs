@@ -0,0 +1,178 @@
+//! Key pairs stored in locked memory.
+//!
+//! The key material is stored in dedicated pages, that are locked in RAM so
+//! that they are never written to swap, excluded from core dumps where the
+//! operating system supports it, and surrounded by inaccessible guard pages
+//! so that out-of-bounds reads and writes of adjacent memory fault instead of
+//! reaching the key. The pages are wiped before being released.
+
+use core::fmt;
+use core::mem;
+use core::ops::Deref;
+use core::ptr;
+
+use super::common::*;
+use super::ed25519::{KeyPair, PublicKey, SecretKey};
+use super::error::Error;
+
+/// Page-aligned, locked memory, between two guard pages.
+struct LockedRegion {
+    base: *mut u8,
+    page_size: usize,
+    len: usize,
+}
+
+impl LockedRegion {
+    fn new(size: usize) -> Result<Self, Error> {
+        let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) };
+        if page_size <= 0 {
+            return Err(Error::MemoryLockFailed);
+        }
+        let page_size = page_size as usize;
+        let len = size.div_ceil(page_size) * page_size;
+        unsafe {
+            let base = libc::mmap(
+                ptr::null_mut(),
+                len + 2 * page_size,
+                libc::PROT_NONE,
+                libc::MAP_PRIVATE | libc::MAP_ANON,
+                -1,
+                0,
+            );
+            if base == libc::MAP_FAILED {
+                return Err(Error::MemoryLockFailed);
+            }
+            let region = LockedRegion {
+                base: base as *mut u8,
+                page_size,
+                len,
+            };
+            let data = region.data() as *mut libc::c_void;
+            if libc::mprotect(data, len, libc::PROT_READ | libc::PROT_WRITE) != 0 {
+                return Err(Error::MemoryLockFailed);
+            }
+            if libc::mlock(data, len) != 0 {
+                return Err(Error::MemoryLockFailed);
+            }
+            #[cfg(any(target_os = "linux", target_os = "android"))]
+            libc::madvise(data, len, libc::MADV_DONTDUMP);
+            #[cfg(target_os = "freebsd")]
+            libc::madvise(data, len, libc::MADV_NOCORE);
+            Ok(region)
+        }
+    }
+
+    fn data(&self) -> *mut u8 {
+        unsafe { self.base.add(self.page_size) }
+    }
+}
+
+impl Drop for LockedRegion {
+    fn drop(&mut self) {
+        unsafe {
+            let data = self.data();
+            if libc::mprotect(
+                data as *mut libc::c_void,
+                self.len,
+                libc::PROT_READ | libc::PROT_WRITE,
+            ) == 0
+            {
+                Mem::wipe(core::slice::from_raw_parts_mut(data, self.len));
+            }
+            libc::munlock(data as *mut libc::c_void, self.len);
+            libc::munmap(
+                self.base as *mut libc::c_void,
+                self.len + 2 * self.page_size,
+            );
+        }
+    }
+}
+
+/// A key pair stored in locked, guard-paged memory.
+///
+/// `LockedKeyPair` dereferences to a `KeyPair`, that can be used to sign
+/// messages. Temporary copies of the secret key made while signing are wiped,
+/// but are not stored in locked memory.
+pub struct LockedKeyPair {
+    region: LockedRegion,
+}
+
+unsafe impl Send for LockedKeyPair {}
+unsafe impl Sync for LockedKeyPair {}
+
+impl LockedKeyPair {
+    /// Moves a key pair into locked memory. The original key pair is wiped.
+    pub fn new(kp: KeyPair) -> Result<Self, Error> {
+        let region = LockedRegion::new(mem::size_of::<KeyPair>())?;
+        unsafe {
+            let p = region.data() as *mut KeyPair;
+            ptr::write(
+                p,
+                KeyPair {
+                    pk: PublicKey::new([0u8; PublicKey::BYTES]),
+                    sk: SecretKey::new([0u8; SecretKey::BYTES]),
+                },
+            );
+            (*p).pk = kp.pk;
+            (*p).sk.copy_from_slice(&kp.sk[..]);
+        }
+        Ok(LockedKeyPair { region })
+    }
+
+    /// Derives a key pair from a secret seed, and stores it in locked memory.
+    /// The caller's seed is wiped.
+    pub fn from_seed(seed: &mut Seed) -> Result<Self, Error> {
+        let kp = KeyPair::from_seed(*seed);
+        seed.wipe_mut();
+        LockedKeyPair::new(kp)
+    }
+
+    /// Generates a new key pair, stored in locked memory.
+    #[cfg(feature = "random")]
+    pub fn generate() -> Result<Self, Error> {
+        LockedKeyPair::from_seed(&mut Seed::default())
+    }
+}
+
+impl Drop for LockedKeyPair {
+    fn drop(&mut self) {
+        unsafe { ptr::drop_in_place(self.region.data() as *mut KeyPair) }
+    }
+}
+
+impl Deref for LockedKeyPair {
+    type Target = KeyPair;
+
+    /// Returns the key pair.
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*(self.region.data() as *const KeyPair) }
+    }
+}
+
+impl fmt::Debug for LockedKeyPair {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LockedKeyPair")
+            .field("pk", &self.pk)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for LockedKeyPair {}
+
+#[test]
+fn test_locked_key_pair() {
+    let kp = KeyPair::from_seed([42u8; 32].into());
+    let mut seed = Seed::new([42u8; 32]);
+    let locked = LockedKeyPair::from_seed(&mut seed).unwrap();
+    assert_eq!(*seed, [0u8; Seed::BYTES]);
+    assert_eq!(locked.pk, kp.pk);
+    assert_eq!(locked.sk, kp.sk);
+    let signature = locked.sk.sign(b"message", None);
+    assert_eq!(signature, kp.sk.sign(b"message", None));
+    kp.pk.verify(b"message", &signature).unwrap();
+
+    let locked = LockedKeyPair::new(kp.clone()).unwrap();
+    assert_eq!(*locked, kp);
+    assert!(format!("{:?}", locked).starts_with("LockedKeyPair { pk: "));
+}
@@ -0,0 +1,624 @@
+//! Support for minisign public keys, secret keys and signatures.
+//!
+//! Both legacy (`Ed`) signatures and prehashed (`ED`) signatures are
+//! supported, as well as the global signature over the trusted comment.
+//! Parsing, verification and signing don't require allocations; encoding
+//! secret keys requires the `std` feature.
+
+use core::fmt;
+
+use ct_codecs::{Base64, Decoder, Encoder};
+
+use super::blake2b;
+use super::common::*;
+use super::error::Error;
+use super::{KeyPair, PublicKey as EdPublicKey, Signature, VerifyingState};
+
+const SIGALG: [u8; 2] = *b"Ed";
+const SIGALG_PREHASHED: [u8; 2] = *b"ED";
+const KDFALG_NONE: [u8; 2] = [0, 0];
+const KDFALG_SCRYPT: [u8; 2] = *b"Sc";
+const CHKALG: [u8; 2] = *b"B2";
+
+const COMMENT_PREFIX: &str = "untrusted comment: ";
+const TRUSTED_COMMENT_PREFIX: &str = "trusted comment: ";
+const DEFAULT_SIG_COMMENT: &str = "signature from minisign secret key";
+
+/// Number of bytes in a key identifier.
+pub const KEYNUM_BYTES: usize = 8;
+
+/// Default scrypt `opslimit` used to encrypt secret keys.
+pub const OPSLIMIT: u64 = 33554432;
+/// Default scrypt `memlimit` used to encrypt secret keys.
+pub const MEMLIMIT: u64 = 1073741824;
+
+const PK_BYTES: usize = 2 + KEYNUM_BYTES + EdPublicKey::BYTES;
+const SIG_BYTES: usize = 2 + KEYNUM_BYTES + Signature::BYTES;
+const KEYNUM_SK_BYTES: usize = KEYNUM_BYTES + 64 + 32;
+const SK_BYTES: usize = 2 + 2 + 2 + 32 + 8 + 8 + KEYNUM_SK_BYTES;
+
+/// Splits a file into lines, ignoring carriage returns.
+fn lines(text: &str) -> impl Iterator<Item = &str> {
+    text.split('\n').map(|line| line.trim_end_matches('\r'))
+}
+
+/// Returns the untrusted comment and the base64-encoded payload of a file.
+fn comment_and_payload(text: &str) -> Result<(&str, &str), Error> {
+    let mut it = lines(text);
+    let comment = it
+        .next()
+        .and_then(|line| line.strip_prefix(COMMENT_PREFIX))
+        .ok_or(Error::ParseError)?;
+    let payload = it.next().ok_or(Error::ParseError)?;
+    if it.any(|line| !line.is_empty()) {
+        return Err(Error::ParseError);
+    }
+    Ok((comment, payload))
+}
+
+fn decode_base64<const N: usize>(b64: &str) -> Result<[u8; N], Error> {
+    let mut bin = [0u8; N];
+    let len = Base64::decode(&mut bin, b64.trim(), None)
+        .map_err(|_| Error::ParseError)?
+        .len();
+    if len != N {
+        return Err(Error::ParseError);
+    }
+    Ok(bin)
+}
+
+/// Writes `bin` as base64 to a formatter.
+fn write_base64(f: &mut fmt::Formatter<'_>, bin: &[u8]) -> fmt::Result {
+    let mut b64 = [0u8; 128];
+    let b64 = Base64::encode(&mut b64, bin).map_err(|_| fmt::Error)?;
+    f.write_str(core::str::from_utf8(b64).map_err(|_| fmt::Error)?)
+}
+
+fn blake2b_prehash(message: &[u8]) -> [u8; 64] {
+    blake2b::Hash::hash(message)
+}
+
+/// A minisign public key.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct PublicKey {
+    keynum: [u8; KEYNUM_BYTES],
+    pk: EdPublicKey,
+}
+
+impl PublicKey {
+    /// Creates a minisign public key from an Ed25519 public key and a key
+    /// identifier.
+    pub fn new(pk: EdPublicKey, keynum: [u8; KEYNUM_BYTES]) -> Self {
+        PublicKey { keynum, pk }
+    }
+
+    /// Returns the key identifier.
+    pub fn keynum(&self) -> &[u8; KEYNUM_BYTES] {
+        &self.keynum
+    }
+
+    /// Returns the Ed25519 public key.
+    pub fn key(&self) -> &EdPublicKey {
+        &self.pk
+    }
+
+    /// Imports a public key from its base64 representation, as found on
+    /// the second line of a public key file.
+    pub fn from_base64(b64: &str) -> Result<Self, Error> {
+        let bin: [u8; PK_BYTES] = decode_base64(b64)?;
+        if bin[0..2] != SIGALG {
+            return Err(Error::InvalidPublicKey);
+        }
+        let mut keynum = [0u8; KEYNUM_BYTES];
+        keynum.copy_from_slice(&bin[2..10]);
+        let pk = EdPublicKey::from_slice(&bin[10..])?;
+        Ok(PublicKey { keynum, pk })
+    }
+
+    /// Imports a public key from the content of a public key file.
+    /// A bare base64-encoded key is also accepted.
+    pub fn decode(text: &str) -> Result<Self, Error> {
+        if !text.starts_with(COMMENT_PREFIX) {
+            return Self::from_base64(text);
+        }
+        let (_comment, b64) = comment_and_payload(text)?;
+        Self::from_base64(b64)
+    }
+
+    /// Verifies a signature and its trusted comment.
+    pub fn verify(&self, message: impl AsRef<[u8]>, sig: &SignatureBox<'_>) -> Result<(), Error> {
+        let mut st = self.verify_incremental(sig)?;
+        st.absorb(message);
+        st.verify()
+    }
+
+    /// Verifies a signature of a multi-part message (streaming).
+    /// The trusted comment is verified immediately.
+    pub fn verify_incremental(&self, sig: &SignatureBox<'_>) -> Result<StreamVerifier, Error> {
+        if sig.keynum != self.keynum {
+            return Err(Error::InvalidPublicKey);
+        }
+        let mut st = self.pk.verify_incremental(&sig.global_signature)?;
+        st.absorb(&sig.signature[..]);
+        st.absorb(sig.trusted_comment.as_bytes());
+        st.verify()?;
+        let inner = if sig.prehashed {
+            StreamVerifierInner::Prehashed {
+                hasher: blake2b::Hash::new(64),
+                pk: self.pk,
+                signature: sig.signature,
+            }
+        } else {
+            StreamVerifierInner::Legacy(self.pk.verify_incremental(&sig.signature)?)
+        };
+        Ok(StreamVerifier { inner })
+    }
+}
+
+impl fmt::Display for PublicKey {
+    /// Formats the public key as the content of a minisign public key file.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut bin = [0u8; PK_BYTES];
+        bin[0..2].copy_from_slice(&SIGALG);
+        bin[2..10].copy_from_slice(&self.keynum);
+        bin[10..].copy_from_slice(&self.pk[..]);
+        writeln!(
+            f,
+            "{}minisign public key {:016X}",
+            COMMENT_PREFIX,
+            u64::from_le_bytes(self.keynum)
+        )?;
+        write_base64(f, &bin)?;
+        writeln!(f)
+    }
+}
+
+#[derive(Clone)]
+enum StreamVerifierInner {
+    Legacy(VerifyingState),
+    Prehashed {
+        hasher: blake2b::Hash,
+        pk: EdPublicKey,
+        signature: Signature,
+    },
+}
+
+/// The state of a streaming minisign verification operation.
+#[derive(Clone)]
+pub struct StreamVerifier {
+    inner: StreamVerifierInner,
+}
+
+impl StreamVerifier {
+    /// Appends data to the message being verified.
+    pub fn absorb(&mut self, chunk: impl AsRef<[u8]>) {
+        match &mut self.inner {
+            StreamVerifierInner::Legacy(st) => st.absorb(chunk),
+            StreamVerifierInner::Prehashed { hasher, .. } => hasher.update(chunk),
+        }
+    }
+
+    /// Verifies the signature.
+    pub fn verify(&self) -> Result<(), Error> {
+        match &self.inner {
+            StreamVerifierInner::Legacy(st) => st.verify(),
+            StreamVerifierInner::Prehashed {
+                hasher,
+                pk,
+                signature,
+            } => pk.verify(hasher.finalize::<64>(), signature),
+        }
+    }
+}
+
+/// A minisign signature, along with its comments.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SignatureBox<'a> {
+    untrusted_comment: &'a str,
+    prehashed: bool,
+    keynum: [u8; KEYNUM_BYTES],
+    signature: Signature,
+    trusted_comment: &'a str,
+    global_signature: Signature,
+}
+
+impl<'a> SignatureBox<'a> {
+    /// Parses the content of a `.minisig` file.
+    pub fn decode(text: &'a str) -> Result<Self, Error> {
+        let mut it = lines(text);
+        let untrusted_comment = it
+            .next()
+            .and_then(|line| line.strip_prefix(COMMENT_PREFIX))
+            .ok_or(Error::ParseError)?;
+        let bin: [u8; SIG_BYTES] = decode_base64(it.next().ok_or(Error::ParseError)?)?;
+        let trusted_comment = it
+            .next()
+            .and_then(|line| line.strip_prefix(TRUSTED_COMMENT_PREFIX))
+            .ok_or(Error::ParseError)?;
+        let global_signature: [u8; Signature::BYTES] =
+            decode_base64(it.next().ok_or(Error::ParseError)?)?;
+        if it.any(|line| !line.is_empty()) {
+            return Err(Error::ParseError);
+        }
+        let prehashed = match [bin[0], bin[1]] {
+            SIGALG => false,
+            SIGALG_PREHASHED => true,
+            _ => return Err(Error::InvalidSignature),
+        };
+        let mut keynum = [0u8; KEYNUM_BYTES];
+        keynum.copy_from_slice(&bin[2..10]);
+        Ok(SignatureBox {
+            untrusted_comment,
+            prehashed,
+            keynum,
+            signature: Signature::from_slice(&bin[10..])?,
+            trusted_comment,
+            global_signature: Signature::new(global_signature),
+        })
+    }
+
+    /// Returns the untrusted comment.
+    pub fn untrusted_comment(&self) -> &'a str {
+        self.untrusted_comment
+    }
+
+    /// Returns the trusted comment.
+    /// It is only authenticated after the signature has been verified.
+    pub fn trusted_comment(&self) -> &'a str {
+        self.trusted_comment
+    }
+
+    /// Returns `true` if the message was prehashed before being signed.
+    pub fn is_prehashed(&self) -> bool {
+        self.prehashed
+    }
+
+    /// Returns the identifier of the key that created the signature.
+    pub fn keynum(&self) -> &[u8; KEYNUM_BYTES] {
+        &self.keynum
+    }
+}
+
+impl fmt::Display for SignatureBox<'_> {
+    /// Formats the signature as the content of a `.minisig` file.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut bin = [0u8; SIG_BYTES];
+        bin[0..2].copy_from_slice(if self.prehashed {
+            &SIGALG_PREHASHED
+        } else {
+            &SIGALG
+        });
+        bin[2..10].copy_from_slice(&self.keynum);
+        bin[10..].copy_from_slice(&self.signature[..]);
+        writeln!(f, "{}{}", COMMENT_PREFIX, self.untrusted_comment)?;
+        write_base64(f, &bin)?;
+        writeln!(f)?;
+        writeln!(f, "{}{}", TRUSTED_COMMENT_PREFIX, self.trusted_comment)?;
+        write_base64(f, &self.global_signature[..])?;
+        writeln!(f)
+    }
+}
+
+/// Parameters used to encrypt a secret key.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct KdfParams {
+    /// Salt for the key derivation function.
+    pub salt: [u8; 32],
+    /// Computational cost, as defined by libsodium.
+    pub opslimit: u64,
+    /// Memory cost, in bytes, as defined by libsodium.
+    pub memlimit: u64,
+}
+
+impl KdfParams {
+    /// Creates key derivation parameters.
+    pub fn new(salt: [u8; 32], opslimit: u64, memlimit: u64) -> Self {
+        KdfParams {
+            salt,
+            opslimit,
+            memlimit,
+        }
+    }
+}
+
+#[cfg(feature = "random")]
+impl Default for KdfParams {
+    /// Generates a random salt, and uses the default cost parameters.
+    fn default() -> Self {
+        let mut salt = [0u8; 32];
+        getrandom::fill(&mut salt).expect("RNG failure");
+        KdfParams::new(salt, OPSLIMIT, MEMLIMIT)
+    }
+}
+
+/// A minisign secret key.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SecretKey {
+    keynum: [u8; KEYNUM_BYTES],
+    kp: KeyPair,
+}
+
+impl SecretKey {
+    /// Creates a minisign secret key from an Ed25519 key pair and a key
+    /// identifier.
+    pub fn new(kp: KeyPair, keynum: [u8; KEYNUM_BYTES]) -> Self {
+        SecretKey { keynum, kp }
+    }
+
+    /// Generates a new secret key with a random key identifier.
+    #[cfg(feature = "random")]
+    pub fn generate() -> Self {
+        let mut keynum = [0u8; KEYNUM_BYTES];
+        getrandom::fill(&mut keynum).expect("RNG failure");
+        SecretKey::new(KeyPair::generate(), keynum)
+    }
+
+    /// Returns the key identifier.
+    pub fn keynum(&self) -> &[u8; KEYNUM_BYTES] {
+        &self.keynum
+    }
+
+    /// Returns the Ed25519 key pair.
+    pub fn key_pair(&self) -> &KeyPair {
+        &self.kp
+    }
+
+    /// Returns the public counterpart of the secret key.
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey::new(self.kp.pk, self.keynum)
+    }
+
+    fn checksum(&self) -> [u8; 32] {
+        let mut h = blake2b::Hash::new(32);
+        h.update(SIGALG);
+        h.update(self.keynum);
+        h.update(&self.kp.sk[..]);
+        h.finalize()
+    }
+
+    /// Imports a secret key from the content of a secret key file.
+    ///
+    /// A password is required if the key is encrypted, and is ignored
+    /// otherwise. Encrypted keys can only be decoded with the `std`
+    /// feature.
+    pub fn decode(text: &str, password: Option<&str>) -> Result<Self, Error> {
+        let (_comment, b64) = comment_and_payload(text)?;
+        let mut bin: [u8; SK_BYTES] = decode_base64(b64)?;
+        if bin[0..2] != SIGALG || bin[4..6] != CHKALG {
+            return Err(Error::InvalidSecretKey);
+        }
+        let res = Self::decode_bin(&mut bin, password);
+        Mem::wipe(&mut bin);
+        res
+    }
+
+    fn decode_bin(bin: &mut [u8; SK_BYTES], password: Option<&str>) -> Result<Self, Error> {
+        let kdfalg = [bin[2], bin[3]];
+        let (params, keynum_sk) = bin[6..].split_at_mut(48);
+        match kdfalg {
+            KDFALG_NONE => {}
+            KDFALG_SCRYPT => {
+                let password = password.ok_or(Error::InvalidSecretKey)?;
+                let mut u64_le = [0u8; 8];
+                u64_le.copy_from_slice(&params[32..40]);
+                let opslimit = u64::from_le_bytes(u64_le);
+                u64_le.copy_from_slice(&params[40..48]);
+                let memlimit = u64::from_le_bytes(u64_le);
+                let mut salt = [0u8; 32];
+                salt.copy_from_slice(&params[0..32]);
+                let kdf = KdfParams::new(salt, opslimit, memlimit);
+                xor_stream(keynum_sk, password, &kdf)?;
+            }
+            _ => return Err(Error::InvalidParameters),
+        }
+        let mut keynum = [0u8; KEYNUM_BYTES];
+        keynum.copy_from_slice(&keynum_sk[0..8]);
+        let sk = SecretKey {
+            keynum,
+            kp: KeyPair::from_slice(&keynum_sk[8..72])?,
+        };
+        if !Mem::ct_eq(&sk.checksum(), &keynum_sk[72..104]) {
+            return Err(Error::InvalidSecretKey);
+        }
+        sk.kp.validate().map_err(|_| Error::InvalidSecretKey)?;
+        Ok(sk)
+    }
+
+    /// Computes a prehashed signature of `message`, as done by default by
+    /// minisign.
+    ///
+    /// `trusted_comment` is signed along with the signature;
+    /// `untrusted_comment` is not. A default value is used if the latter is
+    /// `None`.
+    pub fn sign<'a>(
+        &self,
+        message: impl AsRef<[u8]>,
+        trusted_comment: &'a str,
+        untrusted_comment: Option<&'a str>,
+    ) -> SignatureBox<'a> {
+        let signature = self.kp.sk.sign(blake2b_prehash(message.as_ref()), None);
+        self.signature_box(signature, true, trusted_comment, untrusted_comment)
+    }
+
+    /// Computes a legacy, non-prehashed signature of `message`.
+    pub fn sign_legacy<'a>(
+        &self,
+        message: impl AsRef<[u8]>,
+        trusted_comment: &'a str,
+        untrusted_comment: Option<&'a str>,
+    ) -> SignatureBox<'a> {
+        let signature = self.kp.sk.sign(message, None);
+        self.signature_box(signature, false, trusted_comment, untrusted_comment)
+    }
+
+    fn signature_box<'a>(
+        &self,
+        signature: Signature,
+        prehashed: bool,
+        trusted_comment: &'a str,
+        untrusted_comment: Option<&'a str>,
+    ) -> SignatureBox<'a> {
+        let global_signature = self
+            .kp
+            .sk
+            .sign_chunks(&[&signature[..], trusted_comment.as_bytes()], None);
+        SignatureBox {
+            untrusted_comment: untrusted_comment.unwrap_or(DEFAULT_SIG_COMMENT),
+            prehashed,
+            keynum: self.keynum,
+            signature,
+            trusted_comment,
+            global_signature,
+        }
+    }
+
+    #[cfg(feature = "std")]
+    fn encode_bin(&self, kdfalg: [u8; 2], kdf: Option<&KdfParams>) -> [u8; SK_BYTES] {
+        let mut bin = [0u8; SK_BYTES];
+        bin[0..2].copy_from_slice(&SIGALG);
+        bin[2..4].copy_from_slice(&kdfalg);
+        bin[4..6].copy_from_slice(&CHKALG);
+        if let Some(kdf) = kdf {
+            bin[6..38].copy_from_slice(&kdf.salt);
+            bin[38..46].copy_from_slice(&kdf.opslimit.to_le_bytes());
+            bin[46..54].copy_from_slice(&kdf.memlimit.to_le_bytes());
+        }
+        bin[54..62].copy_from_slice(&self.keynum);
+        bin[62..126].copy_from_slice(&self.kp.sk[..]);
+        bin[126..158].copy_from_slice(&self.checksum());
+        bin
+    }
+
+    #[cfg(feature = "std")]
+    fn encode_file(comment: &str, bin: &mut [u8; SK_BYTES]) -> String {
+        let b64 = Base64::encode_to_string(&bin[..]).unwrap();
+        Mem::wipe(bin);
+        format!("{}{}\n{}\n", COMMENT_PREFIX, comment, b64)
+    }
+
+    /// Exports the secret key as an unencrypted secret key file.
+    #[cfg(feature = "std")]
+    pub fn encode(&self) -> String {
+        let mut bin = self.encode_bin(KDFALG_NONE, None);
+        Self::encode_file("minisign secret key", &mut bin)
+    }
+
+    /// Exports the secret key as a secret key file, encrypted with a
+    /// password.
+    #[cfg(feature = "std")]
+    pub fn encode_encrypted(&self, password: &str, kdf: &KdfParams) -> Result<String, Error> {
+        let mut bin = self.encode_bin(KDFALG_SCRYPT, Some(kdf));
+        xor_stream(&mut bin[54..], password, kdf)?;
+        Ok(Self::encode_file("minisign encrypted secret key", &mut bin))
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for SecretKey {
+    fn zeroize(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.kp)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for SecretKey {}
+
+/// Encrypts or decrypts the key material of a secret key.
+#[cfg(feature = "std")]
+fn xor_stream(keynum_sk: &mut [u8], password: &str, kdf: &KdfParams) -> Result<(), Error> {
+    use super::scrypt;
+
+    let (n_log2, r, p) = scrypt::pick_params(kdf.opslimit, kdf.memlimit);
+    let mut stream = [0u8; KEYNUM_SK_BYTES];
+    scrypt::scrypt(password.as_bytes(), &kdf.salt, n_log2, r, p, &mut stream)?;
+    for (x, s) in keynum_sk.iter_mut().zip(stream.iter()) {
+        *x ^= s;
+    }
+    Mem::wipe(&mut stream);
+    Ok(())
+}
+
+/// Encrypted keys require scrypt, which is only available with `std`.
+#[cfg(not(feature = "std"))]
+fn xor_stream(_keynum_sk: &mut [u8], _password: &str, _kdf: &KdfParams) -> Result<(), Error> {
+    Err(Error::InvalidParameters)
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_minisign() {
+    let pk = PublicKey::decode(
+        "untrusted comment: minisign public key: 24F1510BFAF3FD5A
+RWRa/fP6C1HxJPAj9yOU45sNZxwz6AFur/8GA2/8n+mvYwY4kQfCXZrd
+",
+    )
+    .unwrap();
+    let sig_text = "untrusted comment: untrusted
+RURa/fP6C1HxJB7atxvkmaB6bC7WLpaiPgfL/0bNnPHvfxJiLM7bzmfc6DHxY+exThqBi5+Zo5HLj6tAp0xP1XmmodKZUeoBOA0=
+trusted comment: trusted
+IxeGbS2A07yZdbr7VBcKPg6J0Tk1W7leEq6ipD33qZEXE56klinsvsGeTcIKixZRVdmc75j5Qbd70KT3KXzQAg==
+";
+    let sig = SignatureBox::decode(sig_text).unwrap();
+    assert!(sig.is_prehashed());
+    assert_eq!(sig.untrusted_comment(), "untrusted");
+    assert_eq!(sig.trusted_comment(), "trusted");
+    assert!(pk.verify(b"test", &sig).is_ok());
+    assert!(pk.verify(b"Test", &sig).is_err());
+    assert_eq!(sig.to_string(), sig_text);
+
+    let forged = sig_text.replace("trusted comment: trusted", "trusted comment: trusteD");
+    let forged = SignatureBox::decode(&forged).unwrap();
+    assert!(pk.verify(b"test", &forged).is_err());
+
+    let sk = SecretKey::new(
+        KeyPair::from_seed(crate::Seed::new([7u8; 32])),
+        [1, 2, 3, 4, 5, 6, 7, 8],
+    );
+    let pk = sk.public_key();
+    assert_eq!(PublicKey::decode(&pk.to_string()).unwrap(), pk);
+    assert!(pk
+        .to_string()
+        .contains("minisign public key 0807060504030201"));
+
+    let sig = sk.sign(b"message", "tc", None);
+    let sig_text = sig.to_string();
+    let sig = SignatureBox::decode(&sig_text).unwrap();
+    assert!(pk.verify(b"message", &sig).is_ok());
+    let mut st = pk.verify_incremental(&sig).unwrap();
+    st.absorb(b"mess");
+    st.absorb(b"age");
+    assert!(st.verify().is_ok());
+
+    let sig = sk.sign_legacy(b"message", "tc", Some("legacy"));
+    let sig_text = sig.to_string();
+    let sig = SignatureBox::decode(&sig_text).unwrap();
+    assert!(!sig.is_prehashed());
+    assert!(pk.verify(b"message", &sig).is_ok());
+    assert!(pk.verify(b"massage", &sig).is_err());
+
+    let other = SecretKey::new(sk.key_pair().clone(), [0u8; 8]).public_key();
+    assert_eq!(other.verify(b"message", &sig), Err(Error::InvalidPublicKey));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_minisign_secret_key() {
+    let sk = SecretKey::new(
+        KeyPair::from_seed(crate::Seed::new([7u8; 32])),
+        [1, 2, 3, 4, 5, 6, 7, 8],
+    );
+    assert_eq!(SecretKey::decode(&sk.encode(), None).unwrap(), sk);
+
+    let encrypted = "untrusted comment: minisign encrypted secret key\nRWRTY0IyCQkJCQkJCQkJCQkJCQkJCQkJCQkJCQkJCQkJCQkJCQkAgAAAAAAAAAAAAAEAAAAADNVk83tp7dgKo/X8gqqk1NA+qA9utSZzi9JXVcgoZ6dqOTHl2VIo1b5LZ6G0f7BupIwJeQjNSvv53CC/JwttdUe19ewer6Mcj1UVPTbMHM+/kVQWTnF9QmHobknME66agiKxWHDj4jc=\n";
+    let kdf = KdfParams::new([9u8; 32], 32768, 16777216);
+    assert_eq!(sk.encode_encrypted("pw", &kdf).unwrap(), encrypted);
+    assert_eq!(SecretKey::decode(encrypted, Some("pw")).unwrap(), sk);
+    assert_eq!(
+        SecretKey::decode(encrypted, Some("wrong")),
+        Err(Error::InvalidSecretKey)
+    );
+    assert_eq!(
+        SecretKey::decode(encrypted, None),
+        Err(Error::InvalidSecretKey)
+    );
+}
@@ -0,0 +1,163 @@
+//! A small Poly1305 implementation, based on poly1305-donna with 64-bit limbs.
+
+use super::common::*;
+
+pub const KEY_BYTES: usize = 32;
+pub const TAG_BYTES: usize = 16;
+
+fn load64_le(s: &[u8]) -> u64 {
+    let mut x = [0u8; 8];
+    x.copy_from_slice(&s[..8]);
+    u64::from_le_bytes(x)
+}
+
+pub struct Poly1305 {
+    r: [u64; 3],
+    h: [u64; 3],
+    pad: [u64; 2],
+    buf: [u8; 16],
+    buf_len: usize,
+}
+
+const MASK44: u64 = 0xfffffffffff;
+const MASK42: u64 = 0x3ffffffffff;
+
+impl Poly1305 {
+    pub fn new(key: &[u8; KEY_BYTES]) -> Self {
+        let t0 = load64_le(&key[0..]);
+        let t1 = load64_le(&key[8..]);
+        let r = [
+            t0 & 0xffc0fffffff,
+            ((t0 >> 44) | (t1 << 20)) & 0xfffffc0ffff,
+            (t1 >> 24) & 0x00ffffffc0f,
+        ];
+        let pad = [load64_le(&key[16..]), load64_le(&key[24..])];
+        Poly1305 {
+            r,
+            h: [0; 3],
+            pad,
+            buf: [0; 16],
+            buf_len: 0,
+        }
+    }
+
+    fn block(&mut self, m: &[u8], hibit: u64) {
+        let [r0, r1, r2] = self.r;
+        let (s1, s2) = (r1 * (5 << 2), r2 * (5 << 2));
+        let [mut h0, mut h1, mut h2] = self.h;
+        let t0 = load64_le(&m[0..]);
+        let t1 = load64_le(&m[8..]);
+        h0 += t0 & MASK44;
+        h1 += ((t0 >> 44) | (t1 << 20)) & MASK44;
+        h2 += ((t1 >> 24) & MASK42) | hibit;
+
+        let m = |a: u64, b: u64| (a as u128) * (b as u128);
+        let d0 = m(h0, r0) + m(h1, s2) + m(h2, s1);
+        let mut d1 = m(h0, r1) + m(h1, r0) + m(h2, s2);
+        let mut d2 = m(h0, r2) + m(h1, r1) + m(h2, r0);
+
+        let mut c = (d0 >> 44) as u64;
+        h0 = d0 as u64 & MASK44;
+        d1 += c as u128;
+        c = (d1 >> 44) as u64;
+        h1 = d1 as u64 & MASK44;
+        d2 += c as u128;
+        c = (d2 >> 42) as u64;
+        h2 = d2 as u64 & MASK42;
+        h0 += c * 5;
+        c = h0 >> 44;
+        h0 &= MASK44;
+        h1 += c;
+        self.h = [h0, h1, h2];
+    }
+
+    pub fn update(&mut self, mut data: &[u8]) {
+        if self.buf_len > 0 {
+            let n = ::core::cmp::min(16 - self.buf_len, data.len());
+            self.buf[self.buf_len..][..n].copy_from_slice(&data[..n]);
+            self.buf_len += n;
+            data = &data[n..];
+            if self.buf_len < 16 {
+                return;
+            }
+            let buf = self.buf;
+            self.block(&buf, 1 << 40);
+            self.buf_len = 0;
+        }
+        while data.len() >= 16 {
+            self.block(&data[..16], 1 << 40);
+            data = &data[16..];
+        }
+        self.buf[..data.len()].copy_from_slice(data);
+        self.buf_len = data.len();
+    }
+
+    pub fn finalize(mut self) -> [u8; TAG_BYTES] {
+        if self.buf_len > 0 {
+            let mut buf = [0u8; 16];
+            buf[..self.buf_len].copy_from_slice(&self.buf[..self.buf_len]);
+            buf[self.buf_len] = 1;
+            self.block(&buf, 0);
+        }
+        let [mut h0, mut h1, mut h2] = self.h;
+        let mut c = h1 >> 44;
+        h1 &= MASK44;
+        h2 += c;
+        c = h2 >> 42;
+        h2 &= MASK42;
+        h0 += c * 5;
+        c = h0 >> 44;
+        h0 &= MASK44;
+        h1 += c;
+        c = h1 >> 44;
+        h1 &= MASK44;
+        h2 += c;
+        c = h2 >> 42;
+        h2 &= MASK42;
+        h0 += c * 5;
+        c = h0 >> 44;
+        h0 &= MASK44;
+        h1 += c;
+
+        // Compute h - p, and select it if it didn't underflow.
+        let mut g0 = h0 + 5;
+        c = g0 >> 44;
+        g0 &= MASK44;
+        let mut g1 = h1 + c;
+        c = g1 >> 44;
+        g1 &= MASK44;
+        let mut g2 = (h2 + c).wrapping_sub(1 << 42);
+        c = (g2 >> 63).wrapping_sub(1);
+        g0 &= c;
+        g1 &= c;
+        g2 &= c;
+        c = !c;
+        h0 = (h0 & c) | g0;
+        h1 = (h1 & c) | g1;
+        h2 = (h2 & c) | g2;
+
+        let [t0, t1] = self.pad;
+        h0 += t0 & MASK44;
+        c = h0 >> 44;
+        h0 &= MASK44;
+        h1 += (((t0 >> 44) | (t1 << 20)) & MASK44) + c;
+        c = h1 >> 44;
+        h1 &= MASK44;
+        h2 += ((t1 >> 24) & MASK42) + c;
+        h2 &= MASK42;
+
+        let mut tag = [0u8; TAG_BYTES];
+        tag[0..8].copy_from_slice(&(h0 | (h1 << 44)).to_le_bytes());
+        tag[8..16].copy_from_slice(&((h1 >> 20) | (h2 << 24)).to_le_bytes());
+        tag
+    }
+}
+
+impl Drop for Poly1305 {
+    fn drop(&mut self) {
+        Mem::wipe(&mut self.r);
+        Mem::wipe(&mut self.h);
+        Mem::wipe(&mut self.pad);
+        Mem::wipe(&mut self.buf);
+    }
+}
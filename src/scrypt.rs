@@ -0,0 +1,189 @@
+//! A small scrypt (RFC 7914) implementation, along with the parameter
+//! selection used by libsodium's `crypto_pwhash_scryptsalsa208sha256`.
+
+use super::common::*;
+use super::error::Error;
+use super::sha256::Hmac;
+
+/// PBKDF2-HMAC-SHA256 with a single iteration of any length
+pub fn pbkdf2_sha256(password: &[u8], salt: &[u8], out: &mut [u8]) {
+    let prf = Hmac::new(password);
+    for (i, chunk) in out.chunks_mut(32).enumerate() {
+        let mut h = prf.clone();
+        h.update(salt);
+        h.update((i as u32 + 1).to_be_bytes());
+        let t = h.finalize();
+        chunk.copy_from_slice(&t[..chunk.len()]);
+    }
+}
+
+fn salsa20_8(b: &mut [u32; 16]) {
+    let mut x = *b;
+    for _ in 0..4 {
+        x[4] ^= x[0].wrapping_add(x[12]).rotate_left(7);
+        x[8] ^= x[4].wrapping_add(x[0]).rotate_left(9);
+        x[12] ^= x[8].wrapping_add(x[4]).rotate_left(13);
+        x[0] ^= x[12].wrapping_add(x[8]).rotate_left(18);
+        x[9] ^= x[5].wrapping_add(x[1]).rotate_left(7);
+        x[13] ^= x[9].wrapping_add(x[5]).rotate_left(9);
+        x[1] ^= x[13].wrapping_add(x[9]).rotate_left(13);
+        x[5] ^= x[1].wrapping_add(x[13]).rotate_left(18);
+        x[14] ^= x[10].wrapping_add(x[6]).rotate_left(7);
+        x[2] ^= x[14].wrapping_add(x[10]).rotate_left(9);
+        x[6] ^= x[2].wrapping_add(x[14]).rotate_left(13);
+        x[10] ^= x[6].wrapping_add(x[2]).rotate_left(18);
+        x[3] ^= x[15].wrapping_add(x[11]).rotate_left(7);
+        x[7] ^= x[3].wrapping_add(x[15]).rotate_left(9);
+        x[11] ^= x[7].wrapping_add(x[3]).rotate_left(13);
+        x[15] ^= x[11].wrapping_add(x[7]).rotate_left(18);
+        x[1] ^= x[0].wrapping_add(x[3]).rotate_left(7);
+        x[2] ^= x[1].wrapping_add(x[0]).rotate_left(9);
+        x[3] ^= x[2].wrapping_add(x[1]).rotate_left(13);
+        x[0] ^= x[3].wrapping_add(x[2]).rotate_left(18);
+        x[6] ^= x[5].wrapping_add(x[4]).rotate_left(7);
+        x[7] ^= x[6].wrapping_add(x[5]).rotate_left(9);
+        x[4] ^= x[7].wrapping_add(x[6]).rotate_left(13);
+        x[5] ^= x[4].wrapping_add(x[7]).rotate_left(18);
+        x[11] ^= x[10].wrapping_add(x[9]).rotate_left(7);
+        x[8] ^= x[11].wrapping_add(x[10]).rotate_left(9);
+        x[9] ^= x[8].wrapping_add(x[11]).rotate_left(13);
+        x[10] ^= x[9].wrapping_add(x[8]).rotate_left(18);
+        x[12] ^= x[15].wrapping_add(x[14]).rotate_left(7);
+        x[13] ^= x[12].wrapping_add(x[15]).rotate_left(9);
+        x[14] ^= x[13].wrapping_add(x[12]).rotate_left(13);
+        x[15] ^= x[14].wrapping_add(x[13]).rotate_left(18);
+    }
+    for (b, x) in b.iter_mut().zip(x.iter()) {
+        *b = b.wrapping_add(*x);
+    }
+}
+
+fn block_mix(b: &[u32], y: &mut [u32], r: usize) {
+    let mut x = [0u32; 16];
+    x.copy_from_slice(&b[(2 * r - 1) * 16..]);
+    for i in 0..2 * r {
+        for (x, b) in x.iter_mut().zip(b[i * 16..][..16].iter()) {
+            *x ^= b;
+        }
+        salsa20_8(&mut x);
+        // Even blocks go to the first half of the output, odd ones to the second.
+        let j = (i / 2) + (i & 1) * r;
+        y[j * 16..][..16].copy_from_slice(&x);
+    }
+}
+
+fn ro_mix(b: &mut [u32], v: &mut [u32], n: usize, r: usize) {
+    let words = 32 * r;
+    let mut y = vec![0u32; words];
+    for i in 0..n {
+        v[i * words..][..words].copy_from_slice(b);
+        block_mix(b, &mut y, r);
+        b.copy_from_slice(&y);
+    }
+    for _ in 0..n {
+        let j = (b[(2 * r - 1) * 16] as usize) & (n - 1);
+        for (b, v) in b.iter_mut().zip(v[j * words..][..words].iter()) {
+            *b ^= v;
+        }
+        block_mix(b, &mut y, r);
+        b.copy_from_slice(&y);
+    }
+    Mem::wipe(&mut y);
+}
+
+/// Upper bound on the size of each of the `V` and `B` buffers of a single
+/// scrypt invocation (1 GiB), matching libsodium's sensitive `memlimit`.
+/// Parameters come from key files, which may be untrusted.
+const MAX_MEM_WORDS: u64 = 1 << 28;
+
+/// Computes scrypt(`password`, `salt`, 2^`n_log2`, `r`, `p`) into `out`.
+pub fn scrypt(
+    password: &[u8],
+    salt: &[u8],
+    n_log2: u32,
+    r: usize,
+    p: usize,
+    out: &mut [u8],
+) -> Result<(), Error> {
+    if n_log2 == 0 || n_log2 >= 63 || r == 0 || p == 0 {
+        return Err(Error::InvalidParameters);
+    }
+    let n = 1usize.checked_shl(n_log2).ok_or(Error::InvalidParameters)?;
+    let words = r.checked_mul(32).ok_or(Error::InvalidParameters)?;
+    let b_len = words.checked_mul(p).ok_or(Error::InvalidParameters)?;
+    let v_len = words.checked_mul(n).ok_or(Error::InvalidParameters)?;
+    // `B` is stored both as bytes and as words.
+    if v_len as u64 > MAX_MEM_WORDS || b_len as u64 > MAX_MEM_WORDS / 2 {
+        return Err(Error::InvalidParameters);
+    }
+    let b_bytes_len = b_len.checked_mul(4).ok_or(Error::InvalidParameters)?;
+    let mut b_bytes = vec![0u8; b_bytes_len];
+    pbkdf2_sha256(password, salt, &mut b_bytes);
+    let mut b: Vec<u32> = b_bytes
+        .chunks(4)
+        .map(|x| u32::from_le_bytes([x[0], x[1], x[2], x[3]]))
+        .collect();
+    let mut v = vec![0u32; v_len];
+    for chunk in b.chunks_mut(words) {
+        ro_mix(chunk, &mut v, n, r);
+    }
+    for (bytes, x) in b_bytes.chunks_mut(4).zip(b.iter()) {
+        bytes.copy_from_slice(&x.to_le_bytes());
+    }
+    pbkdf2_sha256(password, &b_bytes, out);
+    Mem::wipe(&mut v);
+    Mem::wipe(&mut b);
+    Mem::wipe(&mut b_bytes);
+    Ok(())
+}
+
+/// Converts libsodium's `opslimit` and `memlimit` parameters into
+/// (`n_log2`, `r`, `p`) scrypt parameters.
+pub fn pick_params(opslimit: u64, memlimit: u64) -> (u32, usize, usize) {
+    let opslimit = ::core::cmp::max(opslimit, 32768);
+    let r: u64 = 8;
+    let cpu_bound = opslimit < memlimit / 32;
+    let max_n = if cpu_bound {
+        opslimit / (r * 4)
+    } else {
+        memlimit / (r * 128)
+    };
+    let mut n_log2 = 1;
+    while n_log2 < 63 && (1u64 << n_log2) <= max_n / 2 {
+        n_log2 += 1;
+    }
+    let p = if cpu_bound {
+        1
+    } else {
+        ::core::cmp::min((opslimit / 4) >> n_log2, 0x3fffffff) / r
+    };
+    (n_log2, r as usize, p as usize)
+}
+
+#[test]
+fn test_scrypt() {
+    use ct_codecs::{Decoder, Hex};
+
+    let mut out = [0u8; 64];
+    scrypt(b"password", b"NaCl", 10, 8, 16, &mut out).unwrap();
+    assert_eq!(
+        &out[..],
+        &Hex::decode_to_vec(
+            "fdbabe1c9d3472007856e7190d01e9fe7c6ad7cbc8237830e77376634b3731622eaf30d92e22a3886ff109279d9830dac727afb94a83ee6d8360cbdfa2cc0640",
+            None
+        )
+        .unwrap()[..]
+    );
+    assert_eq!(pick_params(33554432, 1073741824), (20, 8, 1));
+    assert_eq!(pick_params(32768, 16777216), (10, 8, 1));
+
+    assert_eq!(
+        scrypt(b"password", b"NaCl", 21, 8, 1, &mut out),
+        Err(Error::InvalidParameters)
+    );
+    let (n_log2, r, p) = pick_params(u64::MAX, 1 << 20);
+    assert_eq!(
+        scrypt(b"password", b"NaCl", n_log2, r, p, &mut out),
+        Err(Error::InvalidParameters)
+    );
+}
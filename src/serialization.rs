@@ -0,0 +1,286 @@
+//! Serde support.
+//!
+//! With human-readable formats, such as JSON, keys and signatures are
+//! serialized as hex strings. With binary formats, they are serialized as
+//! fixed-size byte arrays, without a length prefix.
+//!
+//! Types holding secrets only implement `Serialize` and `Deserialize` when
+//! wrapped in `ExposeSecret`.
+
+use core::fmt;
+
+use ct_codecs::{Decoder, Encoder, Hex};
+use serde::de::{self, Deserializer, SeqAccess, Visitor};
+use serde::ser::{self, SerializeTuple, Serializer};
+use serde::{Deserialize, Serialize};
+
+use super::common::*;
+#[cfg(all(feature = "blind-keys", not(feature = "disable-signatures")))]
+use super::ed25519::BlindPublicKey;
+#[cfg(not(feature = "disable-signatures"))]
+use super::ed25519::{KeyPair, Noise, PublicKey, SecretKey, Signature};
+use super::error::Error;
+#[cfg(feature = "x25519")]
+use super::x25519;
+
+/// Size of the largest serialized object.
+const MAX_BYTES: usize = 64;
+
+/// Wrapper to serialize and deserialize a secret.
+///
+/// Seeds, noise, secret keys and key pairs don't implement `Serialize` and
+/// `Deserialize`, so that they can't be serialized by accident, for example
+/// as a field of a larger structure. Wrapping them in `ExposeSecret` makes
+/// serializing them explicit:
+///
+/// ```rust
+/// # #[cfg(all(feature = "random", not(feature = "disable-signatures")))] {
+/// use ed25519_compact::{ExposeSecret, KeyPair};
+///
+/// let kp = KeyPair::generate();
+/// let json = serde_json::to_string(&ExposeSecret(&kp)).unwrap();
+/// let ExposeSecret(kp2): ExposeSecret<KeyPair> = serde_json::from_str(&json).unwrap();
+/// assert_eq!(kp, kp2);
+/// # }
+/// ```
+///
+/// Key pairs are serialized as their secret key, and the public key is
+/// recomputed when they are deserialized.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct ExposeSecret<T>(pub T);
+
+fn serialize_bytes<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    if serializer.is_human_readable() {
+        let mut hex = [0u8; MAX_BYTES * 2];
+        let res = match Hex::encode_to_str(&mut hex, bytes) {
+            Ok(hex) => serializer.serialize_str(hex),
+            Err(e) => Err(ser::Error::custom(e)),
+        };
+        Mem::wipe(&mut hex);
+        res
+    } else {
+        let mut tuple = serializer.serialize_tuple(bytes.len())?;
+        for b in bytes {
+            tuple.serialize_element(b)?;
+        }
+        tuple.end()
+    }
+}
+
+fn deserialize_bytes<'de, D: Deserializer<'de>>(
+    deserializer: D,
+    out: &mut [u8],
+) -> Result<(), D::Error> {
+    if deserializer.is_human_readable() {
+        deserializer.deserialize_str(BytesVisitor(out))
+    } else {
+        deserializer.deserialize_tuple(out.len(), BytesVisitor(out))
+    }
+}
+
+/// Fills a buffer with a hex string, a byte string, or a sequence of bytes.
+/// Error messages never include the rejected value, as it may be secret.
+struct BytesVisitor<'a>(&'a mut [u8]);
+
+impl<'de> Visitor<'de> for BytesVisitor<'_> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} bytes, or a hex string of {} characters",
+            self.0.len(),
+            self.0.len() * 2
+        )
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<(), E> {
+        if v.len() != self.0.len() * 2 {
+            return Err(E::invalid_length(v.len(), &self));
+        }
+        Hex::decode(self.0, v, None).map_err(|_| E::custom("invalid hex string"))?;
+        Ok(())
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<(), E> {
+        if v.len() != self.0.len() {
+            return Err(E::invalid_length(v.len(), &self));
+        }
+        self.0.copy_from_slice(v);
+        Ok(())
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        for i in 0..self.0.len() {
+            self.0[i] = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+        }
+        if seq.next_element::<u8>()?.is_some() {
+            return Err(de::Error::invalid_length(self.0.len() + 1, &self));
+        }
+        Ok(())
+    }
+}
+
+/// Implements `Serialize` and `Deserialize` for a public type with `BYTES`
+/// and `new()`.
+macro_rules! impl_serde_public {
+    ($t:ty) => {
+        impl Serialize for $t {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serialize_bytes(&self[..], serializer)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $t {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let mut bytes = [0u8; <$t>::BYTES];
+                deserialize_bytes(deserializer, &mut bytes)?;
+                Ok(<$t>::new(bytes))
+            }
+        }
+    };
+}
+
+/// Implements `Serialize` and `Deserialize` for a secret type with `BYTES`
+/// and `new()`, wrapped in `ExposeSecret`. An optional check rejects
+/// deserialized values that are not consistent.
+macro_rules! impl_serde_secret {
+    ($t:ty) => {
+        impl_serde_secret!($t, |_: &$t| Ok::<(), Error>(()));
+    };
+    ($t:ty, $check:expr) => {
+        impl Serialize for ExposeSecret<$t> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serialize_bytes(&self.0[..], serializer)
+            }
+        }
+
+        impl Serialize for ExposeSecret<&$t> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serialize_bytes(&self.0[..], serializer)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for ExposeSecret<$t> {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let mut bytes = [0u8; <$t>::BYTES];
+                let res = deserialize_bytes(deserializer, &mut bytes)
+                    .map(|_| ExposeSecret(<$t>::new(bytes)));
+                Mem::wipe(&mut bytes);
+                let secret = res?;
+                ($check)(&secret.0).map_err(de::Error::custom)?;
+                Ok(secret)
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "disable-signatures"))]
+impl_serde_public!(PublicKey);
+#[cfg(not(feature = "disable-signatures"))]
+impl_serde_public!(Signature);
+#[cfg(all(feature = "blind-keys", not(feature = "disable-signatures")))]
+impl_serde_public!(BlindPublicKey);
+#[cfg(feature = "x25519")]
+impl_serde_public!(x25519::PublicKey);
+
+impl_serde_secret!(Seed);
+#[cfg(not(feature = "disable-signatures"))]
+impl_serde_secret!(Noise);
+#[cfg(not(feature = "disable-signatures"))]
+impl_serde_secret!(SecretKey, |sk: &SecretKey| {
+    sk.validate_public_key(&sk.public_key())
+});
+#[cfg(feature = "x25519")]
+impl_serde_secret!(x25519::SecretKey);
+
+#[cfg(not(feature = "disable-signatures"))]
+impl Serialize for ExposeSecret<KeyPair> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ExposeSecret(&self.0).serialize(serializer)
+    }
+}
+
+#[cfg(not(feature = "disable-signatures"))]
+impl Serialize for ExposeSecret<&KeyPair> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_bytes(&self.0.sk[..], serializer)
+    }
+}
+
+#[cfg(not(feature = "disable-signatures"))]
+impl<'de> Deserialize<'de> for ExposeSecret<KeyPair> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let ExposeSecret(sk) = ExposeSecret::<SecretKey>::deserialize(deserializer)?;
+        Ok(ExposeSecret(KeyPair {
+            pk: sk.public_key(),
+            sk,
+        }))
+    }
+}
+
+#[cfg(feature = "x25519")]
+impl Serialize for ExposeSecret<x25519::KeyPair> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ExposeSecret(&self.0).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "x25519")]
+impl Serialize for ExposeSecret<&x25519::KeyPair> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_bytes(&self.0.sk[..], serializer)
+    }
+}
+
+#[cfg(feature = "x25519")]
+impl<'de> Deserialize<'de> for ExposeSecret<x25519::KeyPair> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let ExposeSecret(sk) = ExposeSecret::<x25519::SecretKey>::deserialize(deserializer)?;
+        let pk = sk.recover_public_key().map_err(de::Error::custom)?;
+        Ok(ExposeSecret(x25519::KeyPair { pk, sk }))
+    }
+}
+
+#[cfg(all(feature = "std", not(feature = "disable-signatures")))]
+#[test]
+fn test_serde() {
+    let kp = KeyPair::from_seed([42u8; 32].into());
+    let signature = kp.sk.sign(b"message", None);
+
+    let json = serde_json::to_string(&kp.pk).unwrap();
+    assert_eq!(
+        json,
+        format!("\"{}\"", Hex::encode_to_string(*kp.pk).unwrap())
+    );
+    assert_eq!(serde_json::from_str::<PublicKey>(&json).unwrap(), kp.pk);
+    let bin = bincode::serialize(&signature).unwrap();
+    assert_eq!(bin, &signature[..]);
+    assert_eq!(bincode::deserialize::<Signature>(&bin).unwrap(), signature);
+
+    let json = serde_json::to_string(&ExposeSecret(&kp)).unwrap();
+    assert_eq!(json.len(), 2 + KeyPair::BYTES * 2);
+    let ExposeSecret(kp2): ExposeSecret<KeyPair> = serde_json::from_str(&json).unwrap();
+    assert_eq!(kp2, kp);
+    let bin = bincode::serialize(&ExposeSecret(&kp)).unwrap();
+    assert_eq!(bin, &kp.sk[..]);
+    let ExposeSecret(kp2): ExposeSecret<KeyPair> = bincode::deserialize(&bin).unwrap();
+    assert_eq!(kp2, kp);
+
+    let json = serde_json::to_string(&ExposeSecret(kp.sk.seed())).unwrap();
+    let ExposeSecret(seed): ExposeSecret<Seed> = serde_json::from_str(&json).unwrap();
+    assert_eq!(seed, kp.sk.seed());
+
+    let mut bad = json.clone();
+    bad.replace_range(1..3, "zz");
+    assert!(serde_json::from_str::<ExposeSecret<Seed>>(&bad).is_err());
+    assert!(serde_json::from_str::<PublicKey>("\"00\"").is_err());
+    assert!(bincode::deserialize::<Signature>(&bin[..32]).is_err());
+
+    let mut bin = bincode::serialize(&ExposeSecret(&kp)).unwrap();
+    bin[40] ^= 1;
+    assert!(bincode::deserialize::<ExposeSecret<KeyPair>>(&bin).is_err());
+    assert!(bincode::deserialize::<ExposeSecret<SecretKey>>(&bin).is_err());
+}
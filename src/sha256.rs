@@ -0,0 +1,422 @@
+//! A small, self-contained SHA256 implementation
+
+#![allow(
+    non_snake_case,
+    clippy::cast_lossless,
+    clippy::eq_op,
+    clippy::identity_op,
+    clippy::many_single_char_names,
+    clippy::unreadable_literal
+)]
+
+#[cfg_attr(feature = "opt_size", inline(never))]
+#[cfg_attr(not(feature = "opt_size"), inline(always))]
+fn load_be(base: &[u8], offset: usize) -> u32 {
+    let addr = &base[offset..];
+    (addr[3] as u32) | (addr[2] as u32) << 8 | (addr[1] as u32) << 16 | (addr[0] as u32) << 24
+}
+
+#[cfg_attr(feature = "opt_size", inline(never))]
+#[cfg_attr(not(feature = "opt_size"), inline(always))]
+fn store_be(base: &mut [u8], offset: usize, x: u32) {
+    let addr = &mut base[offset..];
+    addr[3] = x as u8;
+    addr[2] = (x >> 8) as u8;
+    addr[1] = (x >> 16) as u8;
+    addr[0] = (x >> 24) as u8;
+}
+
+struct W([u32; 16]);
+
+#[derive(Copy, Clone)]
+struct State([u32; 8]);
+
+impl W {
+    fn new(input: &[u8]) -> Self {
+        let mut w = [0u32; 16];
+        for (i, e) in w.iter_mut().enumerate() {
+            *e = load_be(input, i * 4)
+        }
+        W(w)
+    }
+
+    #[cfg_attr(feature = "opt_size", inline(never))]
+    #[cfg_attr(not(feature = "opt_size"), inline(always))]
+    fn Ch(x: u32, y: u32, z: u32) -> u32 {
+        (x & y) ^ (!x & z)
+    }
+
+    #[cfg_attr(feature = "opt_size", inline(never))]
+    #[cfg_attr(not(feature = "opt_size"), inline(always))]
+    fn Maj(x: u32, y: u32, z: u32) -> u32 {
+        (x & y) ^ (x & z) ^ (y & z)
+    }
+
+    #[cfg_attr(feature = "opt_size", inline(never))]
+    #[cfg_attr(not(feature = "opt_size"), inline(always))]
+    fn Sigma0(x: u32) -> u32 {
+        x.rotate_right(2) ^ x.rotate_right(13) ^ x.rotate_right(22)
+    }
+
+    #[cfg_attr(feature = "opt_size", inline(never))]
+    #[cfg_attr(not(feature = "opt_size"), inline(always))]
+    fn Sigma1(x: u32) -> u32 {
+        x.rotate_right(6) ^ x.rotate_right(11) ^ x.rotate_right(25)
+    }
+
+    #[cfg_attr(feature = "opt_size", inline(never))]
+    #[cfg_attr(not(feature = "opt_size"), inline(always))]
+    fn sigma0(x: u32) -> u32 {
+        x.rotate_right(7) ^ x.rotate_right(18) ^ (x >> 3)
+    }
+
+    #[cfg_attr(feature = "opt_size", inline(never))]
+    #[cfg_attr(not(feature = "opt_size"), inline(always))]
+    fn sigma1(x: u32) -> u32 {
+        x.rotate_right(17) ^ x.rotate_right(19) ^ (x >> 10)
+    }
+
+    #[cfg_attr(feature = "opt_size", inline(never))]
+    #[cfg_attr(not(feature = "opt_size"), inline(always))]
+    fn M(&mut self, a: usize, b: usize, c: usize, d: usize) {
+        let w = &mut self.0;
+        w[a] = w[a]
+            .wrapping_add(Self::sigma1(w[b]))
+            .wrapping_add(w[c])
+            .wrapping_add(Self::sigma0(w[d]));
+    }
+
+    #[cfg_attr(feature = "opt_size", inline(never))]
+    #[cfg_attr(not(feature = "opt_size"), inline(always))]
+    fn expand(&mut self) {
+        for i in 0..16 {
+            self.M(i, (i + 14) & 15, (i + 9) & 15, (i + 1) & 15);
+        }
+    }
+
+    #[cfg_attr(feature = "opt_size", inline(never))]
+    #[cfg_attr(not(feature = "opt_size"), inline(always))]
+    fn F(&mut self, state: &mut State, i: usize, k: u32) {
+        let t = &mut state.0;
+        t[(16 - i + 7) & 7] = t[(16 - i + 7) & 7]
+            .wrapping_add(Self::Sigma1(t[(16 - i + 4) & 7]))
+            .wrapping_add(Self::Ch(
+                t[(16 - i + 4) & 7],
+                t[(16 - i + 5) & 7],
+                t[(16 - i + 6) & 7],
+            ))
+            .wrapping_add(k)
+            .wrapping_add(self.0[i]);
+        t[(16 - i + 3) & 7] = t[(16 - i + 3) & 7].wrapping_add(t[(16 - i + 7) & 7]);
+        t[(16 - i + 7) & 7] = t[(16 - i + 7) & 7]
+            .wrapping_add(Self::Sigma0(t[(16 - i + 0) & 7]))
+            .wrapping_add(Self::Maj(
+                t[(16 - i + 0) & 7],
+                t[(16 - i + 1) & 7],
+                t[(16 - i + 2) & 7],
+            ));
+    }
+
+    fn G(&mut self, state: &mut State, s: usize) {
+        const ROUND_CONSTANTS: [u32; 64] = [
+            0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4,
+            0xab1c5ed5, 0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe,
+            0x9bdc06a7, 0xc19bf174, 0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f,
+            0x4a7484aa, 0x5cb0a9dc, 0x76f988da, 0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7,
+            0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967, 0x27b70a85, 0x2e1b2138, 0x4d2c6dfc,
+            0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85, 0xa2bfe8a1, 0xa81a664b,
+            0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070, 0x19a4c116,
+            0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+            0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7,
+            0xc67178f2,
+        ];
+        let rc = &ROUND_CONSTANTS[s * 16..];
+        for i in 0..16 {
+            self.F(state, i, rc[i]);
+        }
+    }
+}
+
+impl State {
+    fn new() -> Self {
+        const IV: [u32; 8] = [
+            0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab,
+            0x5be0cd19,
+        ];
+        State(IV)
+    }
+
+    #[cfg_attr(feature = "opt_size", inline(never))]
+    #[cfg_attr(not(feature = "opt_size"), inline(always))]
+    fn add(&mut self, x: &State) {
+        for (sx, ex) in self.0.iter_mut().zip(x.0.iter()) {
+            *sx = sx.wrapping_add(*ex);
+        }
+    }
+
+    fn store(&self, out: &mut [u8]) {
+        for (i, &e) in self.0.iter().enumerate() {
+            store_be(out, i * 4, e);
+        }
+    }
+
+    fn blocks(&mut self, mut input: &[u8]) -> usize {
+        let mut t = *self;
+        let mut inlen = input.len();
+        while inlen >= 64 {
+            let mut w = W::new(input);
+            w.G(&mut t, 0);
+            w.expand();
+            w.G(&mut t, 1);
+            w.expand();
+            w.G(&mut t, 2);
+            w.expand();
+            w.G(&mut t, 3);
+            t.add(self);
+            self.0 = t.0;
+            input = &input[64..];
+            inlen -= 64;
+        }
+        inlen
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct Hash {
+    state: State,
+    w: [u8; 64],
+    r: usize,
+    len: usize,
+}
+
+impl Hash {
+    pub fn new() -> Hash {
+        Hash {
+            state: State::new(),
+            r: 0,
+            w: [0u8; 64],
+            len: 0,
+        }
+    }
+
+    /// Absorb content
+    pub fn update<T: AsRef<[u8]>>(&mut self, input: T) {
+        let input = input.as_ref();
+        let mut n = input.len();
+        self.len += n;
+        let av = 64 - self.r;
+        let tc = ::core::cmp::min(n, av);
+        self.w[self.r..self.r + tc].copy_from_slice(&input[0..tc]);
+        self.r += tc;
+        n -= tc;
+        let pos = tc;
+        if self.r == 64 {
+            self.state.blocks(&self.w);
+            self.r = 0;
+        }
+        if self.r == 0 && n > 0 {
+            let rb = self.state.blocks(&input[pos..]);
+            if rb > 0 {
+                self.w[..rb].copy_from_slice(&input[pos + n - rb..]);
+                self.r = rb;
+            }
+        }
+    }
+
+    /// Compute SHA256(absorbed content)
+    pub fn finalize(mut self) -> [u8; 32] {
+        let mut padded = [0u8; 128];
+        padded[..self.r].copy_from_slice(&self.w[..self.r]);
+        padded[self.r] = 0x80;
+        let r = if self.r < 56 { 64 } else { 128 };
+        let bits = self.len as u64 * 8;
+        for i in 0..8 {
+            padded[r - 8 + i] = (bits >> (56 - i * 8)) as u8;
+        }
+        self.state.blocks(&padded[..r]);
+        let mut out = [0u8; 32];
+        self.state.store(&mut out);
+        out
+    }
+
+    /// Compute SHA256(`input`)
+    #[allow(dead_code)]
+    pub fn hash<T: AsRef<[u8]>>(input: T) -> [u8; 32] {
+        let mut h = Hash::new();
+        h.update(input);
+        h.finalize()
+    }
+}
+
+impl Default for Hash {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(any(
+    all(feature = "minisign", feature = "std"),
+    feature = "age",
+    feature = "hpke"
+))]
+#[derive(Clone)]
+pub struct Hmac {
+    inner: Hash,
+    outer: Hash,
+}
+
+#[cfg(any(
+    all(feature = "minisign", feature = "std"),
+    feature = "age",
+    feature = "hpke"
+))]
+impl Hmac {
+    pub fn new(k: impl AsRef<[u8]>) -> Hmac {
+        let k = k.as_ref();
+        let mut hk = [0u8; 32];
+        let k2 = if k.len() > 64 {
+            hk.copy_from_slice(&Hash::hash(k));
+            &hk
+        } else {
+            k
+        };
+        let mut padded = [0x36; 64];
+        for (p, &k) in padded.iter_mut().zip(k2.iter()) {
+            *p ^= k;
+        }
+        let mut inner = Hash::new();
+        inner.update(&padded[..]);
+        for p in padded.iter_mut() {
+            *p ^= 0x36 ^ 0x5c;
+        }
+        let mut outer = Hash::new();
+        outer.update(&padded[..]);
+        Hmac { inner, outer }
+    }
+
+    /// Absorb content
+    pub fn update<T: AsRef<[u8]>>(&mut self, input: T) {
+        self.inner.update(input);
+    }
+
+    /// Compute HMAC-SHA256(absorbed content)
+    pub fn finalize(self) -> [u8; 32] {
+        let ih = self.inner.finalize();
+        let mut outer = self.outer;
+        outer.update(ih);
+        outer.finalize()
+    }
+}
+
+#[cfg(any(feature = "age", feature = "hpke"))]
+pub struct Hkdf;
+
+#[cfg(any(feature = "age", feature = "hpke"))]
+impl Hkdf {
+    /// HKDF-Extract(`salt`, `ikm`)
+    #[cfg(feature = "age")]
+    pub fn extract(salt: impl AsRef<[u8]>, ikm: impl AsRef<[u8]>) -> [u8; 32] {
+        let mut h = Hmac::new(salt);
+        h.update(ikm);
+        h.finalize()
+    }
+
+    /// HKDF-Expand(`prk`, `info`), filling `out`.
+    /// `out` must not be larger than 255*32 bytes.
+    #[cfg(feature = "age")]
+    pub fn expand(out: &mut [u8], prk: &[u8; 32], info: impl AsRef<[u8]>) {
+        Self::expand_parts(out, prk, &[info.as_ref()])
+    }
+
+    /// HKDF-Expand(`prk`, `info`), with `info` being the concatenation of
+    /// `info_parts`.
+    pub fn expand_parts(out: &mut [u8], prk: &[u8; 32], info_parts: &[&[u8]]) {
+        assert!(out.len() <= 255 * 32);
+        let mut t = [0u8; 32];
+        for (i, chunk) in out.chunks_mut(32).enumerate() {
+            let mut h = Hmac::new(prk);
+            if i > 0 {
+                h.update(t);
+            }
+            for info in info_parts {
+                h.update(info);
+            }
+            h.update([i as u8 + 1]);
+            t = h.finalize();
+            chunk.copy_from_slice(&t[..chunk.len()]);
+        }
+    }
+}
+
+#[test]
+fn test_sha256() {
+    use ct_codecs::{Decoder, Hex};
+
+    let h = Hash::hash(b"abc");
+    assert_eq!(
+        &h[..],
+        &Hex::decode_to_vec(
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+            None
+        )
+        .unwrap()[..]
+    );
+    let msg = b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq";
+    assert_eq!(
+        &Hash::hash(msg)[..],
+        &Hex::decode_to_vec(
+            "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1",
+            None
+        )
+        .unwrap()[..]
+    );
+    let mut h = Hash::new();
+    for chunk in msg.chunks(5) {
+        h.update(chunk);
+    }
+    assert_eq!(h.finalize(), Hash::hash(msg));
+}
+
+#[cfg(any(
+    all(feature = "minisign", feature = "std"),
+    feature = "age",
+    feature = "hpke"
+))]
+#[test]
+fn test_hmac_sha256() {
+    use ct_codecs::{Decoder, Hex};
+
+    let mut h = Hmac::new(b"Jefe");
+    h.update(b"what do ya want for nothing?");
+    let mac = h.finalize();
+    assert_eq!(
+        &mac[..],
+        &Hex::decode_to_vec(
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+            None
+        )
+        .unwrap()[..]
+    );
+}
+
+#[cfg(feature = "age")]
+#[test]
+fn test_hkdf_sha256() {
+    use ct_codecs::{Decoder, Hex};
+
+    // RFC 5869, test case 1
+    let ikm = [0x0bu8; 22];
+    let salt = Hex::decode_to_vec("000102030405060708090a0b0c", None).unwrap();
+    let info = Hex::decode_to_vec("f0f1f2f3f4f5f6f7f8f9", None).unwrap();
+    let prk = Hkdf::extract(&salt, ikm);
+    let mut okm = [0u8; 42];
+    Hkdf::expand(&mut okm, &prk, &info);
+    assert_eq!(
+        &okm[..],
+        &Hex::decode_to_vec(
+            "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865",
+            None
+        )
+        .unwrap()[..]
+    );
+}
@@ -10,6 +10,9 @@
     clippy::unreadable_literal
 )]
 
+use super::common::*;
+use super::error::Error;
+
 #[cfg_attr(feature = "opt_size", inline(never))]
 #[cfg_attr(not(feature = "opt_size"), inline(always))]
 fn load_be(base: &[u8], offset: usize) -> u64 {
@@ -38,6 +41,89 @@ fn store_be(base: &mut [u8], offset: usize, x: u64) {
     addr[0] = (x >> 56) as u8;
 }
 
+pub(crate) const ROUND_CONSTANTS: [u64; 80] = [
+    0x428a2f98d728ae22,
+    0x7137449123ef65cd,
+    0xb5c0fbcfec4d3b2f,
+    0xe9b5dba58189dbbc,
+    0x3956c25bf348b538,
+    0x59f111f1b605d019,
+    0x923f82a4af194f9b,
+    0xab1c5ed5da6d8118,
+    0xd807aa98a3030242,
+    0x12835b0145706fbe,
+    0x243185be4ee4b28c,
+    0x550c7dc3d5ffb4e2,
+    0x72be5d74f27b896f,
+    0x80deb1fe3b1696b1,
+    0x9bdc06a725c71235,
+    0xc19bf174cf692694,
+    0xe49b69c19ef14ad2,
+    0xefbe4786384f25e3,
+    0x0fc19dc68b8cd5b5,
+    0x240ca1cc77ac9c65,
+    0x2de92c6f592b0275,
+    0x4a7484aa6ea6e483,
+    0x5cb0a9dcbd41fbd4,
+    0x76f988da831153b5,
+    0x983e5152ee66dfab,
+    0xa831c66d2db43210,
+    0xb00327c898fb213f,
+    0xbf597fc7beef0ee4,
+    0xc6e00bf33da88fc2,
+    0xd5a79147930aa725,
+    0x06ca6351e003826f,
+    0x142929670a0e6e70,
+    0x27b70a8546d22ffc,
+    0x2e1b21385c26c926,
+    0x4d2c6dfc5ac42aed,
+    0x53380d139d95b3df,
+    0x650a73548baf63de,
+    0x766a0abb3c77b2a8,
+    0x81c2c92e47edaee6,
+    0x92722c851482353b,
+    0xa2bfe8a14cf10364,
+    0xa81a664bbc423001,
+    0xc24b8b70d0f89791,
+    0xc76c51a30654be30,
+    0xd192e819d6ef5218,
+    0xd69906245565a910,
+    0xf40e35855771202a,
+    0x106aa07032bbd1b8,
+    0x19a4c116b8d2d0c8,
+    0x1e376c085141ab53,
+    0x2748774cdf8eeb99,
+    0x34b0bcb5e19b48a8,
+    0x391c0cb3c5c95a63,
+    0x4ed8aa4ae3418acb,
+    0x5b9cca4f7763e373,
+    0x682e6ff3d6b2b8a3,
+    0x748f82ee5defb2fc,
+    0x78a5636f43172f60,
+    0x84c87814a1f0ab72,
+    0x8cc702081a6439ec,
+    0x90befffa23631e28,
+    0xa4506cebde82bde9,
+    0xbef9a3f7b2c67915,
+    0xc67178f2e372532b,
+    0xca273eceea26619c,
+    0xd186b8c721c0c207,
+    0xeada7dd6cde0eb1e,
+    0xf57d4f7fee6ed178,
+    0x06f067aa72176fba,
+    0x0a637dc5a2c898a6,
+    0x113f9804bef90dae,
+    0x1b710b35131c471b,
+    0x28db77f523047d84,
+    0x32caab7b40c72493,
+    0x3c9ebe0a15c9bebc,
+    0x431d67c49c100d4c,
+    0x4cc5d4becb3e42b6,
+    0x597f299cfc657e2a,
+    0x5fcb6fab3ad6faec,
+    0x6c44198c4a475817,
+];
+
 struct W([u64; 16]);
 
 #[derive(Copy, Clone)]
@@ -76,18 +162,21 @@ impl W {
         x.rotate_right(14) ^ x.rotate_right(18) ^ x.rotate_right(41)
     }
 
+    #[cfg(not(all(target_arch = "wasm32", target_feature = "simd128")))]
     #[cfg_attr(feature = "opt_size", inline(never))]
     #[cfg_attr(not(feature = "opt_size"), inline(always))]
     fn sigma0(x: u64) -> u64 {
         x.rotate_right(1) ^ x.rotate_right(8) ^ (x >> 7)
     }
 
+    #[cfg(not(all(target_arch = "wasm32", target_feature = "simd128")))]
     #[cfg_attr(feature = "opt_size", inline(never))]
     #[cfg_attr(not(feature = "opt_size"), inline(always))]
     fn sigma1(x: u64) -> u64 {
         x.rotate_right(19) ^ x.rotate_right(61) ^ (x >> 6)
     }
 
+    #[cfg(not(all(target_arch = "wasm32", target_feature = "simd128")))]
     #[cfg_attr(feature = "opt_size", inline(never))]
     #[cfg_attr(not(feature = "opt_size"), inline(always))]
     fn M(&mut self, a: usize, b: usize, c: usize, d: usize) {
@@ -98,6 +187,7 @@ impl W {
             .wrapping_add(Self::sigma0(w[d]));
     }
 
+    #[cfg(not(all(target_arch = "wasm32", target_feature = "simd128")))]
     #[cfg_attr(feature = "opt_size", inline(never))]
     #[cfg_attr(not(feature = "opt_size"), inline(always))]
     fn expand(&mut self) {
@@ -119,6 +209,37 @@ impl W {
         self.M(15, (15 + 14) & 15, (15 + 9) & 15, (15 + 1) & 15);
     }
 
+    /// Computes the next 16 words of the message schedule, two at a time.
+    #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+    #[cfg_attr(feature = "opt_size", inline(never))]
+    #[cfg_attr(not(feature = "opt_size"), inline(always))]
+    fn expand(&mut self) {
+        use core::arch::wasm32::*;
+
+        #[inline(always)]
+        fn rotr<const N: u32>(x: v128) -> v128 {
+            v128_or(u64x2_shr(x, N), i64x2_shl(x, 64 - N))
+        }
+
+        let w = &mut self.0;
+        let mut v = [u64x2_splat(0); 8];
+        for (i, v) in v.iter_mut().enumerate() {
+            *v = u64x2(w[2 * i], w[2 * i + 1]);
+        }
+        for i in 0..8 {
+            let w15 = i64x2_shuffle::<1, 2>(v[i], v[(i + 1) & 7]);
+            let w7 = i64x2_shuffle::<1, 2>(v[(i + 4) & 7], v[(i + 5) & 7]);
+            let w2 = v[(i + 7) & 7];
+            let s0 = v128_xor(v128_xor(rotr::<1>(w15), rotr::<8>(w15)), u64x2_shr(w15, 7));
+            let s1 = v128_xor(v128_xor(rotr::<19>(w2), rotr::<61>(w2)), u64x2_shr(w2, 6));
+            v[i] = i64x2_add(i64x2_add(v[i], s0), i64x2_add(w7, s1));
+        }
+        for (i, v) in v.iter().enumerate() {
+            w[2 * i] = u64x2_extract_lane::<0>(*v);
+            w[2 * i + 1] = u64x2_extract_lane::<1>(*v);
+        }
+    }
+
     #[cfg_attr(feature = "opt_size", inline(never))]
     #[cfg_attr(not(feature = "opt_size"), inline(always))]
     fn F(&mut self, state: &mut State, i: usize, k: u64) {
@@ -143,88 +264,6 @@ impl W {
     }
 
     fn G(&mut self, state: &mut State, s: usize) {
-        const ROUND_CONSTANTS: [u64; 80] = [
-            0x428a2f98d728ae22,
-            0x7137449123ef65cd,
-            0xb5c0fbcfec4d3b2f,
-            0xe9b5dba58189dbbc,
-            0x3956c25bf348b538,
-            0x59f111f1b605d019,
-            0x923f82a4af194f9b,
-            0xab1c5ed5da6d8118,
-            0xd807aa98a3030242,
-            0x12835b0145706fbe,
-            0x243185be4ee4b28c,
-            0x550c7dc3d5ffb4e2,
-            0x72be5d74f27b896f,
-            0x80deb1fe3b1696b1,
-            0x9bdc06a725c71235,
-            0xc19bf174cf692694,
-            0xe49b69c19ef14ad2,
-            0xefbe4786384f25e3,
-            0x0fc19dc68b8cd5b5,
-            0x240ca1cc77ac9c65,
-            0x2de92c6f592b0275,
-            0x4a7484aa6ea6e483,
-            0x5cb0a9dcbd41fbd4,
-            0x76f988da831153b5,
-            0x983e5152ee66dfab,
-            0xa831c66d2db43210,
-            0xb00327c898fb213f,
-            0xbf597fc7beef0ee4,
-            0xc6e00bf33da88fc2,
-            0xd5a79147930aa725,
-            0x06ca6351e003826f,
-            0x142929670a0e6e70,
-            0x27b70a8546d22ffc,
-            0x2e1b21385c26c926,
-            0x4d2c6dfc5ac42aed,
-            0x53380d139d95b3df,
-            0x650a73548baf63de,
-            0x766a0abb3c77b2a8,
-            0x81c2c92e47edaee6,
-            0x92722c851482353b,
-            0xa2bfe8a14cf10364,
-            0xa81a664bbc423001,
-            0xc24b8b70d0f89791,
-            0xc76c51a30654be30,
-            0xd192e819d6ef5218,
-            0xd69906245565a910,
-            0xf40e35855771202a,
-            0x106aa07032bbd1b8,
-            0x19a4c116b8d2d0c8,
-            0x1e376c085141ab53,
-            0x2748774cdf8eeb99,
-            0x34b0bcb5e19b48a8,
-            0x391c0cb3c5c95a63,
-            0x4ed8aa4ae3418acb,
-            0x5b9cca4f7763e373,
-            0x682e6ff3d6b2b8a3,
-            0x748f82ee5defb2fc,
-            0x78a5636f43172f60,
-            0x84c87814a1f0ab72,
-            0x8cc702081a6439ec,
-            0x90befffa23631e28,
-            0xa4506cebde82bde9,
-            0xbef9a3f7b2c67915,
-            0xc67178f2e372532b,
-            0xca273eceea26619c,
-            0xd186b8c721c0c207,
-            0xeada7dd6cde0eb1e,
-            0xf57d4f7fee6ed178,
-            0x06f067aa72176fba,
-            0x0a637dc5a2c898a6,
-            0x113f9804bef90dae,
-            0x1b710b35131c471b,
-            0x28db77f523047d84,
-            0x32caab7b40c72493,
-            0x3c9ebe0a15c9bebc,
-            0x431d67c49c100d4c,
-            0x4cc5d4becb3e42b6,
-            0x597f299cfc657e2a,
-            0x5fcb6fab3ad6faec,
-            0x6c44198c4a475817,
-        ];
         let rc = &ROUND_CONSTANTS[s * 16..];
         self.F(state, 0, rc[0]);
         self.F(state, 1, rc[1]);
@@ -245,19 +284,42 @@ impl W {
     }
 }
 
+pub(crate) const IV: [u64; 8] = [
+    0x6a09e667f3bcc908,
+    0xbb67ae8584caa73b,
+    0x3c6ef372fe94f82b,
+    0xa54ff53a5f1d36f1,
+    0x510e527fade682d1,
+    0x9b05688c2b3e6c1f,
+    0x1f83d9abfb41bd6b,
+    0x5be0cd19137e2179,
+];
+
+const IV_384: [u64; 8] = [
+    0xcbbb9d5dc1059ed8,
+    0x629a292a367cd507,
+    0x9159015a3070dd17,
+    0x152fecd8f70e5939,
+    0x67332667ffc00b31,
+    0x8eb44a8768581511,
+    0xdb0c2e0d64f98fa7,
+    0x47b5481dbefa4fa4,
+];
+
+const IV_512_256: [u64; 8] = [
+    0x22312194fc2bf72c,
+    0x9f555fa3c84c64c2,
+    0x2393b86b6f53b151,
+    0x963877195940eabd,
+    0x96283ee2a88effe3,
+    0xbe5e1e2553863992,
+    0x2b0199fc2c85b8aa,
+    0x0eb72ddc81c52ca2,
+];
+
 impl State {
-    fn new() -> Self {
-        const IV: [u64; 8] = [
-            0x6a09e667f3bcc908,
-            0xbb67ae8584caa73b,
-            0x3c6ef372fe94f82b,
-            0xa54ff53a5f1d36f1,
-            0x510e527fade682d1,
-            0x9b05688c2b3e6c1f,
-            0x1f83d9abfb41bd6b,
-            0x5be0cd19137e2179,
-        ];
-        State(IV)
+    fn new(iv: [u64; 8]) -> Self {
+        State(iv)
     }
 
     #[cfg_attr(feature = "opt_size", inline(never))]
@@ -281,7 +343,15 @@ impl State {
         }
     }
 
-    fn blocks(&mut self, mut input: &[u8]) -> usize {
+    fn blocks(&mut self, input: &[u8]) -> usize {
+        #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+        if let Some(inlen) = super::sha512_hw::blocks(&mut self.0, input) {
+            return inlen;
+        }
+        self.blocks_portable(input)
+    }
+
+    fn blocks_portable(&mut self, mut input: &[u8]) -> usize {
         let mut t = *self;
         let mut inlen = input.len();
         while inlen >= 128 {
@@ -304,6 +374,20 @@ impl State {
     }
 }
 
+/// Processes all the complete 128-byte blocks of `input`, and returns the
+/// number of remaining bytes.
+#[cfg(any(
+    target_arch = "x86_64",
+    target_arch = "aarch64",
+    all(target_arch = "wasm32", target_feature = "simd128")
+))]
+pub(crate) fn compress_blocks(state: &mut [u64; 8], input: &[u8]) -> usize {
+    let mut st = State(*state);
+    let inlen = st.blocks(input);
+    *state = st.0;
+    inlen
+}
+
 #[derive(Copy, Clone)]
 pub struct Hash {
     state: State,
@@ -314,8 +398,12 @@ pub struct Hash {
 
 impl Hash {
     pub fn new() -> Hash {
+        Hash::with_iv(IV)
+    }
+
+    fn with_iv(iv: [u64; 8]) -> Hash {
         Hash {
-            state: State::new(),
+            state: State::new(iv),
             r: 0,
             w: [0u8; 128],
             len: 0,
@@ -368,6 +456,20 @@ impl Hash {
         h.update(input);
         h.finalize()
     }
+
+    /// Compute SHA512 of every input, storing the digests in `out`, which
+    /// must have as many elements as `inputs`.
+    ///
+    /// Messages are hashed in parallel using SIMD instructions when possible,
+    /// which is faster than hashing them one by one, especially for short
+    /// messages.
+    pub fn hash_many<T: AsRef<[u8]>>(inputs: &[T], out: &mut [[u8; 64]]) -> Result<(), Error> {
+        if inputs.len() != out.len() {
+            return Err(Error::InvalidParameters);
+        }
+        super::sha512_mb::hash_many(inputs, out);
+        Ok(())
+    }
 }
 
 impl Default for Hash {
@@ -375,3 +477,634 @@ impl Default for Hash {
         Self::new()
     }
 }
+
+/// Version of the exported state format.
+const EXPORT_VERSION: u8 = 1;
+pub(crate) const EXPORT_HEADER_BYTES: usize = 2;
+pub(crate) const EXPORT_TAG_BYTES: usize = 32;
+
+/// Kinds of exported states. A state can only be imported as its own kind.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub(crate) enum ExportKind {
+    Hash = 1,
+    #[cfg(not(feature = "disable-signatures"))]
+    VerifyingState = 2,
+    #[cfg(not(feature = "disable-signatures"))]
+    SigningState = 3,
+}
+
+/// Writes the header and the authentication tag of an exported state.
+/// `out` is the full serialized state, whose payload must have already been
+/// written after the header. `ad` is authenticated, but not stored.
+pub(crate) fn export_seal(out: &mut [u8], kind: ExportKind, key: &[u8], ad: &[u8]) {
+    out[0] = EXPORT_VERSION;
+    out[1] = kind as u8;
+    let tag_pos = out.len() - EXPORT_TAG_BYTES;
+    let mut h = Hmac::new(key);
+    h.update(ad);
+    h.update(&out[..tag_pos]);
+    let mut tag = h.finalize();
+    out[tag_pos..].copy_from_slice(&tag[..EXPORT_TAG_BYTES]);
+    Mem::wipe(&mut tag);
+}
+
+/// Checks the header and the authentication tag of an exported state, and
+/// returns its payload.
+pub(crate) fn export_open<'t>(
+    bytes: &'t [u8],
+    expected_len: usize,
+    kind: ExportKind,
+    key: &[u8],
+    ad: &[u8],
+) -> Result<&'t [u8], Error> {
+    if bytes.len() != expected_len {
+        return Err(Error::ParseError);
+    }
+    if bytes[0] != EXPORT_VERSION || bytes[1] != kind as u8 {
+        return Err(Error::InvalidParameters);
+    }
+    let tag_pos = bytes.len() - EXPORT_TAG_BYTES;
+    let mut h = Hmac::new(key);
+    h.update(ad);
+    h.update(&bytes[..tag_pos]);
+    h.verify_truncated(&bytes[tag_pos..], EXPORT_TAG_BYTES)?;
+    Ok(&bytes[EXPORT_HEADER_BYTES..tag_pos])
+}
+
+impl Hash {
+    /// Number of bytes in the raw state of a hash function.
+    pub(crate) const STATE_BYTES: usize = 64 + 8 + 128;
+
+    /// Number of bytes in an exported state.
+    pub const EXPORTED_BYTES: usize = EXPORT_HEADER_BYTES + Self::STATE_BYTES + EXPORT_TAG_BYTES;
+
+    pub(crate) fn store_state(&self, out: &mut [u8]) {
+        self.state.store(&mut out[..64]);
+        out[64..72].copy_from_slice(&(self.len as u64).to_be_bytes());
+        out[72..72 + self.r].copy_from_slice(&self.w[..self.r]);
+        for x in out[72 + self.r..Self::STATE_BYTES].iter_mut() {
+            *x = 0;
+        }
+    }
+
+    pub(crate) fn load_state(bytes: &[u8]) -> Result<Hash, Error> {
+        let mut st = [0u64; 8];
+        for (i, x) in st.iter_mut().enumerate() {
+            *x = load_be(bytes, i * 8);
+        }
+        let len = load_be(bytes, 64);
+        if len > usize::MAX as u64 {
+            return Err(Error::InvalidParameters);
+        }
+        let len = len as usize;
+        let r = len % 128;
+        let mut w = [0u8; 128];
+        w.copy_from_slice(&bytes[72..Self::STATE_BYTES]);
+        if w[r..].iter().any(|&x| x != 0) {
+            return Err(Error::NonCanonical);
+        }
+        Ok(Hash {
+            state: State::new(st),
+            w,
+            r,
+            len,
+        })
+    }
+
+    /// Exports the state of the hash function, so that it can be resumed
+    /// later, possibly by another process.
+    ///
+    /// The state is authenticated using `key`, which will be required to
+    /// import it.
+    pub fn export(&self, key: &[u8]) -> [u8; Hash::EXPORTED_BYTES] {
+        let mut out = [0u8; Hash::EXPORTED_BYTES];
+        self.store_state(&mut out[EXPORT_HEADER_BYTES..]);
+        export_seal(&mut out, ExportKind::Hash, key, &[]);
+        out
+    }
+
+    /// Imports a state previously exported with `export()`.
+    pub fn import(bytes: &[u8], key: &[u8]) -> Result<Hash, Error> {
+        let state = export_open(bytes, Hash::EXPORTED_BYTES, ExportKind::Hash, key, &[])?;
+        Hash::load_state(state)
+    }
+}
+
+/// SHA-384.
+#[derive(Copy, Clone)]
+pub struct Sha384(Hash);
+
+impl Sha384 {
+    /// Number of bytes in a digest.
+    pub const BYTES: usize = 48;
+
+    pub fn new() -> Sha384 {
+        Sha384(Hash::with_iv(IV_384))
+    }
+
+    /// Absorb content
+    pub fn update<T: AsRef<[u8]>>(&mut self, input: T) {
+        self.0.update(input)
+    }
+
+    /// Compute SHA384(absorbed content)
+    pub fn finalize(self) -> [u8; 48] {
+        let mut out = [0u8; 48];
+        out.copy_from_slice(&self.0.finalize()[..48]);
+        out
+    }
+
+    /// Compute SHA384(`input`)
+    pub fn hash<T: AsRef<[u8]>>(input: T) -> [u8; 48] {
+        let mut h = Sha384::new();
+        h.update(input);
+        h.finalize()
+    }
+}
+
+impl Default for Sha384 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// SHA-512/256.
+#[derive(Copy, Clone)]
+pub struct Sha512_256(Hash);
+
+impl Sha512_256 {
+    /// Number of bytes in a digest.
+    pub const BYTES: usize = 32;
+
+    pub fn new() -> Sha512_256 {
+        Sha512_256(Hash::with_iv(IV_512_256))
+    }
+
+    /// Absorb content
+    pub fn update<T: AsRef<[u8]>>(&mut self, input: T) {
+        self.0.update(input)
+    }
+
+    /// Compute SHA512/256(absorbed content)
+    pub fn finalize(self) -> [u8; 32] {
+        let mut out = [0u8; 32];
+        out.copy_from_slice(&self.0.finalize()[..32]);
+        out
+    }
+
+    /// Compute SHA512/256(`input`)
+    pub fn hash<T: AsRef<[u8]>>(input: T) -> [u8; 32] {
+        let mut h = Sha512_256::new();
+        h.update(input);
+        h.finalize()
+    }
+}
+
+impl Default for Sha512_256 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// SHA-512/t, with `t = 8 * N`.
+///
+/// `N` must be between 1 and 63, and cannot be 48 (use `Sha384` instead).
+/// The initial state is computed by `new()`, as specified in FIPS 180-4.
+///
+/// With the `digest` feature, the traits from the `digest` crate are only
+/// implemented for SHA-512/224 (`Sha512T<28>`) and SHA-512/256
+/// (`Sha512T<32>`).
+#[derive(Copy, Clone)]
+pub struct Sha512T<const N: usize>(Hash);
+
+impl<const N: usize> Sha512T<N> {
+    /// Number of bytes in a digest.
+    pub const BYTES: usize = N;
+
+    const VALID: () = assert!(N > 0 && N < 64 && N != 48, "invalid SHA-512/t length");
+
+    pub fn new() -> Self {
+        let () = Self::VALID;
+        let mut iv = IV;
+        for x in iv.iter_mut() {
+            *x ^= 0xa5a5a5a5a5a5a5a5;
+        }
+        let mut h = Hash::with_iv(iv);
+        h.update(b"SHA-512/");
+        let t = N * 8;
+        let digits = [(t / 100) as u8, (t / 10 % 10) as u8, (t % 10) as u8];
+        let skip = if t >= 100 {
+            0
+        } else if t >= 10 {
+            1
+        } else {
+            2
+        };
+        for d in &digits[skip..] {
+            h.update([b'0' + d]);
+        }
+        let ivt = h.finalize();
+        for (i, x) in iv.iter_mut().enumerate() {
+            *x = load_be(&ivt, i * 8);
+        }
+        Sha512T(Hash::with_iv(iv))
+    }
+
+    /// Absorb content
+    pub fn update<T: AsRef<[u8]>>(&mut self, input: T) {
+        self.0.update(input)
+    }
+
+    /// Compute SHA512/t(absorbed content)
+    pub fn finalize(self) -> [u8; N] {
+        let mut out = [0u8; N];
+        out.copy_from_slice(&self.0.finalize()[..N]);
+        out
+    }
+
+    /// Compute SHA512/t(`input`)
+    pub fn hash<T: AsRef<[u8]>>(input: T) -> [u8; N] {
+        let mut h = Self::new();
+        h.update(input);
+        h.finalize()
+    }
+}
+
+impl<const N: usize> Default for Sha512T<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// HMAC-SHA512.
+#[derive(Clone)]
+pub struct Hmac {
+    inner: Hash,
+    outer: Hash,
+}
+
+impl Hmac {
+    /// Number of bytes in a tag.
+    pub const BYTES: usize = 64;
+
+    /// Creates a new HMAC state with the key `k`.
+    pub fn new(k: impl AsRef<[u8]>) -> Hmac {
+        let k = k.as_ref();
+        let mut hk = [0u8; 64];
+        let k2 = if k.len() > 128 {
+            hk.copy_from_slice(&Hash::hash(k));
+            &hk
+        } else {
+            k
+        };
+        let mut padded = [0x36; 128];
+        for (p, &k) in padded.iter_mut().zip(k2.iter()) {
+            *p ^= k;
+        }
+        let mut inner = Hash::new();
+        inner.update(&padded[..]);
+        for p in padded.iter_mut() {
+            *p ^= 0x36 ^ 0x5c;
+        }
+        let mut outer = Hash::new();
+        outer.update(&padded[..]);
+        Mem::wipe(&mut hk);
+        Mem::wipe(&mut padded);
+        Hmac { inner, outer }
+    }
+
+    /// Absorb content
+    pub fn update<T: AsRef<[u8]>>(&mut self, input: T) {
+        self.inner.update(input);
+    }
+
+    /// Compute HMAC-SHA512(absorbed content)
+    pub fn finalize(self) -> [u8; 64] {
+        let ih = self.inner.finalize();
+        let mut outer = self.outer;
+        outer.update(ih);
+        outer.finalize()
+    }
+
+    /// Verify in constant time that `tag` is HMAC-SHA512(absorbed content).
+    /// `tag` must be a full, 64 bytes tag.
+    pub fn verify(self, tag: &[u8]) -> Result<(), Error> {
+        self.verify_truncated(tag, Self::BYTES)
+    }
+
+    /// Verify in constant time that `tag` is HMAC-SHA512(absorbed content),
+    /// truncated to `len` bytes.
+    /// `tag` must be exactly `len` bytes long, and `len` must be between 16
+    /// and 64.
+    pub fn verify_truncated(self, tag: &[u8], len: usize) -> Result<(), Error> {
+        if !(16..=Self::BYTES).contains(&len) {
+            return Err(Error::InvalidParameters);
+        }
+        let mut mac = self.finalize();
+        let ok = tag.len() == len && Mem::ct_eq(&mac[..len], tag);
+        Mem::wipe(&mut mac);
+        if !ok {
+            return Err(Error::TagMismatch);
+        }
+        Ok(())
+    }
+
+    /// Compute HMAC-SHA512(`k`, `input`)
+    pub fn mac<T: AsRef<[u8]>>(k: impl AsRef<[u8]>, input: T) -> [u8; 64] {
+        let mut h = Hmac::new(k);
+        h.update(input);
+        h.finalize()
+    }
+}
+
+/// HKDF-SHA512.
+pub struct Hkdf;
+
+impl Hkdf {
+    /// Maximum number of bytes that can be derived from a single key.
+    pub const MAX_OUTPUT_BYTES: usize = 255 * 64;
+
+    /// HKDF-Extract(`salt`, `ikm`)
+    pub fn extract(salt: impl AsRef<[u8]>, ikm: impl AsRef<[u8]>) -> [u8; 64] {
+        Hmac::mac(salt, ikm)
+    }
+
+    /// HKDF-Expand(`prk`, `info`), filling `out`.
+    /// `out` must not be larger than `MAX_OUTPUT_BYTES`.
+    pub fn expand(out: &mut [u8], prk: &[u8; 64], info: impl AsRef<[u8]>) -> Result<(), Error> {
+        if out.len() > Self::MAX_OUTPUT_BYTES {
+            return Err(Error::InvalidParameters);
+        }
+        let mut t = [0u8; 64];
+        for (i, chunk) in out.chunks_mut(64).enumerate() {
+            let mut h = Hmac::new(&prk[..]);
+            if i > 0 {
+                h.update(t);
+            }
+            h.update(info.as_ref());
+            h.update([i as u8 + 1]);
+            t = h.finalize();
+            chunk.copy_from_slice(&t[..chunk.len()]);
+        }
+        Mem::wipe(&mut t);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "digest")]
+mod digest_trait {
+    use ::digest::common::BlockSizeUser;
+    use ::digest::consts::{U128, U28, U32, U48, U64};
+    use ::digest::{
+        FixedOutput, FixedOutputReset, HashMarker, Output, OutputSizeUser, Reset, Update,
+    };
+
+    use super::{Hash, Sha384, Sha512T, Sha512_256};
+
+    macro_rules! impl_digest {
+        ($t:ty, $output_size:ty) => {
+            impl Update for $t {
+                fn update(&mut self, data: &[u8]) {
+                    <$t>::update(self, data)
+                }
+            }
+
+            impl OutputSizeUser for $t {
+                type OutputSize = $output_size;
+            }
+
+            impl BlockSizeUser for $t {
+                type BlockSize = U128;
+            }
+
+            impl FixedOutput for $t {
+                fn finalize_into(self, out: &mut Output<Self>) {
+                    out.copy_from_slice(&<$t>::finalize(self)[..]);
+                }
+            }
+
+            impl Reset for $t {
+                fn reset(&mut self) {
+                    *self = <$t>::new();
+                }
+            }
+
+            impl FixedOutputReset for $t {
+                fn finalize_into_reset(&mut self, out: &mut Output<Self>) {
+                    FixedOutput::finalize_into(*self, out);
+                    Reset::reset(self);
+                }
+            }
+
+            impl HashMarker for $t {}
+        };
+    }
+
+    impl_digest!(Hash, U64);
+    impl_digest!(Sha384, U48);
+    impl_digest!(Sha512_256, U32);
+    impl_digest!(Sha512T<28>, U28);
+    impl_digest!(Sha512T<32>, U32);
+}
+
+#[cfg(feature = "digest")]
+#[test]
+fn test_digest_trait() {
+    use ::digest::Digest;
+
+    fn digest<D: Digest + ::digest::FixedOutputReset>(input: &[u8]) -> ::digest::Output<D> {
+        let mut h = D::new();
+        Digest::update(&mut h, &input[..10]);
+        Digest::update(&mut h, &input[10..]);
+        let out = h.finalize_reset();
+        assert_eq!(out, h.chain_update(input).finalize());
+        out
+    }
+
+    let msg = [0x42u8; 1000];
+    assert_eq!(&digest::<Hash>(&msg)[..], &Hash::hash(&msg[..])[..]);
+    assert_eq!(&digest::<Sha384>(&msg)[..], &Sha384::hash(&msg[..])[..]);
+    assert_eq!(
+        &digest::<Sha512_256>(&msg)[..],
+        &Sha512_256::hash(&msg[..])[..]
+    );
+    assert_eq!(
+        &digest::<Sha512T<28>>(&msg)[..],
+        &Sha512T::<28>::hash(&msg[..])[..]
+    );
+    assert_eq!(
+        &digest::<Sha512T<32>>(&msg)[..],
+        &Sha512T::<32>::hash(&msg[..])[..]
+    );
+}
+
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+#[test]
+fn test_sha512_backends() {
+    use super::sha512_hw::Backend;
+
+    let mut input = [0u8; 128 * 9 + 17];
+    for (i, x) in input.iter_mut().enumerate() {
+        *x = (i * 7 + (i >> 8)) as u8;
+    }
+    for backend in Backend::available() {
+        for len in [0, 17, 128, 256, 128 * 9, input.len()] {
+            let mut expected = State::new(IV);
+            let mut state = State::new(IV);
+            let rb = expected.blocks_portable(&input[..len]);
+            assert_eq!(backend.blocks(&mut state.0, &input[..len]), rb);
+            assert_eq!(state.0, expected.0, "{:?}", backend);
+        }
+    }
+}
+
+#[test]
+fn test_sha512_variants() {
+    use ct_codecs::{Decoder, Hex};
+
+    // FIPS 180-4 examples
+    let msgs: [&[u8]; 2] = [
+        b"abc",
+        b"abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu",
+    ];
+    let sha384 = [
+        "cb00753f45a35e8bb5a03d699ac65007272c32ab0eded1631a8b605a43ff5bed8086072ba1e7cc2358baeca134c825a7",
+        "09330c33f71147e83d192fc782cd1b4753111b173b3b05d22fa08086e3b0f712fcc7c71a557e2db966c3e9fa91746039",
+    ];
+    let sha512_256 = [
+        "53048e2681941ef99b2e29b76b4c7dabe4c2d0c634fc6d46e0e2f13107e7af23",
+        "3928e184fb8690f840da3988121d31be65cb9d3ef83ee6146feac861e19b563a",
+    ];
+    let sha512_224 = [
+        "4634270f707b6a54daae7530460842e20e37ed265ceee9a43e8924aa",
+        "23fec5bb94d60b23308192640b0c453335d664734fe40e7268674af9",
+    ];
+    for (i, msg) in msgs.iter().enumerate() {
+        let hex = |s: &str| Hex::decode_to_vec(s, None).unwrap();
+        assert_eq!(&Sha384::hash(msg)[..], &hex(sha384[i])[..]);
+        assert_eq!(&Sha512_256::hash(msg)[..], &hex(sha512_256[i])[..]);
+        assert_eq!(Sha512T::<32>::hash(msg), Sha512_256::hash(msg));
+        assert_eq!(&Sha512T::<28>::hash(msg)[..], &hex(sha512_224[i])[..]);
+
+        let mut h = Sha384::new();
+        for chunk in msg.chunks(5) {
+            h.update(chunk);
+        }
+        assert_eq!(h.finalize(), Sha384::hash(msg));
+    }
+    assert_eq!(Sha512T::<32>::new().0.state.0, IV_512_256);
+}
+
+#[test]
+fn test_hash_export() {
+    let msg = [0x42u8; 300];
+    for split in [0, 1, 127, 128, 129, 300].iter() {
+        let mut h = Hash::new();
+        h.update(&msg[..*split]);
+        let exported = h.export(b"key");
+        assert_eq!(
+            Hash::import(&exported, b"other key").err(),
+            Some(Error::TagMismatch)
+        );
+        let mut tampered = exported;
+        tampered[70] ^= 1;
+        assert_eq!(
+            Hash::import(&tampered, b"key").err(),
+            Some(Error::TagMismatch)
+        );
+        let mut h = Hash::import(&exported, b"key").unwrap();
+        h.update(&msg[*split..]);
+        assert_eq!(h.finalize(), Hash::hash(&msg[..]));
+    }
+}
+
+#[test]
+fn test_hmac_sha512() {
+    use ct_codecs::{Decoder, Hex};
+
+    // RFC 4231, test cases 1, 2 and 6
+    let tests: [(&[u8], &[u8], &str); 3] = [
+        (
+            &[0x0b; 20],
+            b"Hi There",
+            "87aa7cdea5ef619d4ff0b4241a1d6cb02379f4e2ce4ec2787ad0b30545e17cdedaa833b7d6b8a702038b274eaea3f4e4be9d914eeb61f1702e696c203a126854",
+        ),
+        (
+            b"Jefe",
+            b"what do ya want for nothing?",
+            "164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea2505549758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737",
+        ),
+        (
+            &[0xaa; 131],
+            b"Test Using Larger Than Block-Size Key - Hash Key First",
+            "80b24263c7c1a3ebb71493c1dd7be8b49b46d1f41b4aeec1121b013783f8f3526b56d037e05f2598bd0fd2215d6a1e5295e64f73f63f0aec8b915a985d786598",
+        ),
+    ];
+    for (key, msg, expected) in tests.iter() {
+        let expected = Hex::decode_to_vec(expected, None).unwrap();
+        assert_eq!(&Hmac::mac(key, msg)[..], &expected[..]);
+        let mut h = Hmac::new(key);
+        for chunk in msg.chunks(7) {
+            h.update(chunk);
+        }
+        h.clone().verify(&expected).unwrap();
+        assert_eq!(h.clone().verify(&expected[..32]), Err(Error::TagMismatch));
+        h.clone().verify_truncated(&expected[..32], 32).unwrap();
+        assert_eq!(
+            h.clone().verify_truncated(&expected[..32], 48),
+            Err(Error::TagMismatch)
+        );
+        assert_eq!(
+            h.clone().verify_truncated(&expected[..8], 8),
+            Err(Error::InvalidParameters)
+        );
+        let mut bad = expected.clone();
+        bad[63] ^= 1;
+        assert_eq!(h.verify(&bad), Err(Error::TagMismatch));
+    }
+}
+
+#[test]
+fn test_hkdf_sha512() {
+    use ct_codecs::{Decoder, Hex};
+
+    let ikm = [0x0bu8; 22];
+    let salt = Hex::decode_to_vec("000102030405060708090a0b0c", None).unwrap();
+    let info = Hex::decode_to_vec("f0f1f2f3f4f5f6f7f8f9", None).unwrap();
+    let prk = Hkdf::extract(&salt, ikm);
+    assert_eq!(
+        &prk[..],
+        &Hex::decode_to_vec(
+            "665799823737ded04a88e47e54a5890bb2c3d247c7a4254a8e61350723590a26c36238127d8661b88cf80ef802d57e2f7cebcf1e00e083848be19929c61b4237",
+            None
+        )
+        .unwrap()[..]
+    );
+    let mut okm = [0u8; 42];
+    Hkdf::expand(&mut okm, &prk, &info).unwrap();
+    assert_eq!(
+        &okm[..],
+        &Hex::decode_to_vec(
+            "832390086cda71fb47625bb5ceb168e4c8e26a1a16ed34d9fc7fe92c1481579338da362cb8d9f925d7cb",
+            None
+        )
+        .unwrap()[..]
+    );
+
+    let prk = Hkdf::extract([], ikm);
+    let mut okm = [0u8; 100];
+    Hkdf::expand(&mut okm, &prk, []).unwrap();
+    assert_eq!(
+        &okm[..],
+        &Hex::decode_to_vec(
+            "f5fa02b18298a72a8c23898a8703472c6eb179dc204c03425c970e3b164bf90fff22d04836d0e2343bacc4e7cb6045faaa698e0e3b3eb91331306def1db8319e8a699b5ee45ab993847dc4df75bde023692c8c0710a67a55123f10a8b2d8327f9eb138da",
+            None
+        )
+        .unwrap()[..]
+    );
+    let mut too_long = [0u8; Hkdf::MAX_OUTPUT_BYTES + 1];
+    assert_eq!(
+        Hkdf::expand(&mut too_long, &prk, []),
+        Err(Error::InvalidParameters)
+    );
+}
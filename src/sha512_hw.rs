@@ -0,0 +1,323 @@
+//! Hardware-accelerated SHA-512 compression functions.
+//!
+//! Backends are selected at runtime when the `std` feature is enabled, and
+//! at compile time (using the enabled target features) otherwise. When no
+//! backend is available, the portable implementation is used.
+
+#![allow(clippy::many_single_char_names)]
+
+use super::sha512::ROUND_CONSTANTS;
+
+/// Backends, in order of preference.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub(crate) enum Backend {
+    /// x86_64 SHA512 extensions.
+    #[cfg(target_arch = "x86_64")]
+    Sha512Ni,
+    /// AVX2 message schedule, scalar rounds.
+    #[cfg(target_arch = "x86_64")]
+    Avx2,
+    /// AArch64 SHA512 instructions.
+    #[cfg(target_arch = "aarch64")]
+    Sha512Ce,
+}
+
+impl Backend {
+    /// Returns the backends supported by the current CPU.
+    pub(crate) fn available() -> impl Iterator<Item = Backend> {
+        const BACKENDS: &[Backend] = &[
+            #[cfg(target_arch = "x86_64")]
+            Backend::Sha512Ni,
+            #[cfg(target_arch = "x86_64")]
+            Backend::Avx2,
+            #[cfg(target_arch = "aarch64")]
+            Backend::Sha512Ce,
+        ];
+        BACKENDS
+            .iter()
+            .copied()
+            .filter(|backend| backend.is_available())
+    }
+
+    fn is_available(self) -> bool {
+        match self {
+            #[cfg(all(target_arch = "x86_64", feature = "std"))]
+            Backend::Sha512Ni => {
+                std::is_x86_feature_detected!("sha512") && std::is_x86_feature_detected!("avx2")
+            }
+            #[cfg(all(target_arch = "x86_64", not(feature = "std")))]
+            Backend::Sha512Ni => cfg!(all(target_feature = "sha512", target_feature = "avx2")),
+            #[cfg(all(target_arch = "x86_64", feature = "std"))]
+            Backend::Avx2 => std::is_x86_feature_detected!("avx2"),
+            #[cfg(all(target_arch = "x86_64", not(feature = "std")))]
+            Backend::Avx2 => cfg!(target_feature = "avx2"),
+            #[cfg(all(target_arch = "aarch64", feature = "std"))]
+            Backend::Sha512Ce => std::arch::is_aarch64_feature_detected!("sha3"),
+            #[cfg(all(target_arch = "aarch64", not(feature = "std")))]
+            Backend::Sha512Ce => cfg!(target_feature = "sha3"),
+        }
+    }
+
+    /// Processes all the complete 128-byte blocks of `input`, and returns
+    /// the number of remaining bytes.
+    /// The backend must be available.
+    pub(crate) fn blocks(self, state: &mut [u64; 8], input: &[u8]) -> usize {
+        debug_assert!(self.is_available());
+        let blocks = input.chunks_exact(128);
+        let remaining = blocks.remainder().len();
+        // SAFETY: the required CPU features have been detected.
+        unsafe {
+            match self {
+                #[cfg(target_arch = "x86_64")]
+                Backend::Sha512Ni => x86_64::blocks_sha512ni(state, blocks),
+                #[cfg(target_arch = "x86_64")]
+                Backend::Avx2 => x86_64::blocks_avx2(state, blocks),
+                #[cfg(target_arch = "aarch64")]
+                Backend::Sha512Ce => aarch64::blocks_sha512ce(state, blocks),
+            }
+        }
+        remaining
+    }
+}
+
+/// Processes all the complete blocks of `input` with the best available
+/// backend, returning the number of remaining bytes, or `None` if no
+/// backend is available.
+pub(crate) fn blocks(state: &mut [u64; 8], input: &[u8]) -> Option<usize> {
+    Backend::available()
+        .next()
+        .map(|backend| backend.blocks(state, input))
+}
+
+#[cfg(target_arch = "x86_64")]
+mod x86_64 {
+    use core::arch::x86_64::*;
+    use core::slice::ChunksExact;
+
+    use super::ROUND_CONSTANTS;
+
+    #[inline(always)]
+    unsafe fn load_be(input: &[u8], mask: __m256i) -> __m256i {
+        _mm256_shuffle_epi8(_mm256_loadu_si256(input.as_ptr() as *const __m256i), mask)
+    }
+
+    #[inline(always)]
+    unsafe fn bswap_mask() -> __m256i {
+        _mm256_set_epi64x(
+            0x08090a0b0c0d0e0f,
+            0x0001020304050607,
+            0x08090a0b0c0d0e0f,
+            0x0001020304050607,
+        )
+    }
+
+    /// Returns `[x[1], x[2], x[3], y[0]]`.
+    #[inline(always)]
+    unsafe fn shift_in(x: __m256i, y: __m256i) -> __m256i {
+        _mm256_alignr_epi8(_mm256_permute2x128_si256(x, y, 0x21), x, 8)
+    }
+
+    #[inline(always)]
+    unsafe fn wk(w: __m256i, i: usize) -> __m256i {
+        _mm256_add_epi64(
+            w,
+            _mm256_loadu_si256(ROUND_CONSTANTS[i..].as_ptr() as *const __m256i),
+        )
+    }
+
+    #[target_feature(enable = "sha512,avx2")]
+    pub(super) unsafe fn blocks_sha512ni(state: &mut [u64; 8], blocks: ChunksExact<'_, u8>) {
+        let mask = bswap_mask();
+        let mut abef = _mm256_set_epi64x(
+            state[0] as i64,
+            state[1] as i64,
+            state[4] as i64,
+            state[5] as i64,
+        );
+        let mut cdgh = _mm256_set_epi64x(
+            state[2] as i64,
+            state[3] as i64,
+            state[6] as i64,
+            state[7] as i64,
+        );
+        for block in blocks {
+            let (abef_saved, cdgh_saved) = (abef, cdgh);
+            let mut m = [
+                load_be(&block[0..], mask),
+                load_be(&block[32..], mask),
+                load_be(&block[64..], mask),
+                load_be(&block[96..], mask),
+            ];
+            for i in 0..20 {
+                let w = if i < 4 {
+                    m[i]
+                } else {
+                    let t = _mm256_sha512msg1_epi64(m[0], _mm256_castsi256_si128(m[1]));
+                    let t = _mm256_add_epi64(t, shift_in(m[2], m[3]));
+                    let w = _mm256_sha512msg2_epi64(t, m[3]);
+                    m = [m[1], m[2], m[3], w];
+                    w
+                };
+                let wk = wk(w, i * 4);
+                let t = _mm256_sha512rnds2_epi64(cdgh, abef, _mm256_castsi256_si128(wk));
+                cdgh = abef;
+                abef = t;
+                let t = _mm256_sha512rnds2_epi64(cdgh, abef, _mm256_extracti128_si256(wk, 1));
+                cdgh = abef;
+                abef = t;
+            }
+            abef = _mm256_add_epi64(abef, abef_saved);
+            cdgh = _mm256_add_epi64(cdgh, cdgh_saved);
+        }
+        let mut out = [[0u64; 4]; 2];
+        _mm256_storeu_si256(out[0].as_mut_ptr() as *mut __m256i, abef);
+        _mm256_storeu_si256(out[1].as_mut_ptr() as *mut __m256i, cdgh);
+        *state = [
+            out[0][3], out[0][2], out[1][3], out[1][2], out[0][1], out[0][0], out[1][1], out[1][0],
+        ];
+    }
+
+    macro_rules! ror {
+        ($x:expr, $n:literal) => {
+            _mm256_or_si256(_mm256_srli_epi64($x, $n), _mm256_slli_epi64($x, 64 - $n))
+        };
+    }
+
+    #[inline(always)]
+    unsafe fn sigma0(x: __m256i) -> __m256i {
+        _mm256_xor_si256(
+            _mm256_xor_si256(ror!(x, 1), ror!(x, 8)),
+            _mm256_srli_epi64(x, 7),
+        )
+    }
+
+    #[inline(always)]
+    unsafe fn sigma1(x: __m256i) -> __m256i {
+        _mm256_xor_si256(
+            _mm256_xor_si256(ror!(x, 19), ror!(x, 61)),
+            _mm256_srli_epi64(x, 6),
+        )
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn blocks_avx2(state: &mut [u64; 8], blocks: ChunksExact<'_, u8>) {
+        let mask = bswap_mask();
+        let mut t = *state;
+        for block in blocks {
+            let mut m = [
+                load_be(&block[0..], mask),
+                load_be(&block[32..], mask),
+                load_be(&block[64..], mask),
+                load_be(&block[96..], mask),
+            ];
+            let mut wk_ = [0u64; 4];
+            for i in 0..20 {
+                let w = if i < 4 {
+                    m[i]
+                } else {
+                    let mut w = _mm256_add_epi64(m[0], sigma0(shift_in(m[0], m[1])));
+                    w = _mm256_add_epi64(w, shift_in(m[2], m[3]));
+                    // w[t] and w[t+1] depend on w[t-2] and w[t-1]
+                    let s = sigma1(m[3]);
+                    w = _mm256_add_epi64(w, _mm256_permute2x128_si256(s, s, 0x81));
+                    // w[t+2] and w[t+3] depend on w[t] and w[t+1]
+                    let s = sigma1(w);
+                    w = _mm256_add_epi64(w, _mm256_permute2x128_si256(s, s, 0x08));
+                    m = [m[1], m[2], m[3], w];
+                    w
+                };
+                _mm256_storeu_si256(wk_.as_mut_ptr() as *mut __m256i, wk(w, i * 4));
+                super::rounds4(&mut t, &wk_);
+            }
+            for (x, y) in t.iter_mut().zip(state.iter()) {
+                *x = x.wrapping_add(*y);
+            }
+            *state = t;
+        }
+    }
+}
+
+#[cfg(target_arch = "aarch64")]
+mod aarch64 {
+    use core::arch::aarch64::*;
+    use core::slice::ChunksExact;
+
+    use super::ROUND_CONSTANTS;
+
+    #[inline(always)]
+    unsafe fn load_be(input: &[u8]) -> uint64x2_t {
+        vreinterpretq_u64_u8(vrev64q_u8(vld1q_u8(input.as_ptr())))
+    }
+
+    #[target_feature(enable = "sha3")]
+    pub(super) unsafe fn blocks_sha512ce(state: &mut [u64; 8], blocks: ChunksExact<'_, u8>) {
+        let mut v = [
+            vld1q_u64(state[0..].as_ptr()),
+            vld1q_u64(state[2..].as_ptr()),
+            vld1q_u64(state[4..].as_ptr()),
+            vld1q_u64(state[6..].as_ptr()),
+        ];
+        for block in blocks {
+            let saved = v;
+            let mut s = [vdupq_n_u64(0); 8];
+            for (i, x) in s.iter_mut().enumerate() {
+                *x = load_be(&block[i * 16..]);
+            }
+            for r in 0..40 {
+                let i = r % 8;
+                if r >= 8 {
+                    s[i] = vsha512su1q_u64(
+                        vsha512su0q_u64(s[i], s[(i + 1) % 8]),
+                        s[(i + 7) % 8],
+                        vextq_u64(s[(i + 4) % 8], s[(i + 5) % 8], 1),
+                    );
+                }
+                // The roles of the state vectors rotate every two rounds.
+                let (ab, cd, ef, gh) = match r % 4 {
+                    0 => (0, 1, 2, 3),
+                    1 => (3, 0, 1, 2),
+                    2 => (2, 3, 0, 1),
+                    _ => (1, 2, 3, 0),
+                };
+                let wk = vaddq_u64(s[i], vld1q_u64(ROUND_CONSTANTS[r * 2..].as_ptr()));
+                let sum = vaddq_u64(vextq_u64(wk, wk, 1), v[gh]);
+                let t = vsha512hq_u64(sum, vextq_u64(v[ef], v[gh], 1), vextq_u64(v[cd], v[ef], 1));
+                v[gh] = vsha512h2q_u64(t, v[cd], v[ab]);
+                v[cd] = vaddq_u64(v[cd], t);
+            }
+            for (x, y) in v.iter_mut().zip(saved.iter()) {
+                *x = vaddq_u64(*x, *y);
+            }
+        }
+        vst1q_u64(state[0..].as_mut_ptr(), v[0]);
+        vst1q_u64(state[2..].as_mut_ptr(), v[1]);
+        vst1q_u64(state[4..].as_mut_ptr(), v[2]);
+        vst1q_u64(state[6..].as_mut_ptr(), v[3]);
+    }
+}
+
+/// Four scalar rounds, given the message schedule with the round constants
+/// already added.
+#[cfg(target_arch = "x86_64")]
+#[inline(always)]
+fn rounds4(state: &mut [u64; 8], wk: &[u64; 4]) {
+    macro_rules! round {
+        ($a:ident, $b:ident, $c:ident, $d:ident, $e:ident, $f:ident, $g:ident, $h:ident, $wk:expr) => {
+            $h = $h
+                .wrapping_add($e.rotate_right(14) ^ $e.rotate_right(18) ^ $e.rotate_right(41))
+                .wrapping_add(($e & $f) ^ (!$e & $g))
+                .wrapping_add($wk);
+            $d = $d.wrapping_add($h);
+            $h = $h
+                .wrapping_add($a.rotate_right(28) ^ $a.rotate_right(34) ^ $a.rotate_right(39))
+                .wrapping_add(($a & $b) ^ ($a & $c) ^ ($b & $c));
+        };
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *state;
+    round!(a, b, c, d, e, f, g, h, wk[0]);
+    round!(h, a, b, c, d, e, f, g, wk[1]);
+    round!(g, h, a, b, c, d, e, f, wk[2]);
+    round!(f, g, h, a, b, c, d, e, wk[3]);
+    *state = [e, f, g, h, a, b, c, d];
+}
@@ -0,0 +1,505 @@
+//! Multi-buffer SHA-512.
+//!
+//! Independent messages are hashed in parallel, one per SIMD lane: 4 lanes
+//! with AVX2, 2 lanes with NEON and WebAssembly SIMD128. When a message is
+//! complete, its lane is refilled with the next one, so that messages of
+//! different lengths keep all the lanes busy.
+//!
+//! Single-lane SHA-512 instructions are faster than the multi-buffer code,
+//! so when they are available, messages are hashed one by one instead.
+
+#![allow(non_snake_case, clippy::many_single_char_names)]
+
+use super::sha512;
+#[cfg(any(
+    target_arch = "x86_64",
+    target_arch = "aarch64",
+    all(target_arch = "wasm32", target_feature = "simd128")
+))]
+use super::sha512::ROUND_CONSTANTS;
+
+/// Operations on a vector of 64-bit lanes.
+#[cfg(any(
+    target_arch = "x86_64",
+    target_arch = "aarch64",
+    all(target_arch = "wasm32", target_feature = "simd128")
+))]
+trait Lanes: Copy {
+    unsafe fn load(x: &[u64]) -> Self;
+    unsafe fn store(self, out: &mut [u64]);
+    unsafe fn splat(x: u64) -> Self;
+    unsafe fn add(self, y: Self) -> Self;
+    unsafe fn xor(self, y: Self) -> Self;
+    unsafe fn and(self, y: Self) -> Self;
+    /// Computes `!self & y`.
+    unsafe fn andnot(self, y: Self) -> Self;
+    unsafe fn shr<const S: i32>(self) -> Self;
+    /// Rotates by `R` bits to the right. `L` must be `64 - R`.
+    unsafe fn ror<const R: i32, const L: i32>(self) -> Self;
+}
+
+/// Compresses one block per lane.
+#[cfg(any(
+    target_arch = "x86_64",
+    target_arch = "aarch64",
+    all(target_arch = "wasm32", target_feature = "simd128")
+))]
+#[inline(always)]
+unsafe fn compress<V: Lanes, const N: usize>(states: &mut [[u64; 8]; N], blocks: &[&[u8]; N]) {
+    let mut x = [0u64; N];
+    let mut w = [V::splat(0); 16];
+    for (i, w) in w.iter_mut().enumerate() {
+        for (x, block) in x.iter_mut().zip(blocks.iter()) {
+            let mut word = [0u8; 8];
+            word.copy_from_slice(&block[i * 8..][..8]);
+            *x = u64::from_be_bytes(word);
+        }
+        *w = V::load(&x);
+    }
+    let mut s = [V::splat(0); 8];
+    for (i, s) in s.iter_mut().enumerate() {
+        for (x, state) in x.iter_mut().zip(states.iter()) {
+            *x = state[i];
+        }
+        *s = V::load(&x);
+    }
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = s;
+
+    macro_rules! round {
+        ($a:ident, $b:ident, $c:ident, $d:ident, $e:ident, $f:ident, $g:ident, $h:ident, $r:expr, $i:expr) => {
+            if $r >= 16 {
+                let w1 = w[($i + 14) & 15];
+                let w15 = w[($i + 1) & 15];
+                let sigma1 = w1.ror::<19, 45>().xor(w1.ror::<61, 3>()).xor(w1.shr::<6>());
+                let sigma0 = w15
+                    .ror::<1, 63>()
+                    .xor(w15.ror::<8, 56>())
+                    .xor(w15.shr::<7>());
+                w[$i] = w[$i].add(sigma1).add(w[($i + 9) & 15]).add(sigma0);
+            }
+            let Sigma1 = $e
+                .ror::<14, 50>()
+                .xor($e.ror::<18, 46>())
+                .xor($e.ror::<41, 23>());
+            let ch = $e.and($f).xor($e.andnot($g));
+            $h = $h
+                .add(Sigma1)
+                .add(ch)
+                .add(V::splat(ROUND_CONSTANTS[$r + $i]))
+                .add(w[$i]);
+            $d = $d.add($h);
+            let Sigma0 = $a
+                .ror::<28, 36>()
+                .xor($a.ror::<34, 30>())
+                .xor($a.ror::<39, 25>());
+            let maj = $a.and($b).xor($a.and($c)).xor($b.and($c));
+            $h = $h.add(Sigma0).add(maj);
+        };
+    }
+
+    for r in (0..80).step_by(16) {
+        round!(a, b, c, d, e, f, g, h, r, 0);
+        round!(h, a, b, c, d, e, f, g, r, 1);
+        round!(g, h, a, b, c, d, e, f, r, 2);
+        round!(f, g, h, a, b, c, d, e, r, 3);
+        round!(e, f, g, h, a, b, c, d, r, 4);
+        round!(d, e, f, g, h, a, b, c, r, 5);
+        round!(c, d, e, f, g, h, a, b, r, 6);
+        round!(b, c, d, e, f, g, h, a, r, 7);
+        round!(a, b, c, d, e, f, g, h, r, 8);
+        round!(h, a, b, c, d, e, f, g, r, 9);
+        round!(g, h, a, b, c, d, e, f, r, 10);
+        round!(f, g, h, a, b, c, d, e, r, 11);
+        round!(e, f, g, h, a, b, c, d, r, 12);
+        round!(d, e, f, g, h, a, b, c, r, 13);
+        round!(c, d, e, f, g, h, a, b, r, 14);
+        round!(b, c, d, e, f, g, h, a, r, 15);
+    }
+
+    for (i, v) in [a, b, c, d, e, f, g, h].iter().enumerate() {
+        v.add(s[i]).store(&mut x);
+        for (state, x) in states.iter_mut().zip(x.iter()) {
+            state[i] = *x;
+        }
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+mod x86_64 {
+    use core::arch::x86_64::*;
+
+    use super::Lanes;
+
+    impl Lanes for __m256i {
+        #[inline(always)]
+        unsafe fn load(x: &[u64]) -> Self {
+            _mm256_loadu_si256(x[..4].as_ptr() as *const __m256i)
+        }
+
+        #[inline(always)]
+        unsafe fn store(self, out: &mut [u64]) {
+            _mm256_storeu_si256(out[..4].as_mut_ptr() as *mut __m256i, self)
+        }
+
+        #[inline(always)]
+        unsafe fn splat(x: u64) -> Self {
+            _mm256_set1_epi64x(x as i64)
+        }
+
+        #[inline(always)]
+        unsafe fn add(self, y: Self) -> Self {
+            _mm256_add_epi64(self, y)
+        }
+
+        #[inline(always)]
+        unsafe fn xor(self, y: Self) -> Self {
+            _mm256_xor_si256(self, y)
+        }
+
+        #[inline(always)]
+        unsafe fn and(self, y: Self) -> Self {
+            _mm256_and_si256(self, y)
+        }
+
+        #[inline(always)]
+        unsafe fn andnot(self, y: Self) -> Self {
+            _mm256_andnot_si256(self, y)
+        }
+
+        #[inline(always)]
+        unsafe fn shr<const S: i32>(self) -> Self {
+            _mm256_srli_epi64::<S>(self)
+        }
+
+        #[inline(always)]
+        unsafe fn ror<const R: i32, const L: i32>(self) -> Self {
+            _mm256_or_si256(_mm256_srli_epi64::<R>(self), _mm256_slli_epi64::<L>(self))
+        }
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn compress_avx2(states: &mut [[u64; 8]; 4], blocks: &[&[u8]; 4]) {
+        super::compress::<__m256i, 4>(states, blocks)
+    }
+}
+
+#[cfg(target_arch = "aarch64")]
+mod aarch64 {
+    use core::arch::aarch64::*;
+
+    use super::Lanes;
+
+    impl Lanes for uint64x2_t {
+        #[inline(always)]
+        unsafe fn load(x: &[u64]) -> Self {
+            vld1q_u64(x[..2].as_ptr())
+        }
+
+        #[inline(always)]
+        unsafe fn store(self, out: &mut [u64]) {
+            vst1q_u64(out[..2].as_mut_ptr(), self)
+        }
+
+        #[inline(always)]
+        unsafe fn splat(x: u64) -> Self {
+            vdupq_n_u64(x)
+        }
+
+        #[inline(always)]
+        unsafe fn add(self, y: Self) -> Self {
+            vaddq_u64(self, y)
+        }
+
+        #[inline(always)]
+        unsafe fn xor(self, y: Self) -> Self {
+            veorq_u64(self, y)
+        }
+
+        #[inline(always)]
+        unsafe fn and(self, y: Self) -> Self {
+            vandq_u64(self, y)
+        }
+
+        #[inline(always)]
+        unsafe fn andnot(self, y: Self) -> Self {
+            vbicq_u64(y, self)
+        }
+
+        #[inline(always)]
+        unsafe fn shr<const S: i32>(self) -> Self {
+            vshrq_n_u64::<S>(self)
+        }
+
+        #[inline(always)]
+        unsafe fn ror<const R: i32, const L: i32>(self) -> Self {
+            vsriq_n_u64::<R>(vshlq_n_u64::<L>(self), self)
+        }
+    }
+
+    #[target_feature(enable = "neon")]
+    pub(super) unsafe fn compress_neon(states: &mut [[u64; 8]; 2], blocks: &[&[u8]; 2]) {
+        super::compress::<uint64x2_t, 2>(states, blocks)
+    }
+}
+
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+mod wasm32 {
+    use core::arch::wasm32::*;
+
+    use super::Lanes;
+
+    impl Lanes for v128 {
+        #[inline(always)]
+        unsafe fn load(x: &[u64]) -> Self {
+            v128_load(x[..2].as_ptr() as *const v128)
+        }
+
+        #[inline(always)]
+        unsafe fn store(self, out: &mut [u64]) {
+            v128_store(out[..2].as_mut_ptr() as *mut v128, self)
+        }
+
+        #[inline(always)]
+        unsafe fn splat(x: u64) -> Self {
+            u64x2_splat(x)
+        }
+
+        #[inline(always)]
+        unsafe fn add(self, y: Self) -> Self {
+            i64x2_add(self, y)
+        }
+
+        #[inline(always)]
+        unsafe fn xor(self, y: Self) -> Self {
+            v128_xor(self, y)
+        }
+
+        #[inline(always)]
+        unsafe fn and(self, y: Self) -> Self {
+            v128_and(self, y)
+        }
+
+        #[inline(always)]
+        unsafe fn andnot(self, y: Self) -> Self {
+            v128_andnot(y, self)
+        }
+
+        #[inline(always)]
+        unsafe fn shr<const S: i32>(self) -> Self {
+            u64x2_shr(self, S as u32)
+        }
+
+        #[inline(always)]
+        unsafe fn ror<const R: i32, const L: i32>(self) -> Self {
+            v128_or(u64x2_shr(self, R as u32), u64x2_shl(self, L as u32))
+        }
+    }
+
+    pub(super) unsafe fn compress_simd128(states: &mut [[u64; 8]; 2], blocks: &[&[u8]; 2]) {
+        super::compress::<v128, 2>(states, blocks)
+    }
+}
+
+/// A message being hashed in a lane.
+#[cfg(any(
+    target_arch = "x86_64",
+    target_arch = "aarch64",
+    all(target_arch = "wasm32", target_feature = "simd128")
+))]
+struct Lane<'t> {
+    index: usize,
+    input: &'t [u8],
+    full_blocks: usize,
+    blocks: usize,
+    next: usize,
+    tail: [u8; 256],
+    state: [u64; 8],
+}
+
+#[cfg(any(
+    target_arch = "x86_64",
+    target_arch = "aarch64",
+    all(target_arch = "wasm32", target_feature = "simd128")
+))]
+impl<'t> Lane<'t> {
+    fn new(index: usize, input: &'t [u8]) -> Self {
+        let full_blocks = input.len() / 128;
+        let r = input.len() % 128;
+        let mut tail = [0u8; 256];
+        tail[..r].copy_from_slice(&input[full_blocks * 128..]);
+        tail[r] = 0x80;
+        let tail_len = if r < 112 { 128 } else { 256 };
+        let bits = input.len() as u64 * 8;
+        tail[tail_len - 8..tail_len].copy_from_slice(&bits.to_be_bytes());
+        Lane {
+            index,
+            input,
+            full_blocks,
+            blocks: full_blocks + tail_len / 128,
+            next: 0,
+            tail,
+            state: sha512::IV,
+        }
+    }
+
+    fn block(&self) -> &[u8] {
+        if self.next < self.full_blocks {
+            &self.input[self.next * 128..][..128]
+        } else {
+            &self.tail[(self.next - self.full_blocks) * 128..][..128]
+        }
+    }
+
+    fn is_done(&self) -> bool {
+        self.next == self.blocks
+    }
+
+    /// Hashes the remaining blocks without using other lanes.
+    fn finish(&mut self) {
+        if self.next < self.full_blocks {
+            sha512::compress_blocks(
+                &mut self.state,
+                &self.input[self.next * 128..self.full_blocks * 128],
+            );
+            self.next = self.full_blocks;
+        }
+        let tail_blocks = self.blocks - self.full_blocks;
+        sha512::compress_blocks(
+            &mut self.state,
+            &self.tail[(self.next - self.full_blocks) * 128..tail_blocks * 128],
+        );
+        self.next = self.blocks;
+    }
+
+    fn digest(&self) -> [u8; 64] {
+        let mut out = [0u8; 64];
+        for (chunk, word) in out.chunks_exact_mut(8).zip(self.state.iter()) {
+            chunk.copy_from_slice(&word.to_be_bytes());
+        }
+        out
+    }
+}
+
+#[cfg(any(
+    target_arch = "x86_64",
+    target_arch = "aarch64",
+    all(target_arch = "wasm32", target_feature = "simd128")
+))]
+type CompressFn<const N: usize> = unsafe fn(&mut [[u64; 8]; N], &[&[u8]; N]);
+
+/// Hashes `inputs` using `N` lanes.
+/// `compress` must be supported by the CPU.
+#[cfg(any(
+    target_arch = "x86_64",
+    target_arch = "aarch64",
+    all(target_arch = "wasm32", target_feature = "simd128")
+))]
+fn hash_lanes<T: AsRef<[u8]>, const N: usize>(
+    inputs: &[T],
+    out: &mut [[u8; 64]],
+    compress: CompressFn<N>,
+) {
+    const UNUSED: [u8; 128] = [0u8; 128];
+
+    let mut pending = inputs
+        .iter()
+        .enumerate()
+        .map(|(index, input)| Lane::new(index, input.as_ref()));
+    let mut lanes: [Option<Lane<'_>>; N] = [(); N].map(|_| pending.next());
+    while lanes.iter().filter(|lane| lane.is_some()).count() > 1 {
+        let mut states = [[0u64; 8]; N];
+        let mut blocks = [&UNUSED[..]; N];
+        for (i, lane) in lanes.iter().enumerate() {
+            if let Some(lane) = lane {
+                states[i] = lane.state;
+                blocks[i] = lane.block();
+            }
+        }
+        // SAFETY: the caller checked that `compress` is supported.
+        unsafe { compress(&mut states, &blocks) };
+        for (slot, state) in lanes.iter_mut().zip(states.iter()) {
+            if let Some(lane) = slot {
+                lane.state = *state;
+                lane.next += 1;
+                if lane.is_done() {
+                    out[lane.index] = lane.digest();
+                    *slot = pending.next();
+                }
+            }
+        }
+    }
+    for lane in lanes.iter_mut().flatten() {
+        lane.finish();
+        out[lane.index] = lane.digest();
+    }
+}
+
+/// Computes the SHA-512 hash of every input, using multiple lanes if
+/// possible.
+pub(crate) fn hash_many<T: AsRef<[u8]>>(inputs: &[T], out: &mut [[u8; 64]]) {
+    debug_assert_eq!(inputs.len(), out.len());
+    #[cfg(target_arch = "x86_64")]
+    {
+        use super::sha512_hw::Backend;
+
+        if Backend::available().next() == Some(Backend::Avx2) {
+            return hash_lanes::<T, 4>(inputs, out, x86_64::compress_avx2);
+        }
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        if super::sha512_hw::Backend::available().next().is_none() {
+            return hash_lanes::<T, 2>(inputs, out, aarch64::compress_neon);
+        }
+    }
+    #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+    {
+        return hash_lanes::<T, 2>(inputs, out, wasm32::compress_simd128);
+    }
+    #[allow(unreachable_code)]
+    for (input, out) in inputs.iter().zip(out.iter_mut()) {
+        *out = sha512::Hash::hash(input);
+    }
+}
+
+#[cfg(any(
+    target_arch = "x86_64",
+    target_arch = "aarch64",
+    all(target_arch = "wasm32", target_feature = "simd128")
+))]
+#[test]
+fn test_hash_lanes() {
+    let mut input = [0u8; 1000];
+    for (i, x) in input.iter_mut().enumerate() {
+        *x = (i * 7 + (i >> 8)) as u8;
+    }
+    let lens = [0, 1, 111, 112, 127, 128, 129, 1000, 5, 300, 239, 240, 17];
+    let inputs: [&[u8]; 13] = lens.map(|len| &input[..len]);
+    let mut expected = [[0u8; 64]; 13];
+    for (input, expected) in inputs.iter().zip(expected.iter_mut()) {
+        *expected = sha512::Hash::hash(input);
+    }
+
+    // SAFETY: no CPU features are required.
+    unsafe fn compress_scalar(states: &mut [[u64; 8]; 3], blocks: &[&[u8]; 3]) {
+        for (state, block) in states.iter_mut().zip(blocks.iter()) {
+            sha512::compress_blocks(state, block);
+        }
+    }
+    for n in 0..inputs.len() {
+        let mut out = [[0u8; 64]; 13];
+        hash_lanes::<_, 3>(&inputs[..n], &mut out[..n], compress_scalar);
+        assert_eq!(out[..n], expected[..n]);
+        hash_many(&inputs[..n], &mut out[..n]);
+        assert_eq!(out[..n], expected[..n]);
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    if super::sha512_hw::Backend::available()
+        .any(|backend| backend == super::sha512_hw::Backend::Avx2)
+    {
+        let mut out = [[0u8; 64]; 13];
+        hash_lanes::<_, 4>(&inputs, &mut out, x86_64::compress_avx2);
+        assert_eq!(out, expected);
+    }
+}
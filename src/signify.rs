@@ -0,0 +1,639 @@
+//! Support for OpenBSD signify public keys, secret keys and signatures.
+//!
+//! Signatures created in embedded mode (`signify -S -e`) can also be
+//! verified, including signed checksum files such as the `SHA256.sig` files
+//! shipped with OpenBSD releases.
+//! Encoding secret keys requires the `std` feature.
+
+use core::fmt;
+
+use ct_codecs::{Base64, Decoder, Encoder, Hex};
+
+use super::bcrypt_pbkdf::bcrypt_pbkdf;
+use super::common::*;
+use super::error::Error;
+use super::{sha256, sha512};
+use super::{KeyPair, PublicKey as EdPublicKey, Signature};
+
+const PKALG: [u8; 2] = *b"Ed";
+const KDFALG: [u8; 2] = *b"BK";
+
+const COMMENT_PREFIX: &str = "untrusted comment: ";
+const COMMENT_MAX_LEN: usize = 1024;
+const DEFAULT_SIG_COMMENT: &str = "signature from signify secret key";
+
+/// Number of bytes in a key identifier.
+pub const KEYNUM_BYTES: usize = 8;
+
+/// Default number of `bcrypt_pbkdf` rounds used to encrypt secret keys.
+pub const KDF_ROUNDS: u32 = 42;
+
+const SALT_BYTES: usize = 16;
+const CHECKSUM_BYTES: usize = 8;
+const PK_BYTES: usize = 2 + KEYNUM_BYTES + EdPublicKey::BYTES;
+const SIG_BYTES: usize = 2 + KEYNUM_BYTES + Signature::BYTES;
+const SK_BYTES: usize = 2 + 2 + 4 + SALT_BYTES + CHECKSUM_BYTES + KEYNUM_BYTES + 64;
+
+/// Splits a file into its untrusted comment, its base64-encoded payload, and
+/// whatever follows (the message, in embedded mode).
+fn parse_file(text: &str) -> Result<(&str, &str, &str), Error> {
+    let text = text.strip_prefix(COMMENT_PREFIX).ok_or(Error::ParseError)?;
+    let (comment, rest) = split_line(text)?;
+    if comment.len() > COMMENT_MAX_LEN {
+        return Err(Error::ParseError);
+    }
+    let (b64, rest) = split_line(rest)?;
+    Ok((comment, b64, rest))
+}
+
+fn split_line(text: &str) -> Result<(&str, &str), Error> {
+    let eol = text.find('\n').ok_or(Error::ParseError)?;
+    Ok((&text[..eol], &text[eol + 1..]))
+}
+
+fn decode_base64<const N: usize>(b64: &str) -> Result<[u8; N], Error> {
+    let mut bin = [0u8; N];
+    let len = Base64::decode(&mut bin, b64, None)
+        .map_err(|_| Error::ParseError)?
+        .len();
+    if len != N || bin[0..2] != PKALG {
+        return Err(Error::ParseError);
+    }
+    Ok(bin)
+}
+
+/// Writes a comment and `bin` encoded as base64 to a formatter.
+fn write_file(f: &mut fmt::Formatter<'_>, comment: &str, bin: &[u8]) -> fmt::Result {
+    let mut b64 = [0u8; 144];
+    let b64 = Base64::encode(&mut b64, bin).map_err(|_| fmt::Error)?;
+    writeln!(f, "{}{}", COMMENT_PREFIX, comment)?;
+    f.write_str(core::str::from_utf8(b64).map_err(|_| fmt::Error)?)?;
+    writeln!(f)
+}
+
+/// A signify public key.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct PublicKey {
+    keynum: [u8; KEYNUM_BYTES],
+    pk: EdPublicKey,
+}
+
+impl PublicKey {
+    /// Creates a signify public key from an Ed25519 public key and a key
+    /// identifier.
+    pub fn new(pk: EdPublicKey, keynum: [u8; KEYNUM_BYTES]) -> Self {
+        PublicKey { keynum, pk }
+    }
+
+    /// Returns the key identifier.
+    pub fn keynum(&self) -> &[u8; KEYNUM_BYTES] {
+        &self.keynum
+    }
+
+    /// Returns the Ed25519 public key.
+    pub fn key(&self) -> &EdPublicKey {
+        &self.pk
+    }
+
+    /// Imports a public key from the content of a `.pub` file.
+    pub fn decode(text: &str) -> Result<Self, Error> {
+        let (_comment, b64, _) = parse_file(text)?;
+        let bin: [u8; PK_BYTES] = decode_base64(b64)?;
+        let mut keynum = [0u8; KEYNUM_BYTES];
+        keynum.copy_from_slice(&bin[2..10]);
+        let pk = EdPublicKey::from_slice(&bin[10..])?;
+        Ok(PublicKey { keynum, pk })
+    }
+
+    /// Verifies a detached signature.
+    ///
+    /// Signatures created by a different key are rejected without being
+    /// checked.
+    pub fn verify(&self, message: impl AsRef<[u8]>, sig: &SignatureBox<'_>) -> Result<(), Error> {
+        if sig.keynum != self.keynum {
+            return Err(Error::InvalidPublicKey);
+        }
+        self.pk.verify(message, &sig.signature)
+    }
+
+    /// Verifies a file signed in embedded mode, and returns the message.
+    pub fn verify_embedded<'a>(&self, text: &'a str) -> Result<&'a str, Error> {
+        let (sig, message) = SignatureBox::decode_embedded(text)?;
+        self.verify(message, &sig)?;
+        Ok(message)
+    }
+
+    /// Verifies a checksum file signed in embedded mode, such as the
+    /// `SHA256.sig` files of OpenBSD releases.
+    pub fn verify_checksums<'a>(&self, text: &'a str) -> Result<Checksums<'a>, Error> {
+        Checksums::new(self.verify_embedded(text)?)
+    }
+}
+
+impl fmt::Display for PublicKey {
+    /// Formats the public key as the content of a `.pub` file.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut bin = [0u8; PK_BYTES];
+        bin[0..2].copy_from_slice(&PKALG);
+        bin[2..10].copy_from_slice(&self.keynum);
+        bin[10..].copy_from_slice(&self.pk[..]);
+        write_file(f, "signify public key", &bin)
+    }
+}
+
+/// A signify signature, along with its untrusted comment.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SignatureBox<'a> {
+    untrusted_comment: &'a str,
+    keynum: [u8; KEYNUM_BYTES],
+    signature: Signature,
+}
+
+impl<'a> SignatureBox<'a> {
+    /// Parses the content of a `.sig` file.
+    pub fn decode(text: &'a str) -> Result<Self, Error> {
+        Self::decode_embedded(text).map(|(sig, _)| sig)
+    }
+
+    /// Parses a file signed in embedded mode, and returns the signature along
+    /// with the (not yet verified) message that follows it.
+    pub fn decode_embedded(text: &'a str) -> Result<(Self, &'a str), Error> {
+        let (untrusted_comment, b64, message) = parse_file(text)?;
+        let bin: [u8; SIG_BYTES] = decode_base64(b64)?;
+        let mut keynum = [0u8; KEYNUM_BYTES];
+        keynum.copy_from_slice(&bin[2..10]);
+        let sig = SignatureBox {
+            untrusted_comment,
+            keynum,
+            signature: Signature::from_slice(&bin[10..])?,
+        };
+        Ok((sig, message))
+    }
+
+    /// Returns the untrusted comment.
+    pub fn untrusted_comment(&self) -> &'a str {
+        self.untrusted_comment
+    }
+
+    /// Returns the identifier of the key that created the signature.
+    pub fn keynum(&self) -> &[u8; KEYNUM_BYTES] {
+        &self.keynum
+    }
+
+    /// Returns the Ed25519 signature.
+    pub fn signature(&self) -> &Signature {
+        &self.signature
+    }
+}
+
+impl fmt::Display for SignatureBox<'_> {
+    /// Formats the signature as the content of a `.sig` file.
+    /// Appending the message produces an embedded signature.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut bin = [0u8; SIG_BYTES];
+        bin[0..2].copy_from_slice(&PKALG);
+        bin[2..10].copy_from_slice(&self.keynum);
+        bin[10..].copy_from_slice(&self.signature[..]);
+        write_file(f, self.untrusted_comment, &bin)
+    }
+}
+
+/// Parameters used to encrypt a secret key.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct KdfParams {
+    /// Salt for the key derivation function.
+    pub salt: [u8; SALT_BYTES],
+    /// Number of `bcrypt_pbkdf` rounds.
+    pub rounds: u32,
+}
+
+impl KdfParams {
+    /// Creates key derivation parameters.
+    pub fn new(salt: [u8; SALT_BYTES], rounds: u32) -> Self {
+        KdfParams { salt, rounds }
+    }
+}
+
+#[cfg(feature = "random")]
+impl Default for KdfParams {
+    /// Generates a random salt, and uses the default number of rounds.
+    fn default() -> Self {
+        let mut salt = [0u8; SALT_BYTES];
+        getrandom::fill(&mut salt).expect("RNG failure");
+        KdfParams::new(salt, KDF_ROUNDS)
+    }
+}
+
+/// A signify secret key.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SecretKey {
+    keynum: [u8; KEYNUM_BYTES],
+    kp: KeyPair,
+}
+
+impl SecretKey {
+    /// Creates a signify secret key from an Ed25519 key pair and a key
+    /// identifier.
+    pub fn new(kp: KeyPair, keynum: [u8; KEYNUM_BYTES]) -> Self {
+        SecretKey { keynum, kp }
+    }
+
+    /// Generates a new secret key with a random key identifier.
+    #[cfg(feature = "random")]
+    pub fn generate() -> Self {
+        let mut keynum = [0u8; KEYNUM_BYTES];
+        getrandom::fill(&mut keynum).expect("RNG failure");
+        SecretKey::new(KeyPair::generate(), keynum)
+    }
+
+    /// Returns the key identifier.
+    pub fn keynum(&self) -> &[u8; KEYNUM_BYTES] {
+        &self.keynum
+    }
+
+    /// Returns the Ed25519 key pair.
+    pub fn key_pair(&self) -> &KeyPair {
+        &self.kp
+    }
+
+    /// Returns the public counterpart of the secret key.
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey::new(self.kp.pk, self.keynum)
+    }
+
+    fn checksum(&self) -> [u8; CHECKSUM_BYTES] {
+        let mut checksum = [0u8; CHECKSUM_BYTES];
+        checksum.copy_from_slice(&sha512::Hash::hash(&self.kp.sk[..])[..CHECKSUM_BYTES]);
+        checksum
+    }
+
+    /// Computes the key that the secret key is XORed with.
+    fn xor_key(password: Option<&str>, kdf: &KdfParams) -> Result<[u8; 64], Error> {
+        let mut xor_key = [0u8; 64];
+        if kdf.rounds > 0 {
+            let password = password.ok_or(Error::InvalidSecretKey)?;
+            bcrypt_pbkdf(password.as_bytes(), &kdf.salt, kdf.rounds, &mut xor_key)?;
+        }
+        Ok(xor_key)
+    }
+
+    /// Imports a secret key from the content of a `.sec` file.
+    ///
+    /// A password is required if the key is encrypted, and is ignored
+    /// otherwise.
+    pub fn decode(text: &str, password: Option<&str>) -> Result<Self, Error> {
+        let (_comment, b64, _) = parse_file(text)?;
+        let mut bin: [u8; SK_BYTES] = decode_base64(b64)?;
+        let res = Self::decode_bin(&mut bin, password);
+        Mem::wipe(&mut bin);
+        res
+    }
+
+    fn decode_bin(bin: &mut [u8; SK_BYTES], password: Option<&str>) -> Result<Self, Error> {
+        if bin[2..4] != KDFALG {
+            return Err(Error::InvalidSecretKey);
+        }
+        let mut rounds = [0u8; 4];
+        rounds.copy_from_slice(&bin[4..8]);
+        let mut salt = [0u8; SALT_BYTES];
+        salt.copy_from_slice(&bin[8..24]);
+        let kdf = KdfParams::new(salt, u32::from_be_bytes(rounds));
+        let mut xor_key = Self::xor_key(password, &kdf)?;
+        for (x, k) in bin[40..].iter_mut().zip(xor_key.iter()) {
+            *x ^= k;
+        }
+        Mem::wipe(&mut xor_key);
+        let mut keynum = [0u8; KEYNUM_BYTES];
+        keynum.copy_from_slice(&bin[32..40]);
+        let sk = SecretKey {
+            keynum,
+            kp: KeyPair::from_slice(&bin[40..])?,
+        };
+        if !Mem::ct_eq(&sk.checksum(), &bin[24..32]) {
+            return Err(Error::InvalidSecretKey);
+        }
+        sk.kp.validate().map_err(|_| Error::InvalidSecretKey)?;
+        Ok(sk)
+    }
+
+    /// Computes a signature of `message`.
+    ///
+    /// A default untrusted comment is used if `untrusted_comment` is `None`.
+    pub fn sign<'a>(
+        &self,
+        message: impl AsRef<[u8]>,
+        untrusted_comment: Option<&'a str>,
+    ) -> SignatureBox<'a> {
+        SignatureBox {
+            untrusted_comment: untrusted_comment.unwrap_or(DEFAULT_SIG_COMMENT),
+            keynum: self.keynum,
+            signature: self.kp.sk.sign(message, None),
+        }
+    }
+
+    #[cfg(feature = "std")]
+    fn encode_with(&self, password: Option<&str>, kdf: &KdfParams) -> Result<String, Error> {
+        let mut bin = [0u8; SK_BYTES];
+        bin[0..2].copy_from_slice(&PKALG);
+        bin[2..4].copy_from_slice(&KDFALG);
+        bin[4..8].copy_from_slice(&kdf.rounds.to_be_bytes());
+        bin[8..24].copy_from_slice(&kdf.salt);
+        bin[24..32].copy_from_slice(&self.checksum());
+        bin[32..40].copy_from_slice(&self.keynum);
+        let mut xor_key = Self::xor_key(password, kdf)?;
+        for ((x, s), k) in bin[40..]
+            .iter_mut()
+            .zip(self.kp.sk.iter())
+            .zip(xor_key.iter())
+        {
+            *x = s ^ k;
+        }
+        Mem::wipe(&mut xor_key);
+        let b64 = Base64::encode_to_string(&bin[..]).unwrap();
+        Mem::wipe(&mut bin);
+        Ok(format!("{}signify secret key\n{}\n", COMMENT_PREFIX, b64))
+    }
+
+    /// Exports the secret key as an unencrypted `.sec` file.
+    #[cfg(feature = "std")]
+    pub fn encode(&self) -> String {
+        self.encode_with(None, &KdfParams::new([0u8; SALT_BYTES], 0))
+            .unwrap()
+    }
+
+    /// Exports the secret key as a `.sec` file, encrypted with a password.
+    #[cfg(feature = "std")]
+    pub fn encode_encrypted(&self, password: &str, kdf: &KdfParams) -> Result<String, Error> {
+        if kdf.rounds == 0 {
+            return Err(Error::InvalidParameters);
+        }
+        self.encode_with(Some(password), kdf)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for SecretKey {
+    fn zeroize(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.kp)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for SecretKey {}
+
+/// A hash function used in a checksum file.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum ChecksumAlgorithm {
+    /// SHA-256
+    Sha256,
+    /// SHA-512
+    Sha512,
+}
+
+impl ChecksumAlgorithm {
+    fn digest_len(self) -> usize {
+        match self {
+            ChecksumAlgorithm::Sha256 => 32,
+            ChecksumAlgorithm::Sha512 => 64,
+        }
+    }
+}
+
+/// An entry of a checksum file, in the BSD `ALG (file name) = digest` format.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct ChecksumEntry<'a> {
+    algorithm: ChecksumAlgorithm,
+    file_name: &'a str,
+    digest: [u8; 64],
+}
+
+impl<'a> ChecksumEntry<'a> {
+    fn parse(line: &'a str) -> Result<Self, Error> {
+        let eoa = line.find(" (").ok_or(Error::ParseError)?;
+        let algorithm = match &line[..eoa] {
+            "SHA256" => ChecksumAlgorithm::Sha256,
+            "SHA512" => ChecksumAlgorithm::Sha512,
+            _ => return Err(Error::ParseError),
+        };
+        let rest = &line[eoa + 2..];
+        let eon = rest.rfind(") = ").ok_or(Error::ParseError)?;
+        let (file_name, hex) = (&rest[..eon], &rest[eon + 4..]);
+        let mut digest = [0u8; 64];
+        let len = Hex::decode(&mut digest, hex, None)
+            .map_err(|_| Error::ParseError)?
+            .len();
+        if len != algorithm.digest_len() {
+            return Err(Error::ParseError);
+        }
+        Ok(ChecksumEntry {
+            algorithm,
+            file_name,
+            digest,
+        })
+    }
+
+    /// Returns the hash function.
+    pub fn algorithm(&self) -> ChecksumAlgorithm {
+        self.algorithm
+    }
+
+    /// Returns the file name.
+    pub fn file_name(&self) -> &'a str {
+        self.file_name
+    }
+
+    /// Returns the expected digest.
+    pub fn digest(&self) -> &[u8] {
+        &self.digest[..self.algorithm.digest_len()]
+    }
+
+    /// Creates a state to check the content of the file incrementally.
+    pub fn verifier(&self) -> ChecksumVerifier {
+        let hasher = match self.algorithm {
+            ChecksumAlgorithm::Sha256 => Hasher::Sha256(sha256::Hash::new()),
+            ChecksumAlgorithm::Sha512 => Hasher::Sha512(sha512::Hash::new()),
+        };
+        ChecksumVerifier {
+            hasher,
+            digest: self.digest,
+        }
+    }
+
+    /// Checks the content of the file.
+    pub fn check(&self, content: impl AsRef<[u8]>) -> Result<(), Error> {
+        let mut st = self.verifier();
+        st.absorb(content);
+        st.verify()
+    }
+}
+
+#[derive(Copy, Clone)]
+enum Hasher {
+    Sha256(sha256::Hash),
+    Sha512(sha512::Hash),
+}
+
+/// The state of a streaming checksum verification operation.
+#[derive(Copy, Clone)]
+pub struct ChecksumVerifier {
+    hasher: Hasher,
+    digest: [u8; 64],
+}
+
+impl ChecksumVerifier {
+    /// Appends data to the content being checked.
+    pub fn absorb(&mut self, chunk: impl AsRef<[u8]>) {
+        match &mut self.hasher {
+            Hasher::Sha256(h) => h.update(chunk),
+            Hasher::Sha512(h) => h.update(chunk),
+        }
+    }
+
+    /// Compares the digest of the content with the expected one.
+    pub fn verify(&self) -> Result<(), Error> {
+        let ok = match self.hasher {
+            Hasher::Sha256(h) => h.finalize()[..] == self.digest[..32],
+            Hasher::Sha512(h) => h.finalize() == self.digest,
+        };
+        if !ok {
+            return Err(Error::ChecksumMismatch);
+        }
+        Ok(())
+    }
+}
+
+/// The verified content of a signed checksum file.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Checksums<'a> {
+    text: &'a str,
+}
+
+impl<'a> Checksums<'a> {
+    fn new(text: &'a str) -> Result<Self, Error> {
+        for line in text.lines().filter(|line| !line.is_empty()) {
+            ChecksumEntry::parse(line)?;
+        }
+        Ok(Checksums { text })
+    }
+
+    /// Returns the entries of the file.
+    pub fn entries(&self) -> impl Iterator<Item = ChecksumEntry<'a>> {
+        self.text
+            .lines()
+            .filter_map(|line| ChecksumEntry::parse(line).ok())
+    }
+
+    /// Returns the entry for `file_name`, if there is one.
+    pub fn find(&self, file_name: &str) -> Option<ChecksumEntry<'a>> {
+        self.entries().find(|entry| entry.file_name == file_name)
+    }
+
+    /// Checks the content of `file_name`.
+    ///
+    /// `Error::ChecksumMismatch` is also returned if the file is not listed.
+    pub fn check(&self, file_name: &str, content: impl AsRef<[u8]>) -> Result<(), Error> {
+        self.find(file_name)
+            .ok_or(Error::ChecksumMismatch)?
+            .check(content)
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_signify() {
+    let pk = PublicKey::decode(
+        "untrusted comment: signify-rs demo public key
+RWQEC93KpsGY1ru5XOWxiNxzzmA1qw3mNk5Kbg01y1BOyfcPQW0vOIQp
+",
+    )
+    .unwrap();
+    let sk = SecretKey::decode(
+        "untrusted comment: signify-rs demo secret key
+RWRCSwAAAAB5yy4ID9IWOh3gaWDT6zAj6Xi5hsLcWz0EC93KpsGY1g6BU//xXcmvE753Yos+Fsw7fSpzK14Pro9zhh9r2Bu+u7lc5bGI3HPOYDWrDeY2TkpuDTXLUE7J9w9BbS84hCk=
+",
+        None,
+    )
+    .unwrap();
+    assert_eq!(sk.public_key(), pk);
+    let sig_text = "untrusted comment: signature from signify secret key
+RWQEC93KpsGY1ra0DC0VQbBys7cJn8ql2GwQT++FPD3DikMSpW5neGMeQBi4cuJjeZJkJCHipOQ0R45RLRgGFu1pFZqyRBfTLQM=
+";
+    let sig = SignatureBox::decode(sig_text).unwrap();
+    assert!(pk.verify("Was I tampered with?", &sig).is_ok());
+    assert!(pk.verify("Was I tampered with!", &sig).is_err());
+    assert_eq!(sk.sign("Was I tampered with?", None).to_string(), sig_text);
+    assert_eq!(SecretKey::decode(&sk.encode(), None).unwrap(), sk);
+    assert_eq!(PublicKey::decode(&pk.to_string()).unwrap(), pk);
+
+    let other = PublicKey::new(*pk.key(), [0u8; KEYNUM_BYTES]);
+    assert_eq!(
+        other.verify("Was I tampered with?", &sig),
+        Err(Error::InvalidPublicKey)
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_signify_encrypted_key() {
+    let encrypted = "untrusted comment: signify secret key
+RWRCSwAAABBaWlpaWlpaWlpaWlpaWlpaTBy29Gl3VRahssPU5fYHGHXCjMvQMQ/80MQw5eCHbFxlmkG3t/qdfuHqAphCY78wCo4K9xyn13R1LIREVTp2WFurSZiQsJCCocfij1iWg38=
+";
+    let pk = PublicKey::decode(
+        "untrusted comment: signify public key
+RWShssPU5fYHGNBKsjJ0K7SrOhNovUYV5ObQIkq3GgFrr4UgozLJd4c3
+",
+    )
+    .unwrap();
+    let sk = SecretKey::decode(encrypted, Some("correct horse")).unwrap();
+    assert_eq!(sk.public_key(), pk);
+    assert_eq!(
+        SecretKey::decode(encrypted, Some("wrong")),
+        Err(Error::InvalidSecretKey)
+    );
+    assert_eq!(
+        SecretKey::decode(encrypted, None),
+        Err(Error::InvalidSecretKey)
+    );
+
+    let kdf = KdfParams::new([0x5a; SALT_BYTES], 16);
+    assert_eq!(
+        sk.encode_encrypted("correct horse", &kdf).unwrap(),
+        encrypted
+    );
+}
+
+#[test]
+fn test_signify_checksums() {
+    let pk = PublicKey::decode(
+        "untrusted comment: xcheck public key
+RWTemyj+CVfeN0CSVnp1IwC8uhBDwl8h6rSdkNYQG5RFf81lqYkqQs5X
+",
+    )
+    .unwrap();
+    let signed = "untrusted comment: verify with xcheck.pub
+RWTemyj+CVfeNwmBVYc9ltNkG+tdj7FrZ4vGHtb8/Y6IvHiakfVyPwMc+YUdsBjhRImYNv3yyUPK/EnjO8z8dZQQIb6YCo9qpgQ=
+SHA256 (INSTALL.amd64) = 9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08
+SHA512 (bsd.rd) = ee26b0dd4af7e749aa1a8ee3c10ae9923f618980772e473f8819a5d4940e0db27ac185f8a0e1d5f84f88bc887fd67b143732c304cc5fa9ad8e6f57f50028a8ff
+";
+    let checksums = pk.verify_checksums(signed).unwrap();
+    assert_eq!(checksums.entries().count(), 2);
+    let entry = checksums.find("bsd.rd").unwrap();
+    assert_eq!(entry.algorithm(), ChecksumAlgorithm::Sha512);
+    assert!(checksums.check("INSTALL.amd64", "test").is_ok());
+    assert!(checksums.check("bsd.rd", "test").is_ok());
+    assert_eq!(
+        checksums.check("bsd.rd", "tset"),
+        Err(Error::ChecksumMismatch)
+    );
+    assert_eq!(
+        checksums.check("bsd.mp", "test"),
+        Err(Error::ChecksumMismatch)
+    );
+    let mut st = entry.verifier();
+    st.absorb("te");
+    st.absorb("st");
+    assert!(st.verify().is_ok());
+
+    let tampered = signed.replace("INSTALL.amd64", "INSTALL.arm64");
+    assert!(pk.verify_checksums(&tampered).is_err());
+}
@@ -0,0 +1,256 @@
+//! Text encodings.
+//!
+//! Public keys and signatures implement `Display`, `LowerHex` and `FromStr`,
+//! using lowercase hex strings. Keys, seeds, noise and signatures can be
+//! encoded as base64 (standard alphabet, with padding) with `to_base64()`
+//! and decoded with `from_base64()`, in constant time. Ed25519 public keys
+//! can also be encoded as multibase (base58btc) strings and `did:key`
+//! identifiers.
+//!
+//! Strings returned by `to_base64()` are regular `String`s, that are not
+//! wiped when dropped. Wiping the encoding of a secret is up to the caller.
+
+use core::fmt;
+use core::str::FromStr;
+
+use ct_codecs::{Base64, Decoder, Encoder, Hex};
+
+use super::common::*;
+use super::error::Error;
+
+#[cfg(all(feature = "blind-keys", not(feature = "disable-signatures")))]
+use super::ed25519::BlindPublicKey;
+#[cfg(not(feature = "disable-signatures"))]
+use super::ed25519::{Noise, PublicKey, SecretKey, Signature};
+#[cfg(feature = "x25519")]
+use super::x25519;
+
+/// Multicodec prefix of Ed25519 public keys.
+#[cfg(not(feature = "disable-signatures"))]
+const ED25519_MULTICODEC: [u8; 2] = [0xed, 0x01];
+
+/// Prefix of `did:key` identifiers.
+#[cfg(not(feature = "disable-signatures"))]
+const DID_KEY_PREFIX: &str = "did:key:";
+
+/// Size of the largest hex-encoded public object, with a `0x` prefix.
+const MAX_HEX_CHARS: usize = 2 + 64 * 2;
+
+/// Writes `bytes` as hex, optionally with a `0x` prefix, honoring the width,
+/// fill and alignment of the formatter.
+fn write_hex(f: &mut fmt::Formatter<'_>, bytes: &[u8], prefix: bool) -> fmt::Result {
+    let mut buf = [0u8; MAX_HEX_CHARS];
+    let offset = if prefix {
+        buf[..2].copy_from_slice(b"0x");
+        2
+    } else {
+        0
+    };
+    let hex_len = Hex::encode(&mut buf[offset..], bytes)
+        .map_err(|_| fmt::Error)?
+        .len();
+    let s = core::str::from_utf8(&buf[..offset + hex_len]).map_err(|_| fmt::Error)?;
+    f.pad(s)
+}
+
+fn hex_decode(out: &mut [u8], s: &str) -> Result<(), Error> {
+    let s = s.strip_prefix("0x").unwrap_or(s);
+    if s.len() != out.len() * 2 {
+        return Err(Error::ParseError);
+    }
+    Hex::decode(out, s, None).map_err(|_| Error::ParseError)?;
+    Ok(())
+}
+
+fn base64_decode(out: &mut [u8], s: &str) -> Result<(), Error> {
+    let len = Base64::decode(out, s, None)
+        .map_err(|_| Error::ParseError)?
+        .len();
+    if len != out.len() {
+        return Err(Error::ParseError);
+    }
+    Ok(())
+}
+
+/// Implements `to_base64()` and `from_base64()` for a type with `BYTES` and
+/// `new()`. Temporary copies made while decoding are wiped, so that it can
+/// be used for secrets, but the encoded string is not.
+macro_rules! impl_base64 {
+    ($t:ty) => {
+        impl_base64!(@ $t, "Encodes as a base64 string.");
+    };
+    ($t:ty, secret) => {
+        impl_base64!(
+            @ $t,
+            "Encodes the secret as a base64 string.\n\n\
+             The returned string is not wiped when dropped: the caller is \
+             responsible for wiping it, for example with `zeroize`."
+        );
+    };
+    (@ $t:ty, $to_doc:literal) => {
+        impl $t {
+            #[doc = $to_doc]
+            #[cfg(feature = "std")]
+            pub fn to_base64(&self) -> String {
+                Base64::encode_to_string(&self[..]).unwrap()
+            }
+
+            /// Decodes from a base64 string.
+            pub fn from_base64(s: &str) -> Result<Self, Error> {
+                let mut bytes = [0u8; <$t>::BYTES];
+                let res = base64_decode(&mut bytes, s).map(|_| <$t>::new(bytes));
+                Mem::wipe(&mut bytes);
+                res
+            }
+        }
+    };
+}
+
+/// Implements hex `Display`, `LowerHex` and `FromStr`, as well as base64
+/// helpers, for a public type with `BYTES` and `new()`.
+macro_rules! impl_text_public {
+    ($t:ty) => {
+        impl fmt::Display for $t {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write_hex(f, &self[..], false)
+            }
+        }
+
+        impl fmt::LowerHex for $t {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write_hex(f, &self[..], f.alternate())
+            }
+        }
+
+        impl FromStr for $t {
+            type Err = Error;
+
+            /// Parses a hex string, optionally prefixed with `0x`.
+            fn from_str(s: &str) -> Result<Self, Error> {
+                let mut bytes = [0u8; <$t>::BYTES];
+                hex_decode(&mut bytes, s)?;
+                Ok(<$t>::new(bytes))
+            }
+        }
+
+        impl_base64!($t);
+    };
+}
+
+#[cfg(not(feature = "disable-signatures"))]
+impl_text_public!(PublicKey);
+#[cfg(not(feature = "disable-signatures"))]
+impl_text_public!(Signature);
+#[cfg(all(feature = "blind-keys", not(feature = "disable-signatures")))]
+impl_text_public!(BlindPublicKey);
+#[cfg(feature = "x25519")]
+impl_text_public!(x25519::PublicKey);
+
+impl_base64!(Seed, secret);
+#[cfg(not(feature = "disable-signatures"))]
+impl_base64!(Noise, secret);
+#[cfg(not(feature = "disable-signatures"))]
+impl_base64!(SecretKey, secret);
+#[cfg(feature = "x25519")]
+impl_base64!(x25519::SecretKey, secret);
+
+#[cfg(not(feature = "disable-signatures"))]
+impl PublicKey {
+    /// Encodes the public key as a multibase string, using the base58btc
+    /// encoding of its multicodec representation (`z6Mk...`).
+    #[cfg(feature = "std")]
+    pub fn to_multibase(&self) -> String {
+        let mut bin = [0u8; ED25519_MULTICODEC.len() + PublicKey::BYTES];
+        bin[..ED25519_MULTICODEC.len()].copy_from_slice(&ED25519_MULTICODEC);
+        bin[ED25519_MULTICODEC.len()..].copy_from_slice(&self[..]);
+        let mut b58 = [0u8; 64];
+        let b58 = crate::base58::encode(&mut b58, &bin).unwrap();
+        format!("z{}", b58)
+    }
+
+    /// Decodes a public key from a multibase string. Only the base58btc
+    /// encoding is supported.
+    pub fn from_multibase(s: &str) -> Result<Self, Error> {
+        let b58 = s.strip_prefix('z').ok_or(Error::ParseError)?;
+        let mut bin = [0u8; ED25519_MULTICODEC.len() + PublicKey::BYTES];
+        let bin = crate::base58::decode(&mut bin, b58).map_err(|_| Error::ParseError)?;
+        match bin.strip_prefix(&ED25519_MULTICODEC[..]) {
+            Some(pk) if pk.len() == PublicKey::BYTES => PublicKey::from_slice(pk),
+            _ => Err(Error::ParseError),
+        }
+    }
+
+    /// Encodes the public key as a `did:key` identifier.
+    #[cfg(feature = "std")]
+    pub fn to_did_key(&self) -> String {
+        format!("{}{}", DID_KEY_PREFIX, self.to_multibase())
+    }
+
+    /// Decodes a public key from a `did:key` identifier. A fragment, such as
+    /// the one of a verification method identifier, is ignored.
+    pub fn from_did_key(s: &str) -> Result<Self, Error> {
+        let s = s.strip_prefix(DID_KEY_PREFIX).ok_or(Error::ParseError)?;
+        let s = s.split('#').next().unwrap_or(s);
+        PublicKey::from_multibase(s)
+    }
+}
+
+#[cfg(all(feature = "std", not(feature = "disable-signatures")))]
+#[test]
+fn test_text() {
+    use super::ed25519::KeyPair;
+
+    let kp = KeyPair::from_seed([42u8; 32].into());
+    let hex = kp.pk.to_string();
+    assert_eq!(hex, Hex::encode_to_string(*kp.pk).unwrap());
+    assert_eq!(format!("{:x}", kp.pk), hex);
+    assert_eq!(format!("{:#x}", kp.pk), format!("0x{}", hex));
+    assert_eq!(format!("{:>80}", kp.pk), format!("{:>80}", hex));
+    assert_eq!(
+        format!("{:-<#70x}", kp.pk),
+        format!("{:-<70}", format!("0x{}", hex))
+    );
+    assert_eq!(hex.parse::<PublicKey>().unwrap(), kp.pk);
+    assert_eq!(format!("0x{}", hex).parse::<PublicKey>().unwrap(), kp.pk);
+    assert_eq!(hex[2..].parse::<PublicKey>(), Err(Error::ParseError));
+    assert_eq!(
+        hex.replace('a', "g").parse::<PublicKey>(),
+        Err(Error::ParseError)
+    );
+
+    let signature = kp.sk.sign(b"message", None);
+    assert_eq!(
+        signature.to_string().parse::<Signature>().unwrap(),
+        signature
+    );
+
+    let b64 = kp.sk.to_base64();
+    assert_eq!(b64, Base64::encode_to_string(&kp.sk[..]).unwrap());
+    assert_eq!(SecretKey::from_base64(&b64).unwrap(), kp.sk);
+    assert_eq!(Seed::from_base64(&b64), Err(Error::ParseError));
+    assert_eq!(PublicKey::from_base64(&kp.pk.to_base64()).unwrap(), kp.pk);
+
+    let did = "did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK";
+    let pk = PublicKey::from_did_key(did).unwrap();
+    assert_eq!(
+        pk,
+        "2e6fcce36701dc791488e0d0b1745cc1e33a4c1c9fcc41c63bd343dbbe0970e6"
+            .parse()
+            .unwrap()
+    );
+    assert_eq!(pk.to_did_key(), did);
+    assert_eq!(
+        PublicKey::from_did_key(&format!("{}#{}", did, pk.to_multibase())).unwrap(),
+        pk
+    );
+    assert_eq!(PublicKey::from_multibase(&pk.to_multibase()).unwrap(), pk);
+    assert!(kp.pk.to_multibase().starts_with("z6Mk"));
+    assert_eq!(
+        PublicKey::from_multibase(&pk.to_multibase()[1..]),
+        Err(Error::ParseError)
+    );
+    assert_eq!(
+        PublicKey::from_did_key(&did[..did.len() - 1]),
+        Err(Error::ParseError)
+    );
+}
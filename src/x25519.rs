@@ -1,6 +1,10 @@
+use core::fmt;
+use core::hash::{Hash, Hasher};
 use core::ops::{Deref, DerefMut};
 
 use super::common::*;
+#[cfg(not(feature = "disable-signatures"))]
+use super::edwards25519;
 use super::error::Error;
 use super::field25519::*;
 
@@ -9,9 +13,28 @@ const POINT_BYTES: usize = 32;
 /// Non-uniform output of a scalar multiplication.
 /// This represents a point on the curve, and should not be used directly as a
 /// cipher key.
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Eq)]
 pub struct DHOutput([u8; DHOutput::BYTES]);
 
+impl fmt::Debug for DHOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("DHOutput(..)")
+    }
+}
+
+impl PartialEq for DHOutput {
+    /// Compares two DH outputs in constant time.
+    fn eq(&self, other: &Self) -> bool {
+        Mem::ct_eq(&self.0, &other.0)
+    }
+}
+
+impl Hash for DHOutput {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
 impl DHOutput {
     pub const BYTES: usize = 32;
 }
@@ -34,6 +57,13 @@ impl DerefMut for DHOutput {
     }
 }
 
+#[cfg(feature = "subtle")]
+impl subtle::ConstantTimeEq for DHOutput {
+    fn ct_eq(&self, other: &Self) -> subtle::Choice {
+        subtle::ConstantTimeEq::ct_eq(&self.0[..], &other.0[..])
+    }
+}
+
 impl From<DHOutput> for PublicKey {
     fn from(dh: DHOutput) -> Self {
         PublicKey(dh.0)
@@ -52,6 +82,16 @@ impl Drop for DHOutput {
     }
 }
 
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for DHOutput {
+    fn zeroize(&mut self) {
+        Mem::wipe(&mut self.0)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for DHOutput {}
+
 /// A public key.
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 pub struct PublicKey([u8; POINT_BYTES]);
@@ -104,34 +144,27 @@ impl PublicKey {
     #[cfg_attr(not(feature = "opt_size"), inline(always))]
     fn ladder(&self, s: &[u8], bits: usize) -> Result<[u8; POINT_BYTES], Error> {
         let x1 = Fe::from_bytes(&self.0);
-        let mut x2 = FE_ONE;
-        let mut z2 = FE_ZERO;
-        let mut x3 = x1;
-        let mut z3 = FE_ONE;
-        let mut swap: u8 = 0;
-        let mut pos = bits - 1;
-        loop {
-            let bit = (s[pos >> 3] >> (pos & 7)) & 1;
-            swap ^= bit;
-            Fe::cswap2(&mut x2, &mut x3, &mut z2, &mut z3, swap);
-            swap = bit;
-            let a = x2 + z2;
-            let b = x2 - z2;
-            let aa = a.square();
-            let bb = b.square();
-            x2 = aa * bb;
-            let e = aa - bb;
-            let da = (x3 - z3) * a;
-            let cb = (x3 + z3) * b;
-            x3 = (da + cb).square();
-            z3 = x1 * ((da - cb).square());
-            z2 = e * (bb + (e.mul32(121666)));
-            if pos == 0 {
-                break;
-            }
-            pos -= 1;
-        }
-        Fe::cswap2(&mut x2, &mut x3, &mut z2, &mut z3, swap);
+        #[cfg(all(
+            feature = "simd",
+            any(
+                target_arch = "x86_64",
+                target_arch = "aarch64",
+                all(target_arch = "wasm32", target_feature = "simd128")
+            )
+        ))]
+        let (mut x2, mut z2) = match super::x25519_simd::ladder(&x1, s, bits) {
+            Some(xz) => xz,
+            None => ladder_xz(&x1, s, bits),
+        };
+        #[cfg(not(all(
+            feature = "simd",
+            any(
+                target_arch = "x86_64",
+                target_arch = "aarch64",
+                all(target_arch = "wasm32", target_feature = "simd128")
+            )
+        )))]
+        let (mut x2, mut z2) = ladder_xz(&x1, s, bits);
         z2 = z2.invert();
         x2 = x2 * z2;
         if x2.is_zero() {
@@ -149,6 +182,43 @@ impl PublicKey {
     }
 }
 
+/// Computes the projective `(x2, z2)` coordinates of `s * x1`, in constant
+/// time.
+#[cfg_attr(feature = "opt_size", inline(never))]
+#[cfg_attr(not(feature = "opt_size"), inline(always))]
+fn ladder_xz(x1: &Fe, s: &[u8], bits: usize) -> (Fe, Fe) {
+    let x1 = *x1;
+    let mut x2 = FE_ONE;
+    let mut z2 = FE_ZERO;
+    let mut x3 = x1;
+    let mut z3 = FE_ONE;
+    let mut swap: u8 = 0;
+    let mut pos = bits - 1;
+    loop {
+        let bit = (s[pos >> 3] >> (pos & 7)) & 1;
+        swap ^= bit;
+        Fe::cswap2(&mut x2, &mut x3, &mut z2, &mut z3, swap);
+        swap = bit;
+        let a = x2 + z2;
+        let b = x2 - z2;
+        let aa = a.square();
+        let bb = b.square();
+        x2 = aa * bb;
+        let e = aa - bb;
+        let da = (x3 - z3) * a;
+        let cb = (x3 + z3) * b;
+        x3 = (da + cb).square();
+        z3 = x1 * ((da - cb).square());
+        z2 = e * (bb + (e.mul32(121666)));
+        if pos == 0 {
+            break;
+        }
+        pos -= 1;
+    }
+    Fe::cswap2(&mut x2, &mut x3, &mut z2, &mut z3, swap);
+    (x2, z2)
+}
+
 impl Deref for PublicKey {
     type Target = [u8; PublicKey::BYTES];
 
@@ -165,10 +235,45 @@ impl DerefMut for PublicKey {
     }
 }
 
+#[cfg(feature = "subtle")]
+impl subtle::ConstantTimeEq for PublicKey {
+    fn ct_eq(&self, other: &Self) -> subtle::Choice {
+        subtle::ConstantTimeEq::ct_eq(&self.0[..], &other.0[..])
+    }
+}
+
+#[cfg(feature = "subtle")]
+impl subtle::ConditionallySelectable for PublicKey {
+    fn conditional_select(a: &Self, b: &Self, choice: subtle::Choice) -> Self {
+        PublicKey(subtle::ConditionallySelectable::conditional_select(
+            &a.0, &b.0, choice,
+        ))
+    }
+}
+
 /// A secret key.
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Eq)]
 pub struct SecretKey([u8; SecretKey::BYTES]);
 
+impl fmt::Debug for SecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SecretKey(..)")
+    }
+}
+
+impl PartialEq for SecretKey {
+    /// Compares two secret keys in constant time.
+    fn eq(&self, other: &Self) -> bool {
+        Mem::ct_eq(&self.0, &other.0)
+    }
+}
+
+impl Hash for SecretKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state)
+    }
+}
+
 impl SecretKey {
     /// Number of bytes in a secret key.
     pub const BYTES: usize = 32;
@@ -200,7 +305,18 @@ impl SecretKey {
     /// Recover the public key
     pub fn recover_public_key(&self) -> Result<PublicKey, Error> {
         let sk = self.clamped();
-        Ok(PublicKey(PublicKey::base_point().ladder(&sk.0, 255)?))
+        #[cfg(all(feature = "fast-base", not(feature = "disable-signatures")))]
+        {
+            let pk = edwards25519::ge_scalarmult_base_x25519(&sk.0);
+            if Fe::from_bytes(&pk).is_zero() {
+                return Err(Error::WeakPublicKey);
+            }
+            Ok(PublicKey(pk))
+        }
+        #[cfg(not(all(feature = "fast-base", not(feature = "disable-signatures"))))]
+        {
+            Ok(PublicKey(PublicKey::base_point().ladder(&sk.0, 255)?))
+        }
     }
 
     /// Returns `Ok(())` if the given public key is the public counterpart of
@@ -208,7 +324,7 @@ impl SecretKey {
     /// Returns `Err(Error::InvalidPublicKey)` otherwise.
     pub fn validate_public_key(&self, pk: &PublicKey) -> Result<(), Error> {
         let recovered_pk = self.recover_public_key()?;
-        if recovered_pk != *pk {
+        if !Mem::ct_eq(&recovered_pk[..], &pk[..]) {
             return Err(Error::InvalidPublicKey);
         }
         Ok(())
@@ -237,6 +353,23 @@ impl DerefMut for SecretKey {
     }
 }
 
+#[cfg(feature = "subtle")]
+impl subtle::ConstantTimeEq for SecretKey {
+    fn ct_eq(&self, other: &Self) -> subtle::Choice {
+        subtle::ConstantTimeEq::ct_eq(&self.0[..], &other.0[..])
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for SecretKey {
+    fn zeroize(&mut self) {
+        Mem::wipe(&mut self.0)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for SecretKey {}
+
 /// A key pair.
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub struct KeyPair {
@@ -252,6 +385,19 @@ impl KeyPair {
     pub fn generate() -> KeyPair {
         let mut sk = [0u8; SecretKey::BYTES];
         getrandom::fill(&mut sk).expect("getrandom");
+        KeyPair::from_random_secret_key(sk)
+    }
+
+    /// Generates a new key pair using `rng`.
+    #[cfg(feature = "rand_core")]
+    pub fn generate_with_rng<R: rand_core::CryptoRng + ?Sized>(rng: &mut R) -> KeyPair {
+        let mut sk = [0u8; SecretKey::BYTES];
+        rng.fill_bytes(&mut sk);
+        KeyPair::from_random_secret_key(sk)
+    }
+
+    #[cfg(any(feature = "random", feature = "rand_core"))]
+    fn from_random_secret_key(sk: [u8; SecretKey::BYTES]) -> KeyPair {
         if Fe::from_bytes(&sk).is_zero() {
             panic!("All-zero secret key");
         }
@@ -268,11 +414,20 @@ impl KeyPair {
     }
 }
 
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for KeyPair {
+    fn zeroize(&mut self) {
+        zeroize::Zeroize::zeroize(&mut self.sk)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for KeyPair {}
+
 #[cfg(not(feature = "disable-signatures"))]
 mod from_ed25519 {
     use super::super::{
-        edwards25519, sha512, KeyPair as EdKeyPair, PublicKey as EdPublicKey,
-        SecretKey as EdSecretKey,
+        sha512, KeyPair as EdKeyPair, PublicKey as EdPublicKey, SecretKey as EdSecretKey,
     };
     use super::*;
 
@@ -366,6 +521,21 @@ fn test_x25519_rfc7748() {
     assert_eq!(shared_a, shared_b);
 }
 
+#[cfg(all(feature = "fast-base", not(feature = "disable-signatures")))]
+#[test]
+fn test_x25519_fixed_base() {
+    use super::sha512;
+
+    let mut sk = SecretKey::new([0u8; 32]);
+    for i in 0..64u8 {
+        let expected = PublicKey::base_point()
+            .ladder(&sk.clamped()[..], 255)
+            .unwrap();
+        assert_eq!(sk.recover_public_key().unwrap(), PublicKey(expected));
+        sk.copy_from_slice(&sha512::Hash::hash([i])[..32]);
+    }
+}
+
 #[cfg(not(feature = "disable-signatures"))]
 #[test]
 fn test_x25519_map() {
@@ -397,3 +567,31 @@ fn test_x25519_invalid_keypair() {
     assert!(kp2.sk.validate_public_key(&kp2.pk).is_ok());
     assert!(kp1.validate().is_ok());
 }
+
+#[cfg(all(
+    feature = "simd",
+    any(
+        target_arch = "x86_64",
+        target_arch = "aarch64",
+        all(target_arch = "wasm32", target_feature = "simd128")
+    )
+))]
+#[test]
+fn test_x25519_ladder_simd() {
+    use super::{sha512, x25519_simd};
+
+    let mut x1 = Fe::from_bytes(&PublicKey::base_point()[..]);
+    for i in 0..32u8 {
+        let s = sha512::Hash::hash([i]);
+        let bits = if i & 1 == 0 { 255 } else { 256 };
+        let (x2, z2) = ladder_xz(&x1, &s[..32], bits);
+        let expected = (x2 * z2.invert()).to_bytes();
+        for (x2, z2) in [
+            x25519_simd::ladder_portable(&x1, &s[..32], bits),
+            x25519_simd::ladder_backend(&x1, &s[..32], bits).unwrap_or((x2, z2)),
+        ] {
+            assert_eq!((x2 * z2.invert()).to_bytes(), expected);
+        }
+        x1 = Fe::from_bytes(&s[32..]);
+    }
+}
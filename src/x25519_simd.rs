@@ -0,0 +1,102 @@
+//! Montgomery ladder using four-way field arithmetic.
+//!
+//! The state `(x2, z2, x3, z3)` is kept in the lanes of a single `FeX4`, so
+//! that every step computes its ten multiplications and squarings as three
+//! vector multiplications.
+
+use super::field25519::{Fe, FE_ONE, FE_ZERO};
+use super::field25519_simd::{lanes, select, Backend, FeX4, Lanes};
+
+/// Computes the projective `(x2, z2)` coordinates of `s * x1`, in constant
+/// time.
+#[inline(always)]
+unsafe fn ladder_generic<V: Lanes>(x1: &Fe, s: &[u8], bits: usize) -> (Fe, Fe) {
+    let x1v = FeX4::<V>::new(&[FE_ONE, FE_ONE, FE_ONE, *x1]);
+    let mut v = FeX4::<V>::new(&[FE_ONE, FE_ZERO, *x1, FE_ONE]);
+    let mut swap: u8 = 0;
+    for pos in (0..bits).rev() {
+        let bit = (s[pos >> 3] >> (pos & 7)) & 1;
+        swap ^= bit;
+        v = v.cswap_halves(swap);
+        swap = bit;
+
+        // (A, B, D, C) = (x2 + z2, x2 - z2, x3 - z3, x3 + z3)
+        let p = v.permute::<{ lanes(0, 0, 2, 2) }>();
+        let q = v.permute::<{ lanes(1, 1, 3, 3) }>();
+        let t = p
+            .add(&q)
+            .blend::<{ select(false, true, true, false) }>(&p.sub(&q));
+
+        // (AA, BB, DA, CB)
+        let r = t.mul(&t.permute::<{ lanes(0, 1, 0, 1) }>());
+
+        // (AA, E, DA + CB, DA - CB) and (BB, BB + a24 * E, DA + CB, DA - CB)
+        let p = r.permute::<{ lanes(0, 0, 2, 2) }>();
+        let q = r.permute::<{ lanes(1, 1, 3, 3) }>();
+        let d = p.sub(&q);
+        let u = p
+            .blend::<{ select(false, true, false, true) }>(&d)
+            .blend::<{ select(false, false, true, false) }>(&p.add(&q));
+        let w = q
+            .blend::<{ select(false, true, false, false) }>(&q.add(&d.mul_small(121666)))
+            .blend::<{ select(false, false, true, true) }>(&u);
+
+        // (x2, z2, x3, z3) = (AA * BB, E * (BB + a24 * E), (DA + CB)^2, x1 * (DA - CB)^2)
+        v = u.mul(&w).mul(&x1v);
+    }
+    v = v.cswap_halves(swap);
+    let [x2, z2, _, _] = v.split();
+    (x2, z2)
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn ladder_avx2(x1: &Fe, s: &[u8], bits: usize) -> (Fe, Fe) {
+    ladder_generic::<core::arch::x86_64::__m256i>(x1, s, bits)
+}
+
+#[cfg(target_arch = "aarch64")]
+#[target_feature(enable = "neon")]
+unsafe fn ladder_neon(x1: &Fe, s: &[u8], bits: usize) -> (Fe, Fe) {
+    ladder_generic::<super::field25519_simd::U64x4>(x1, s, bits)
+}
+
+/// Computes the projective `(x2, z2)` coordinates of `s * x1` using the best
+/// available backend, or returns `None` if the scalar ladder is faster.
+///
+/// The four-way ladder is only faster than the scalar one with the 32-bit
+/// field arithmetic. With the 64-bit one, it is always slower.
+pub(crate) fn ladder(x1: &Fe, s: &[u8], bits: usize) -> Option<(Fe, Fe)> {
+    if cfg!(all(
+        target_pointer_width = "64",
+        not(feature = "u32-backend")
+    )) {
+        return None;
+    }
+    ladder_vector(x1, s, bits)
+}
+
+/// Computes the projective `(x2, z2)` coordinates of `s * x1` using the best
+/// available backend, or returns `None` if the CPU doesn't support any.
+fn ladder_vector(x1: &Fe, s: &[u8], bits: usize) -> Option<(Fe, Fe)> {
+    match Backend::detect()? {
+        #[cfg(target_arch = "x86_64")]
+        Backend::Avx2 => Some(unsafe { ladder_avx2(x1, s, bits) }),
+        #[cfg(target_arch = "aarch64")]
+        Backend::Neon => Some(unsafe { ladder_neon(x1, s, bits) }),
+        #[cfg(target_arch = "wasm32")]
+        Backend::Simd128 => {
+            Some(unsafe { ladder_generic::<super::field25519_simd::U64x4>(x1, s, bits) })
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) fn ladder_backend(x1: &Fe, s: &[u8], bits: usize) -> Option<(Fe, Fe)> {
+    ladder_vector(x1, s, bits)
+}
+
+#[cfg(test)]
+pub(crate) fn ladder_portable(x1: &Fe, s: &[u8], bits: usize) -> (Fe, Fe) {
+    unsafe { ladder_generic::<[u64; 4]>(x1, s, bits) }
+}
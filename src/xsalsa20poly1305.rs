@@ -0,0 +1,158 @@
+//! A small XSalsa20-Poly1305 implementation, compatible with NaCl's
+//! `crypto_secretbox`.
+
+use super::common::*;
+use super::error::Error;
+use super::poly1305::Poly1305;
+
+pub const KEY_BYTES: usize = 32;
+pub const NONCE_BYTES: usize = 24;
+pub const TAG_BYTES: usize = super::poly1305::TAG_BYTES;
+
+const SIGMA: [u32; 4] = [0x61707865, 0x3320646e, 0x79622d32, 0x6b206574];
+
+#[inline(always)]
+fn quarter_round(x: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize) {
+    x[b] ^= x[a].wrapping_add(x[d]).rotate_left(7);
+    x[c] ^= x[b].wrapping_add(x[a]).rotate_left(9);
+    x[d] ^= x[c].wrapping_add(x[b]).rotate_left(13);
+    x[a] ^= x[d].wrapping_add(x[c]).rotate_left(18);
+}
+
+fn rounds(x: &mut [u32; 16]) {
+    for _ in 0..10 {
+        quarter_round(x, 0, 4, 8, 12);
+        quarter_round(x, 5, 9, 13, 1);
+        quarter_round(x, 10, 14, 2, 6);
+        quarter_round(x, 15, 3, 7, 11);
+        quarter_round(x, 0, 1, 2, 3);
+        quarter_round(x, 5, 6, 7, 4);
+        quarter_round(x, 10, 11, 8, 9);
+        quarter_round(x, 15, 12, 13, 14);
+    }
+}
+
+fn load32_le(s: &[u8]) -> u32 {
+    u32::from_le_bytes([s[0], s[1], s[2], s[3]])
+}
+
+fn initial_state(key: &[u8; KEY_BYTES], input: &[u8; 16]) -> [u32; 16] {
+    let mut state = [0u32; 16];
+    for i in 0..4 {
+        state[i * 5] = SIGMA[i];
+        state[1 + i] = load32_le(&key[i * 4..]);
+        state[11 + i] = load32_le(&key[16 + i * 4..]);
+        state[6 + i] = load32_le(&input[i * 4..]);
+    }
+    state
+}
+
+/// Derives a subkey from a key and a 16-byte input.
+pub fn hsalsa20(key: &[u8; KEY_BYTES], input: &[u8; 16]) -> [u8; 32] {
+    let mut x = initial_state(key, input);
+    rounds(&mut x);
+    let mut out = [0u8; 32];
+    for (i, &j) in [0, 5, 10, 15, 6, 7, 8, 9].iter().enumerate() {
+        out[i * 4..][..4].copy_from_slice(&x[j].to_le_bytes());
+    }
+    Mem::wipe(&mut x);
+    out
+}
+
+struct XSalsa20 {
+    state: [u32; 16],
+}
+
+impl XSalsa20 {
+    fn new(key: &[u8; KEY_BYTES], nonce: &[u8; NONCE_BYTES]) -> Self {
+        let mut input = [0u8; 16];
+        input.copy_from_slice(&nonce[..16]);
+        let mut subkey = hsalsa20(key, &input);
+        input[..8].copy_from_slice(&nonce[16..]);
+        input[8..].copy_from_slice(&[0u8; 8]);
+        let state = initial_state(&subkey, &input);
+        Mem::wipe(&mut subkey);
+        XSalsa20 { state }
+    }
+
+    fn block(&mut self) -> [u8; 64] {
+        let mut x = self.state;
+        rounds(&mut x);
+        let mut out = [0u8; 64];
+        for (i, (x, s)) in x.iter().zip(self.state.iter()).enumerate() {
+            out[i * 4..][..4].copy_from_slice(&x.wrapping_add(*s).to_le_bytes());
+        }
+        let counter = ((self.state[9] as u64) << 32 | self.state[8] as u64).wrapping_add(1);
+        self.state[8] = counter as u32;
+        self.state[9] = (counter >> 32) as u32;
+        out
+    }
+
+    /// Derives the Poly1305 key from the first half of the first block, and
+    /// returns the second half, used to encrypt the first 32 bytes of data.
+    fn first_block(&mut self) -> (Poly1305, [u8; 32]) {
+        let mut block = self.block();
+        let mut poly_key = [0u8; 32];
+        poly_key.copy_from_slice(&block[..32]);
+        let poly = Poly1305::new(&poly_key);
+        let mut ks = [0u8; 32];
+        ks.copy_from_slice(&block[32..]);
+        Mem::wipe(&mut block);
+        Mem::wipe(&mut poly_key);
+        (poly, ks)
+    }
+
+    fn xor(&mut self, data: &mut [u8]) {
+        for chunk in data.chunks_mut(64) {
+            let mut ks = self.block();
+            for (d, k) in chunk.iter_mut().zip(ks.iter()) {
+                *d ^= k;
+            }
+            Mem::wipe(&mut ks);
+        }
+    }
+}
+
+impl Drop for XSalsa20 {
+    fn drop(&mut self) {
+        Mem::wipe(&mut self.state)
+    }
+}
+
+fn xor_all(salsa: &mut XSalsa20, ks: &mut [u8; 32], data: &mut [u8]) {
+    let len = data.len().min(32);
+    let (head, tail) = data.split_at_mut(len);
+    for (d, k) in head.iter_mut().zip(ks.iter()) {
+        *d ^= k;
+    }
+    Mem::wipe(ks);
+    salsa.xor(tail);
+}
+
+/// Encrypts `data` in place, and returns the authentication tag.
+pub fn seal(key: &[u8; KEY_BYTES], nonce: &[u8; NONCE_BYTES], data: &mut [u8]) -> [u8; TAG_BYTES] {
+    let mut salsa = XSalsa20::new(key, nonce);
+    let (mut poly, mut ks) = salsa.first_block();
+    xor_all(&mut salsa, &mut ks, data);
+    poly.update(data);
+    poly.finalize()
+}
+
+/// Verifies the tag and decrypts `data` in place.
+/// `data` is left untouched if verification fails.
+pub fn open(
+    key: &[u8; KEY_BYTES],
+    nonce: &[u8; NONCE_BYTES],
+    data: &mut [u8],
+    tag: &[u8; TAG_BYTES],
+) -> Result<(), Error> {
+    let mut salsa = XSalsa20::new(key, nonce);
+    let (mut poly, mut ks) = salsa.first_block();
+    poly.update(data);
+    if !Mem::ct_eq(&poly.finalize(), tag) {
+        Mem::wipe(&mut ks);
+        return Err(Error::DecryptionFailed);
+    }
+    xor_all(&mut salsa, &mut ks, data);
+    Ok(())
+}